@@ -0,0 +1,454 @@
+//! Classification of Google Cloud API errors.
+//!
+//! Client calls return `anyhow` errors wrapping the underlying gax error.
+//! This module digs the gax error back out and sorts it into a handful of
+//! categories so the UI can explain what went wrong (and only send the user
+//! to the authentication screen when credentials are actually the problem).
+
+use std::fmt;
+
+use google_cloud_gax::error::rpc::{Code, Status, StatusDetails};
+use google_cloud_gax::error::{CredentialsError, Error as GaxError};
+
+/// Broad category of an API failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Credentials are missing or invalid
+    Unauthenticated,
+    /// The refresh token has expired or was revoked
+    CredentialsExpired,
+    /// The caller lacks an IAM permission
+    PermissionDenied {
+        /// The missing permission, when the API names it
+        permission: Option<String>,
+    },
+    /// The Secret Manager (or Resource Manager) API is not enabled
+    ApiDisabled {
+        /// Console URL to enable the API
+        activation_url: Option<String>,
+    },
+    /// No usable quota project: none is set, or the caller may not bill it
+    QuotaProject,
+    /// The resource does not exist
+    NotFound,
+    /// The resource changed since it was read (aborted, or an etag
+    /// mismatch)
+    Conflict,
+    /// A quota or rate limit was exceeded
+    Quota,
+    /// The service could not be reached or timed out
+    Network,
+    /// Anything else
+    Other,
+}
+
+/// A classified API error with a user-facing message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// Error category
+    pub kind: ErrorKind,
+    /// Message from the service (or the error itself)
+    pub message: String,
+}
+
+impl ApiError {
+    /// Classifies an error returned by one of the client wrappers.
+    pub fn classify(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<GaxError>() {
+                return Self::from_gax(e);
+            }
+            if let Some(e) = cause.downcast_ref::<CredentialsError>() {
+                return Self::from_credentials(e);
+            }
+            if cause
+                .downcast_ref::<tokio::time::error::Elapsed>()
+                .is_some()
+            {
+                return Self {
+                    kind: ErrorKind::Network,
+                    message: "request timed out".to_string(),
+                };
+            }
+            if let Some(e) = cause.downcast_ref::<google_cloud_gax::client_builder::Error>() {
+                if e.is_default_credentials() {
+                    return Self {
+                        kind: ErrorKind::Unauthenticated,
+                        message: e.to_string(),
+                    };
+                }
+            }
+        }
+        Self {
+            kind: ErrorKind::Other,
+            message: format!("{err:#}"),
+        }
+    }
+
+    /// Classifies a gax error.
+    fn from_gax(err: &GaxError) -> Self {
+        if let Some(status) = err.status() {
+            return Self::from_status(status);
+        }
+        if err.is_authentication() {
+            if let Some(e) = std::error::Error::source(err)
+                .and_then(|source| source.downcast_ref::<CredentialsError>())
+            {
+                return Self::from_credentials(e);
+            }
+        }
+        let kind = if err.is_authentication() {
+            ErrorKind::Unauthenticated
+        } else if err.is_timeout() || err.is_connect() || err.is_io() || err.is_transport() {
+            ErrorKind::Network
+        } else {
+            match err.http_status_code() {
+                Some(401) => ErrorKind::Unauthenticated,
+                Some(403) => ErrorKind::PermissionDenied { permission: None },
+                Some(404) => ErrorKind::NotFound,
+                Some(429) => ErrorKind::Quota,
+                Some(502..=504) => ErrorKind::Network,
+                _ => ErrorKind::Other,
+            }
+        };
+        Self {
+            kind,
+            message: err.to_string(),
+        }
+    }
+
+    /// Classifies a failure to load or refresh credentials.
+    ///
+    /// The OAuth token endpoint answers `invalid_grant` when a refresh token
+    /// has expired or been revoked, and `invalid_rapt` when Google requires
+    /// the user to sign in again.
+    fn from_credentials(err: &CredentialsError) -> Self {
+        let message = err.to_string();
+        let kind = if message.contains("invalid_grant") || message.contains("invalid_rapt") {
+            ErrorKind::CredentialsExpired
+        } else if err.is_transient() {
+            ErrorKind::Network
+        } else {
+            ErrorKind::Unauthenticated
+        };
+        Self { kind, message }
+    }
+
+    /// Classifies an RPC status.
+    fn from_status(status: &Status) -> Self {
+        let kind = match status.code {
+            Code::Unauthenticated => ErrorKind::Unauthenticated,
+            Code::PermissionDenied => {
+                if is_quota_project_problem(status) {
+                    ErrorKind::QuotaProject
+                } else if is_service_disabled(status) {
+                    ErrorKind::ApiDisabled {
+                        activation_url: activation_url(status),
+                    }
+                } else {
+                    ErrorKind::PermissionDenied {
+                        permission: missing_permission(status),
+                    }
+                }
+            }
+            Code::NotFound => ErrorKind::NotFound,
+            Code::Aborted => ErrorKind::Conflict,
+            // Also returned for versions in the wrong state, which a retry
+            // with a fresh etag cannot fix
+            Code::FailedPrecondition if is_etag_mismatch(status) => ErrorKind::Conflict,
+            Code::ResourceExhausted => ErrorKind::Quota,
+            Code::Unavailable | Code::DeadlineExceeded => ErrorKind::Network,
+            _ => ErrorKind::Other,
+        };
+        Self {
+            kind,
+            message: status.message.clone(),
+        }
+    }
+
+    /// Classifies the error of a call once, for both the status bar and the
+    /// audit log: returns the classified result and the error text to record.
+    pub fn classify_result<T>(result: anyhow::Result<T>) -> (Result<T, Self>, Option<String>) {
+        let result = result.map_err(|e| Self::classify(&e));
+        let error = result.as_ref().err().map(ToString::to_string);
+        (result, error)
+    }
+
+    /// Returns true if the user needs to (re-)authenticate.
+    pub fn is_auth(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::Unauthenticated | ErrorKind::CredentialsExpired
+        )
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Unauthenticated => write!(f, "Not authenticated: {}", self.message),
+            ErrorKind::CredentialsExpired => {
+                write!(f, "Credentials expired or revoked - sign in again")
+            }
+            ErrorKind::PermissionDenied {
+                permission: Some(permission),
+            } => write!(f, "Permission denied: missing {permission}"),
+            ErrorKind::PermissionDenied { permission: None } => {
+                write!(f, "Permission denied: {}", self.message)
+            }
+            ErrorKind::ApiDisabled {
+                activation_url: Some(url),
+            } => write!(f, "API not enabled - enable it at {url}"),
+            ErrorKind::ApiDisabled {
+                activation_url: None,
+            } => write!(f, "API not enabled: {}", self.message),
+            ErrorKind::QuotaProject => write!(
+                f,
+                "No usable quota project - set quota_project in the config or run \
+                 'gcloud auth application-default set-quota-project PROJECT'"
+            ),
+            ErrorKind::NotFound => write!(f, "Not found: {}", self.message),
+            ErrorKind::Conflict => write!(
+                f,
+                "Changed by someone else since it was loaded: {}",
+                self.message
+            ),
+            ErrorKind::Quota => write!(f, "Quota exceeded: {}", self.message),
+            ErrorKind::Network => write!(f, "Network error: {}", self.message),
+            ErrorKind::Other => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Looks up the `ErrorInfo` detail of a status, if any.
+fn error_info(status: &Status) -> Option<&google_cloud_rpc::model::ErrorInfo> {
+    status.details.iter().find_map(|d| match d {
+        StatusDetails::ErrorInfo(info) => Some(info),
+        _ => None,
+    })
+}
+
+/// Returns true if a failed-precondition status is about a stale etag.
+fn is_etag_mismatch(status: &Status) -> bool {
+    if let Some(info) = error_info(status) {
+        if info.reason.to_ascii_uppercase().contains("ETAG") {
+            return true;
+        }
+    }
+    status.message.to_ascii_lowercase().contains("etag")
+}
+
+/// Returns true if a permission-denied status means the API is disabled.
+fn is_service_disabled(status: &Status) -> bool {
+    if let Some(info) = error_info(status) {
+        return info.reason == "SERVICE_DISABLED";
+    }
+    status.message.contains("has not been used in project")
+        || status.message.contains("it is disabled")
+}
+
+/// Projects owning gcloud's OAuth client. User credentials without a quota
+/// project are billed to these, where our APIs are never enabled.
+const GCLOUD_CLIENT_PROJECTS: [&str; 2] = ["764086051850", "32555940559"];
+
+/// Returns true if a permission-denied status is about the quota project.
+fn is_quota_project_problem(status: &Status) -> bool {
+    if let Some(info) = error_info(status) {
+        if info.reason == "USER_PROJECT_DENIED" {
+            return true;
+        }
+        if info.reason == "SERVICE_DISABLED" {
+            let consumer = info.metadata.get("consumer").map_or("", String::as_str);
+            return GCLOUD_CLIENT_PROJECTS
+                .iter()
+                .any(|p| consumer.ends_with(p) || status.message.contains(p));
+        }
+    }
+    status.message.contains("requires a quota project")
+        || GCLOUD_CLIENT_PROJECTS
+            .iter()
+            .any(|p| status.message.contains(&format!("project {p}")))
+}
+
+/// Extracts the console URL for enabling a disabled API.
+fn activation_url(status: &Status) -> Option<String> {
+    if let Some(url) = error_info(status).and_then(|i| i.metadata.get("activationUrl")) {
+        return Some(url.clone());
+    }
+    let start = status.message.find("https://")?;
+    let url = status.message[start..]
+        .split_whitespace()
+        .next()?
+        .trim_end_matches(['.', ',', ')']);
+    Some(url.to_string())
+}
+
+/// Extracts the missing permission from a permission-denied status.
+///
+/// Secret Manager reports it in `ErrorInfo` metadata; older messages read
+/// "Permission 'secretmanager.secrets.list' denied ...".
+fn missing_permission(status: &Status) -> Option<String> {
+    if let Some(permission) = error_info(status).and_then(|i| i.metadata.get("permission")) {
+        return Some(permission.clone());
+    }
+    let rest = status.message.split("Permission '").nth(1)?;
+    let end = rest.find('\'')?;
+    Some(rest[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use google_cloud_rpc::model::ErrorInfo;
+
+    fn service_error(code: Code, message: &str) -> anyhow::Error {
+        let status = Status::default().set_code(code).set_message(message);
+        Err::<(), _>(GaxError::service(status))
+            .context("Failed to list secrets")
+            .unwrap_err()
+    }
+
+    fn with_info(code: Code, message: &str, info: ErrorInfo) -> anyhow::Error {
+        let status = Status::default()
+            .set_code(code)
+            .set_message(message)
+            .set_details([StatusDetails::ErrorInfo(info)]);
+        anyhow::Error::new(GaxError::service(status))
+    }
+
+    #[test]
+    fn test_failed_precondition_is_a_conflict_only_for_etags() {
+        let state = ApiError::classify(&service_error(
+            Code::FailedPrecondition,
+            "Secret Version [projects/1/secrets/api-key/versions/2] is in DISABLED state.",
+        ));
+        assert_eq!(state.kind, ErrorKind::Other);
+        assert!(state.to_string().contains("DISABLED state"));
+
+        let etag = ApiError::classify(&service_error(
+            Code::FailedPrecondition,
+            "The etag provided in the request does not match the current etag",
+        ));
+        assert_eq!(etag.kind, ErrorKind::Conflict);
+        assert!(etag.to_string().contains("does not match the current etag"));
+    }
+
+    #[test]
+    fn test_classifies_status_codes() {
+        let cases = [
+            (Code::Unauthenticated, ErrorKind::Unauthenticated),
+            (Code::NotFound, ErrorKind::NotFound),
+            (Code::Aborted, ErrorKind::Conflict),
+            (Code::FailedPrecondition, ErrorKind::Other),
+            (Code::ResourceExhausted, ErrorKind::Quota),
+            (Code::Unavailable, ErrorKind::Network),
+            (Code::DeadlineExceeded, ErrorKind::Network),
+            (Code::Internal, ErrorKind::Other),
+        ];
+        for (code, kind) in cases {
+            assert_eq!(ApiError::classify(&service_error(code, "x")).kind, kind);
+        }
+        assert!(ApiError::classify(&service_error(Code::Unauthenticated, "x")).is_auth());
+        assert!(!ApiError::classify(&service_error(Code::PermissionDenied, "x")).is_auth());
+    }
+
+    #[test]
+    fn test_permission_from_message_and_metadata() {
+        let err = service_error(
+            Code::PermissionDenied,
+            "Permission 'secretmanager.secrets.list' denied for resource 'projects/p'",
+        );
+        let api = ApiError::classify(&err);
+        assert_eq!(
+            api.kind,
+            ErrorKind::PermissionDenied {
+                permission: Some("secretmanager.secrets.list".to_string())
+            }
+        );
+        assert_eq!(
+            api.to_string(),
+            "Permission denied: missing secretmanager.secrets.list"
+        );
+
+        let info = ErrorInfo::new()
+            .set_reason("IAM_PERMISSION_DENIED")
+            .set_metadata([("permission", "secretmanager.versions.access")]);
+        let api = ApiError::classify(&with_info(Code::PermissionDenied, "denied", info));
+        assert_eq!(
+            api.kind,
+            ErrorKind::PermissionDenied {
+                permission: Some("secretmanager.versions.access".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_api_disabled_with_activation_url() {
+        let url = "https://console.developers.google.com/apis/api/secretmanager.googleapis.com/overview?project=p";
+        let info = ErrorInfo::new()
+            .set_reason("SERVICE_DISABLED")
+            .set_metadata([("activationUrl", url)]);
+        let api = ApiError::classify(&with_info(Code::PermissionDenied, "disabled", info));
+        assert_eq!(
+            api.kind,
+            ErrorKind::ApiDisabled {
+                activation_url: Some(url.to_string())
+            }
+        );
+
+        let err = service_error(
+            Code::PermissionDenied,
+            &format!("Secret Manager API has not been used in project p before or it is disabled. Enable it by visiting {url} then retry."),
+        );
+        assert_eq!(
+            ApiError::classify(&err).to_string(),
+            format!("API not enabled - enable it at {url}")
+        );
+    }
+
+    #[test]
+    fn test_expired_credentials() {
+        let err = CredentialsError::from_msg(
+            false,
+            r#"failed to refresh user access token, body=<{"error": "invalid_grant"}>"#,
+        );
+        let api = ApiError::classify(&anyhow::Error::new(err).context("Failed to refresh"));
+        assert_eq!(api.kind, ErrorKind::CredentialsExpired);
+        assert!(api.is_auth());
+
+        let err = GaxError::authentication(CredentialsError::from_msg(true, "connection reset"));
+        assert_eq!(
+            ApiError::classify(&anyhow::Error::new(err)).kind,
+            ErrorKind::Network
+        );
+    }
+
+    #[test]
+    fn test_quota_project_errors() {
+        let info = ErrorInfo::new()
+            .set_reason("SERVICE_DISABLED")
+            .set_metadata([("consumer", "projects/764086051850")]);
+        let api = ApiError::classify(&with_info(Code::PermissionDenied, "disabled", info));
+        assert_eq!(api.kind, ErrorKind::QuotaProject);
+
+        let err = service_error(
+            Code::PermissionDenied,
+            "Your application is authenticating by using local Application Default Credentials. The secretmanager.googleapis.com API requires a quota project, which is not set by default.",
+        );
+        assert_eq!(ApiError::classify(&err).kind, ErrorKind::QuotaProject);
+
+        let info = ErrorInfo::new().set_reason("USER_PROJECT_DENIED");
+        let api = ApiError::classify(&with_info(Code::PermissionDenied, "denied", info));
+        assert_eq!(api.kind, ErrorKind::QuotaProject);
+    }
+
+    #[test]
+    fn test_unrelated_error_is_other() {
+        let api = ApiError::classify(&anyhow::anyhow!("boom"));
+        assert_eq!(api.kind, ErrorKind::Other);
+        assert_eq!(api.to_string(), "boom");
+    }
+}
@@ -3,12 +3,33 @@
 //! This module contains the core application state, view management,
 //! and event handling logic.
 
-use anyhow::Result;
-use ratatui::widgets::ListState;
-
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use ratatui::widgets::{ListState, TableState};
+
+use crate::api_error::{ApiError, ErrorKind};
+use crate::audit::{AuditAction, AuditLog, AuditRecord};
+use crate::config::{Column, Config};
+use crate::connection::{location_label, ConnectionOptions, CredentialSource};
+use crate::constants::{CONFIRM_INPUT_DELAY, VERSION_SUMMARY_CONCURRENCY};
 use crate::event::Action;
-use crate::project_client::{self, ProjectInfo};
-use crate::secret_client::{SecretClient, SecretInfo, VersionInfo, VersionState};
+use crate::expiration;
+use crate::filters::FilterHistory;
+use crate::gcloud::{self, GcloudConfig};
+use crate::iam::{self, IamPolicy};
+use crate::keymap::{Keymap, Scope};
+use crate::logging;
+use crate::project_client::{ProjectClient, ProjectInfo};
+use crate::secret_client::{
+    format_ttl, ReplicationPolicy, SecretClient, SecretInfo, VersionInfo, VersionState,
+};
+use crate::tag_client::{TagBinding, TagClient};
+use crate::ui::theme::Theme;
 
 /// The different views/screens in the application.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,30 +40,264 @@ pub enum View {
     SecretsList,
     /// Details and versions of a specific secret
     SecretDetail,
+    /// IAM policy of a specific secret (a tab of the secret detail)
+    SecretIam,
+    /// Tag bindings of a specific secret (a tab of the secret detail)
+    SecretTags,
     /// Text input mode (for creating secrets, adding values, etc.)
     Input(InputMode),
     /// Confirmation dialog (for destructive actions)
     Confirm(ConfirmAction),
+    /// A write was rejected because its target changed since it was loaded
+    Conflict(Conflict),
     /// Project selector dialog
     ProjectSelector,
+    /// gcloud named configuration selector dialog
+    ConfigSelector,
+    /// Secret location selector dialog
+    LocationSelector,
+    /// Audit log of modifying actions
+    History,
 }
 
 /// Different input modes for text entry.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputMode {
     /// Creating a new secret (entering the name)
-    NewSecretName,
+    NewSecretName {
+        /// Location the secret is created in (`None` for global)
+        location: Option<String>,
+    },
     /// Adding a new version (entering the value)
     NewVersionValue,
+    /// Granting a role (entering the member and an optional role)
+    GrantMember,
+    /// Setting, extending or removing a secret's expiration
+    Expiration,
+    /// Setting or removing a secret's version destroy TTL
+    DestroyTtl,
+    /// Binding a tag value (entering the namespaced value or ID)
+    BindTag,
+    /// Filtering the secrets list (entering an API filter expression)
+    SecretsFilter,
+    /// Filtering the versions list (entering an API filter expression)
+    VersionsFilter,
 }
 
 /// Actions that require confirmation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmAction {
     /// Delete a secret
-    DeleteSecret(String),
+    DeleteSecret {
+        /// Secret name
+        secret: String,
+        /// Location of the secret (`None` for global)
+        location: Option<String>,
+        /// Etag of the secret when the dialog opened
+        etag: String,
+    },
     /// Destroy a secret version
-    DestroyVersion(String, String),
+    DestroyVersion {
+        /// Secret name
+        secret: String,
+        /// Version number
+        version: String,
+        /// Location of the secret (`None` for global)
+        location: Option<String>,
+        /// Etag of the version when the dialog opened
+        etag: String,
+    },
+    /// Remove a member from a role binding of a secret
+    RemoveIamMember {
+        /// Secret name
+        secret: String,
+        /// Index of the binding in the loaded policy
+        binding: usize,
+        /// Role of the binding
+        role: String,
+        /// Member to remove
+        member: String,
+    },
+    /// Grant a role to `allUsers` or `allAuthenticatedUsers`
+    GrantPublicAccess {
+        /// Secret name
+        secret: String,
+        /// Role to grant
+        role: String,
+        /// Public member
+        member: String,
+    },
+    /// Unbind a tag value from a secret
+    UnbindTag {
+        /// Secret name
+        secret: String,
+        /// Binding to remove
+        binding: TagBinding,
+    },
+}
+
+/// Writes that send the etag of the secret or version they change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteKind {
+    /// Enable a version
+    EnableVersion,
+    /// Disable a version
+    DisableVersion,
+    /// Destroy a version
+    DestroyVersion,
+    /// Delete a secret
+    DeleteSecret,
+    /// Set (Unix seconds) or remove the expiration of a secret
+    SetExpiration(Option<i64>),
+    /// Set (seconds) or remove the delay before versions are destroyed
+    SetDestroyTtl(Option<i64>),
+}
+
+impl WriteKind {
+    /// Verb used in status messages.
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::EnableVersion => "enable",
+            Self::DisableVersion => "disable",
+            Self::DestroyVersion => "destroy",
+            Self::DeleteSecret => "delete",
+            Self::SetExpiration(_) => "change expiration",
+            Self::SetDestroyTtl(_) => "change destroy TTL",
+        }
+    }
+
+    /// Detail recorded in the audit log.
+    fn detail(self) -> Option<String> {
+        match self {
+            Self::SetExpiration(Some(expire_time)) => Some(expiration::format_time(expire_time)),
+            Self::SetExpiration(None) => Some("never".to_string()),
+            Self::SetDestroyTtl(Some(ttl)) => Some(format_ttl(ttl)),
+            Self::SetDestroyTtl(None) => Some("off".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Action recorded in the audit log.
+    fn audit_action(self) -> AuditAction {
+        match self {
+            Self::EnableVersion => AuditAction::EnableVersion,
+            Self::DisableVersion => AuditAction::DisableVersion,
+            Self::DestroyVersion => AuditAction::DestroyVersion,
+            Self::DeleteSecret => AuditAction::DeleteSecret,
+            Self::SetExpiration(_) => AuditAction::UpdateExpiration,
+            Self::SetDestroyTtl(_) => AuditAction::UpdateDestroyTtl,
+        }
+    }
+}
+
+/// A write to a secret or one of its versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Write {
+    /// What is done
+    pub kind: WriteKind,
+    /// Secret name
+    pub secret: String,
+    /// Location of the secret (`None` for global)
+    pub location: Option<String>,
+    /// Version number (`None` for writes to the secret itself)
+    pub version: Option<String>,
+}
+
+/// A write rejected because its target changed since it was loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The rejected write
+    pub write: Write,
+    /// The target as now stored: (field, value)
+    pub current: Vec<(&'static str, String)>,
+    /// Etag of the target as now stored, sent when retrying
+    pub etag: String,
+}
+
+impl Conflict {
+    /// Returns the typed confirmation a retry needs, for deletes and
+    /// destroys, which cannot be undone and must be confirmed again against
+    /// the target as now stored.
+    pub fn reconfirm(&self) -> Option<ConfirmAction> {
+        let write = self.write.clone();
+        match (write.kind, write.version) {
+            (WriteKind::DeleteSecret, _) => Some(ConfirmAction::DeleteSecret {
+                secret: write.secret,
+                location: write.location,
+                etag: self.etag.clone(),
+            }),
+            (WriteKind::DestroyVersion, Some(version)) => Some(ConfirmAction::DestroyVersion {
+                secret: write.secret,
+                version,
+                location: write.location,
+                etag: self.etag.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Describes a version as now stored.
+    fn from_version(write: Write, version: &VersionInfo) -> Self {
+        let mut current = vec![
+            ("State", version.state.to_string()),
+            ("Created", version.create_time.clone()),
+        ];
+        if let Some(time) = &version.destroy_time {
+            current.push(("Destroyed", time.clone()));
+        }
+        if let Some(time) = version.scheduled_destroy_time {
+            current.push(("Destroying", expiration::format_time(time)));
+        }
+        current.push(("Etag", version.etag.clone()));
+        Self {
+            write,
+            current,
+            etag: version.etag.clone(),
+        }
+    }
+
+    /// Describes a secret as now stored.
+    fn from_secret(write: Write, secret: &SecretInfo) -> Self {
+        let pairs = |pairs: &[(String, String)]| {
+            if pairs.is_empty() {
+                "none".to_string()
+            } else {
+                pairs
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+        let aliases: Vec<(String, String)> = secret
+            .version_aliases
+            .iter()
+            .map(|(alias, version)| (alias.clone(), version.to_string()))
+            .collect();
+        Self {
+            write,
+            current: vec![
+                ("Labels", pairs(&secret.labels)),
+                ("Annotations", pairs(&secret.annotations)),
+                ("Aliases", pairs(&aliases)),
+                (
+                    "Expires",
+                    secret
+                        .expire_time
+                        .map_or_else(|| "never".to_string(), expiration::format_time),
+                ),
+                (
+                    "Destroy TTL",
+                    secret
+                        .version_destroy_ttl
+                        .clone()
+                        .unwrap_or_else(|| "off".to_string()),
+                ),
+                ("Etag", secret.etag.clone()),
+            ],
+            etag: secret.etag.clone(),
+        }
+    }
 }
 
 /// Actions that need to be handled by the main loop.
@@ -63,12 +318,143 @@ pub struct StatusMessage {
     pub is_error: bool,
 }
 
+/// Secrets listed across the browsed locations.
+#[derive(Debug, Default)]
+struct Listing {
+    /// Secrets of the locations that could be listed, in location order
+    secrets: Vec<SecretInfo>,
+    /// Locations that could not be listed, with the error
+    failures: Vec<(Option<String>, anyhow::Error)>,
+    /// How many listed secrets have no version summary because their
+    /// versions could not be listed
+    missing_summaries: usize,
+}
+
+/// Which secrets and versions are listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretFilter {
+    /// Only secrets not fully protected by CMEK
+    pub non_cmek: bool,
+    /// Filter expression sent to the API when listing secrets
+    pub secrets: Option<String>,
+    /// Filter expression sent to the API when listing versions
+    pub versions: Option<String>,
+}
+
+/// How the secrets list is sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretSort {
+    /// Column sorted by
+    pub column: Column,
+    /// Largest, latest or last first
+    pub descending: bool,
+}
+
+impl SecretSort {
+    /// Returns an ascending sort by the column after the current one;
+    /// the index column is skipped.
+    pub fn next(current: Option<Self>, columns: &[Column]) -> Self {
+        let sortable: Vec<Column> = columns
+            .iter()
+            .copied()
+            .filter(|c| *c != Column::Index)
+            .collect();
+        let next = current
+            .and_then(|sort| sortable.iter().position(|c| *c == sort.column))
+            .map_or(0, |i| (i + 1) % sortable.len());
+        Self {
+            column: sortable.get(next).copied().unwrap_or(Column::Name),
+            descending: false,
+        }
+    }
+
+    /// Compares two secrets by the sorted column, then by name.
+    ///
+    /// Secrets without a value in the column (no rotation, no expiration,
+    /// versions not loaded) are listed last in both directions.
+    pub fn compare(self, a: &SecretInfo, b: &SecretInfo) -> Ordering {
+        let ordering = match self.column {
+            Column::Index | Column::Name => self.by(Some(&a.short_name), Some(&b.short_name)),
+            Column::Created => self.by(Some(&a.create_time), Some(&b.create_time)),
+            Column::Labels => self.by(
+                (!a.labels.is_empty()).then_some(&a.labels),
+                (!b.labels.is_empty()).then_some(&b.labels),
+            ),
+            Column::Replication => self.by(Some(replica_locations(a)), Some(replica_locations(b))),
+            Column::Location => self.by(Some(&a.location), Some(&b.location)),
+            Column::LatestVersion => self.by(
+                a.versions.and_then(|v| v.latest),
+                b.versions.and_then(|v| v.latest),
+            ),
+            Column::EnabledVersions => {
+                self.by(a.versions.map(|v| v.enabled), b.versions.map(|v| v.enabled))
+            }
+            Column::Rotation => self.by(next_rotation(a), next_rotation(b)),
+            Column::Expiration => self.by(a.expire_time, b.expire_time),
+            Column::Topics => self.by(Some(a.topics.len()), Some(b.topics.len())),
+        };
+        ordering
+            .then_with(|| a.short_name.cmp(&b.short_name))
+            .then_with(|| a.location.cmp(&b.location))
+    }
+
+    /// Orders two values in the sort direction, missing values last.
+    fn by<T: Ord>(self, a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) if self.descending => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        }
+    }
+}
+
+/// Returns the replica locations of a secret (none for automatic replication).
+fn replica_locations(secret: &SecretInfo) -> &[String] {
+    match &secret.replication {
+        ReplicationPolicy::Automatic => &[],
+        ReplicationPolicy::UserManaged(locations) => locations,
+    }
+}
+
+/// Returns the next rotation time of a secret (`YYYY-MM-DD`).
+fn next_rotation(secret: &SecretInfo) -> Option<&str> {
+    secret.rotation.as_ref()?.next_rotation_time.as_deref()
+}
+
 /// Main application state.
 pub struct App {
     /// Google Cloud project ID
     pub project_id: String,
-    /// Secret Manager client (initialized lazily)
-    client: Option<SecretClient>,
+    /// How API clients connect (location, impersonation)
+    pub connection: ConnectionOptions,
+    /// Service account from `--impersonate-service-account`; overrides the config
+    pub impersonation_override: Option<String>,
+    /// Principal API calls are made as, when known
+    pub principal: Option<String>,
+    /// Where the credentials come from
+    pub credential_source: CredentialSource,
+    /// Why the sign-in screen is shown
+    pub auth_error: Option<String>,
+    /// When set, actions that modify secrets are refused
+    pub read_only: bool,
+    /// User configuration
+    pub config: Config,
+    /// Key bindings (built from the config)
+    pub keymap: Keymap,
+    /// Color theme used by the renderers
+    pub theme: Theme,
+    /// Secret Manager clients, one per browsed location (initialized lazily)
+    clients: Vec<SecretClient>,
+    /// Tag bindings clients by secret location (initialized lazily)
+    tag_clients: Vec<(Option<String>, TagClient)>,
+    /// Resource Manager projects client (initialized lazily)
+    project_client: Option<ProjectClient>,
+    /// Global Secret Manager client for listing locations while global is
+    /// not browsed (initialized lazily)
+    location_client: Option<SecretClient>,
+    /// Locations whose secrets are listed (`None` is global); new secrets
+    /// are created in the first unless a secret is selected
+    pub locations: Vec<Option<String>>,
     /// Current view/screen
     pub current_view: View,
     /// Previous view (for going back)
@@ -82,7 +468,15 @@ pub struct App {
     /// List of secrets
     pub secrets: Vec<SecretInfo>,
     /// Selection state for the secrets list
-    pub secrets_state: ListState,
+    pub secrets_state: TableState,
+    /// Which secrets and versions are listed
+    pub filter: SecretFilter,
+    /// Recently used filter expressions
+    pub filter_history: FilterHistory,
+    /// Position in `filter_history` recalled into the filter prompt
+    pub filter_recall: Option<usize>,
+    /// How the secrets list is sorted (`None` keeps the API's order)
+    pub sort: Option<SecretSort>,
 
     // --- Secret detail state ---
     /// Currently selected secret (when viewing details)
@@ -93,6 +487,25 @@ pub struct App {
     pub versions_state: ListState,
     /// Currently visible secret value (if revealed)
     pub revealed_value: Option<String>,
+    /// When the revealed value should be hidden again
+    reveal_deadline: Option<Instant>,
+    /// When to clear the clipboard, and the value that was copied
+    clipboard_deadline: Option<(Instant, String)>,
+    /// When the current confirmation dialog was opened
+    confirm_opened_at: Option<Instant>,
+
+    // --- Secret IAM state ---
+    /// IAM policy of the current secret (loaded on the IAM tab)
+    pub iam_policy: Option<IamPolicy>,
+    /// Selection state for the policy's members
+    pub iam_state: ListState,
+    /// Tag values bound to the current secret (loaded on the tags tab)
+    pub tag_bindings: Option<Vec<TagBinding>>,
+    /// Selection state for the tag bindings
+    pub tags_state: ListState,
+    /// Permissions the caller holds on each secret opened so far, by
+    /// resource name
+    granted_permissions: HashMap<String, Vec<String>>,
 
     // --- Input state ---
     /// Current input buffer for text entry
@@ -108,6 +521,30 @@ pub struct App {
     pub available_projects: Vec<ProjectInfo>,
     /// Selection state for the projects list
     pub projects_state: ListState,
+
+    // --- gcloud configuration selector state ---
+    /// Named gcloud configurations
+    pub gcloud_configs: Vec<GcloudConfig>,
+    /// Selection state for the configurations list
+    pub configs_state: ListState,
+    /// Name of the gcloud configuration in use
+    pub gcloud_config: String,
+
+    // --- Location selector state ---
+    /// Locations offered in the selector (`None` is global)
+    pub available_locations: Vec<Option<String>>,
+    /// Selection state for the locations list
+    pub locations_state: ListState,
+    /// Locations marked in the selector
+    pub location_selection: Vec<Option<String>>,
+
+    // --- Audit history state ---
+    /// Audit log of modifying actions
+    pub audit: AuditLog,
+    /// Records shown in the history view (newest first)
+    pub history: Vec<AuditRecord>,
+    /// Selection state for the history list
+    pub history_state: ListState,
 }
 
 impl App {
@@ -124,58 +561,140 @@ impl App {
 
         Self {
             project_id: project,
-            client: None,
+            connection: ConnectionOptions::default(),
+            impersonation_override: None,
+            principal: None,
+            credential_source: CredentialSource::MetadataServer,
+            auth_error: None,
+            read_only: false,
+            config: Config::default(),
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            clients: Vec::new(),
+            tag_clients: Vec::new(),
+            project_client: None,
+            location_client: None,
+            locations: vec![None],
             current_view: initial_view,
             previous_view: None,
             is_loading: false,
             status: None,
             secrets: Vec::new(),
-            secrets_state: ListState::default(),
+            secrets_state: TableState::default(),
+            filter: SecretFilter::default(),
+            filter_history: FilterHistory::default(),
+            filter_recall: None,
+            sort: None,
             current_secret: None,
             versions: Vec::new(),
             versions_state: ListState::default(),
             revealed_value: None,
+            reveal_deadline: None,
+            clipboard_deadline: None,
+            confirm_opened_at: None,
+            iam_policy: None,
+            iam_state: ListState::default(),
+            tag_bindings: None,
+            tags_state: ListState::default(),
+            granted_permissions: HashMap::new(),
             input_buffer: String::new(),
             cursor_position: 0,
             show_help: false,
             available_projects: Vec::new(),
             projects_state: ListState::default(),
+            gcloud_configs: Vec::new(),
+            configs_state: ListState::default(),
+            gcloud_config: String::new(),
+            available_locations: Vec::new(),
+            locations_state: ListState::default(),
+            location_selection: Vec::new(),
+            audit: AuditLog::default(),
+            history: Vec::new(),
+            history_state: ListState::default(),
         }
     }
 
     /// Loads the list of secrets from the API.
-    /// If loading fails (likely auth issue), switches to `AuthRequired` view.
+    ///
+    /// Secrets of all browsed locations are listed together.
+    /// Only authentication failures switch to the `AuthRequired` view.
     pub async fn load_secrets(&mut self) -> Result<()> {
         self.is_loading = true;
         self.set_status("Loading secrets...", false);
 
-        // Initialize client if needed
-        let project_id = self.project_id.clone();
-        if self.client.is_none() {
-            match SecretClient::new(project_id).await {
-                Ok(c) => self.client = Some(c),
-                Err(e) => {
-                    self.set_status(&format!("Auth error: {e}"), true);
-                    self.current_view = View::AuthRequired;
-                    self.is_loading = false;
-                    return Ok(());
+        // Initialize clients if needed
+        if self.clients.is_empty() {
+            if !self.verify_credentials().await {
+                self.is_loading = false;
+                return Ok(());
+            }
+            for location in self.locations.clone() {
+                let connection = self.connection.at_location(location);
+                match SecretClient::new(self.project_id.clone(), &connection).await {
+                    Ok(c) => self.clients.push(c),
+                    Err(e) => {
+                        self.clients.clear();
+                        self.report_load_error(&e);
+                        self.is_loading = false;
+                        return Ok(());
+                    }
                 }
             }
         }
 
-        match self.client.as_ref().unwrap().list_secrets().await {
-            Ok(secrets) => {
+        let Listing {
+            mut secrets,
+            failures,
+            missing_summaries,
+        } = self.list_all_secrets().await;
+        match failures.first() {
+            // Every location failed: report the first error
+            Some((_, e)) if failures.len() == self.clients.len() => self.report_load_error(e),
+            _ => {
+                if self.filter.non_cmek {
+                    secrets.retain(|s| !s.is_cmek());
+                }
+                if let Some(sort) = self.sort {
+                    secrets.sort_by(|a, b| sort.compare(a, b));
+                }
                 self.secrets = secrets;
                 // Select the first item if list is not empty
-                if !self.secrets.is_empty() {
-                    self.secrets_state.select(Some(0));
-                }
+                self.secrets_state
+                    .select((!self.secrets.is_empty()).then_some(0));
                 let count = self.secrets.len();
-                self.set_status(&format!("Loaded {count} secrets"), false);
-            }
-            Err(e) => {
-                self.set_status(&format!("Auth error: {e}"), true);
-                self.current_view = View::AuthRequired;
+                let filter = if self.filter.non_cmek {
+                    " without CMEK"
+                } else {
+                    ""
+                };
+                let n = self.locations.len();
+                let status = if !failures.is_empty() {
+                    let failed: Vec<String> = failures
+                        .iter()
+                        .map(|(location, e)| {
+                            format!(
+                                "{} ({})",
+                                location_label(location.as_deref()),
+                                ApiError::classify(e)
+                            )
+                        })
+                        .collect();
+                    format!(
+                        "Loaded {count} secrets{filter} from {} of {n} locations - failed: {}",
+                        n - failures.len(),
+                        failed.join("; ")
+                    )
+                } else if n == 1 {
+                    format!("Loaded {count} secrets{filter}")
+                } else {
+                    format!("Loaded {count} secrets{filter} from {n} locations")
+                };
+                let status = match missing_summaries {
+                    0 => status,
+                    1 => format!("{status} - versions of 1 secret could not be listed"),
+                    k => format!("{status} - versions of {k} secrets could not be listed"),
+                };
+                self.set_status(&status, !failures.is_empty() || missing_summaries > 0);
             }
         }
 
@@ -183,15 +702,57 @@ impl App {
         Ok(())
     }
 
+    /// Lists the secrets of every browsed location, in location order.
+    ///
+    /// A location that fails does not keep the others from being listed;
+    /// its error is returned along with the secrets that were listed. When a
+    /// column needs version summaries, the versions of a location's secrets
+    /// are listed a few secrets at a time.
+    async fn list_all_secrets(&self) -> Listing {
+        let with_versions = self.config.columns.iter().any(|c| c.needs_versions());
+        let mut listing = Listing::default();
+        for client in &self.clients {
+            let mut listed = match client.list_secrets(self.filter.secrets.as_deref()).await {
+                Ok(listed) => listed,
+                Err(e) => {
+                    listing
+                        .failures
+                        .push((client.location().map(str::to_string), e));
+                    continue;
+                }
+            };
+            if with_versions {
+                let summaries: Vec<_> = stream::iter(&listed)
+                    .map(|secret| client.version_summary(&secret.short_name))
+                    .buffered(VERSION_SUMMARY_CONCURRENCY)
+                    .collect()
+                    .await;
+                // Secrets whose versions cannot be listed show no summary
+                for (secret, summary) in listed.iter_mut().zip(summaries) {
+                    listing.missing_summaries += usize::from(summary.is_err());
+                    secret.versions = summary.ok();
+                }
+            }
+            listing.secrets.extend(listed);
+        }
+        listing
+    }
+
     /// Loads the list of available projects from the API.
     ///
     /// Used when starting without a project or when opening the project selector.
-    /// If loading fails (likely auth issue), switches to `AuthRequired` view.
+    /// Only authentication failures switch to the `AuthRequired` view.
     pub async fn load_projects(&mut self) -> Result<()> {
         self.is_loading = true;
         self.set_status("Loading projects...", false);
 
-        match project_client::list_projects().await {
+        if self.clients.is_empty() && !self.verify_credentials().await {
+            self.is_loading = false;
+            return Ok(());
+        }
+
+        let result = async { self.project_client().await?.list_projects().await }.await;
+        match result {
             Ok(projects) => {
                 self.available_projects = projects;
                 // Try to select the current project in the list, or first item
@@ -209,11 +770,7 @@ impl App {
                 let count = self.available_projects.len();
                 self.set_status(&format!("Found {count} projects"), false);
             }
-            Err(e) => {
-                // Loading failed - likely an auth issue, switch to auth view
-                self.set_status(&format!("Auth error: {e}"), true);
-                self.current_view = View::AuthRequired;
-            }
+            Err(e) => self.report_load_error(&e),
         }
 
         self.is_loading = false;
@@ -230,13 +787,13 @@ impl App {
         self.is_loading = true;
         self.set_status("Loading versions...", false);
 
-        match self
-            .client
-            .as_ref()
-            .unwrap()
-            .list_versions(&secret_name)
-            .await
-        {
+        let result = async {
+            self.secret_client()?
+                .list_versions(&secret_name, self.filter.versions.as_deref())
+                .await
+        }
+        .await;
+        match result {
             Ok(versions) => {
                 self.versions = versions;
                 // Select the first version if list is not empty
@@ -247,7 +804,7 @@ impl App {
                 self.set_status(&format!("Loaded {count} versions"), false);
             }
             Err(e) => {
-                self.set_status(&format!("Error loading versions: {e}"), true);
+                self.report_error("Error loading versions", &e);
             }
         }
 
@@ -255,6 +812,30 @@ impl App {
         Ok(())
     }
 
+    /// Returns true if handling `action` may be abandoned part way.
+    ///
+    /// Only reads may be cancelled: a write that was already sent could
+    /// still take effect after the UI stopped waiting for it.
+    pub fn can_cancel(&self, action: &Action) -> bool {
+        !action.is_mutating()
+            && !matches!(
+                self.current_view,
+                View::Input(_) | View::Confirm(_) | View::Conflict(_)
+            )
+    }
+
+    /// Cleans up after a call was cancelled part way.
+    ///
+    /// Clients are created one location at a time, so a partial set is
+    /// dropped to be created again by the next load.
+    pub fn on_cancelled(&mut self) {
+        if self.clients.len() != self.locations.len() {
+            self.clients.clear();
+        }
+        self.is_loading = false;
+        self.set_status("Cancelled", false);
+    }
+
     /// Handles an action and returns an `AppAction` if one is needed.
     pub async fn handle_event(&mut self, action: Action) -> Result<Option<AppAction>> {
         // Handle help toggle from any view
@@ -269,6 +850,18 @@ impl App {
             return Ok(None);
         }
 
+        // Refuse modifications in read-only mode
+        if self.is_read_only() && action.is_mutating() {
+            self.set_status("Read-only mode - modifying secrets is disabled", true);
+            return Ok(None);
+        }
+
+        // Refuse commands the caller is known to lack the permission for
+        if let Some(permission) = self.missing_permission(&action) {
+            self.set_status(&format!("Not permitted - missing {permission}"), true);
+            return Ok(None);
+        }
+
         // Handle confirmation dialogs
         if let View::Confirm(ref confirm_action) = self.current_view {
             return self
@@ -276,6 +869,11 @@ impl App {
                 .await;
         }
 
+        // Handle the conflict dialog
+        if let View::Conflict(ref conflict) = self.current_view {
+            return self.handle_conflict_action(action, conflict.clone()).await;
+        }
+
         // Handle input mode
         if let View::Input(ref input_mode) = self.current_view {
             return self.handle_input_action(action, input_mode.clone()).await;
@@ -286,11 +884,101 @@ impl App {
             View::AuthRequired => Ok(self.handle_auth_required_action(&action)),
             View::SecretsList => self.handle_secrets_list_action(action).await,
             View::SecretDetail => self.handle_secret_detail_action(action).await,
+            View::SecretIam => self.handle_secret_iam_action(action).await,
+            View::SecretTags => self.handle_secret_tags_action(action).await,
             View::ProjectSelector => self.handle_project_selector_action(action).await,
+            View::ConfigSelector => self.handle_config_selector_action(action).await,
+            View::LocationSelector => self.handle_location_selector_action(action).await,
+            View::History => Ok(self.handle_history_action(&action)),
             _ => Ok(None),
         }
     }
 
+    /// Returns true if modifications are disabled for the current project.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only || self.config.is_read_only_project(&self.project_id)
+    }
+
+    /// Applies per-project connection settings for the current project.
+    ///
+    /// Picks the service account to impersonate (the command line wins over
+    /// the config) and updates the principal shown in the header and audit log.
+    pub fn apply_project_settings(&mut self) {
+        self.connection.impersonate_service_account =
+            self.impersonation_override.clone().or_else(|| {
+                self.config
+                    .impersonation_for(&self.project_id)
+                    .map(str::to_string)
+            });
+        self.credential_source = CredentialSource::detect();
+        self.principal = self.connection.principal();
+        self.audit.set_principal(self.principal.clone());
+    }
+
+    /// Checks that the credentials can produce a token and records whose
+    /// they are.
+    ///
+    /// Returns false after reporting the problem if they cannot, so that
+    /// expired or revoked credentials are explained instead of surfacing as
+    /// a failed list call.
+    async fn verify_credentials(&mut self) -> bool {
+        match self.connection.identify().await {
+            Ok(principal) => {
+                if principal.is_some() {
+                    self.principal = principal;
+                    self.audit.set_principal(self.principal.clone());
+                }
+                true
+            }
+            Err(e) => {
+                self.report_load_error(&e);
+                false
+            }
+        }
+    }
+
+    /// Runs time-based housekeeping; called on every loop iteration.
+    ///
+    /// Hides revealed values and clears copied secrets from the clipboard
+    /// once their configured timeouts expire.
+    pub fn on_tick(&mut self) {
+        let now = Instant::now();
+
+        if self.reveal_deadline.is_some_and(|deadline| now >= deadline) {
+            self.reveal_deadline = None;
+            if self.revealed_value.take().is_some() {
+                self.set_status("Secret value hidden (timeout)", false);
+            }
+        }
+
+        if self
+            .clipboard_deadline
+            .as_ref()
+            .is_some_and(|(deadline, _)| now >= *deadline)
+        {
+            if let Some((_, copied)) = self.clipboard_deadline.take() {
+                self.clear_clipboard(&copied);
+            }
+        }
+    }
+
+    /// Clears the clipboard if it still holds the value we copied.
+    fn clear_clipboard(&mut self, copied: &str) {
+        let Ok(mut clipboard) = arboard::Clipboard::new() else {
+            return;
+        };
+        if clipboard.get_text().is_ok_and(|text| text == copied) && clipboard.clear().is_ok() {
+            self.set_status("Clipboard cleared", false);
+        }
+    }
+
+    /// Reports config file problems to the user.
+    pub fn report_config_errors(&mut self, errors: &[String]) {
+        if !errors.is_empty() {
+            self.set_status(&format!("Config: {}", errors.join("; ")), true);
+        }
+    }
+
     /// Handles actions in the auth required view.
     fn handle_auth_required_action(&mut self, action: &Action) -> Option<AppAction> {
         match action {
@@ -303,6 +991,11 @@ impl App {
     /// Called after successful gcloud auth to load projects.
     pub async fn on_auth_success(&mut self) -> Result<()> {
         self.set_status("Authentication successful!", false);
+        self.auth_error = None;
+        self.clients.clear();
+        self.project_client = None;
+        self.location_client = None;
+        self.apply_project_settings();
         self.current_view = View::ProjectSelector;
         self.load_projects().await
     }
@@ -328,7 +1021,19 @@ impl App {
             Action::Refresh => self.load_secrets().await?,
             Action::NewSecret => self.start_new_secret(),
             Action::Delete => self.confirm_delete_secret(),
+            Action::SetExpiration => self.start_secret_input(InputMode::Expiration),
+            Action::SetDestroyTtl => self.start_secret_input(InputMode::DestroyTtl),
+            Action::ToggleCmekFilter => {
+                self.filter.non_cmek = !self.filter.non_cmek;
+                self.load_secrets().await?;
+            }
+            Action::Filter => self.start_filter(InputMode::SecretsFilter),
+            Action::Sort => self.sort_secrets(false),
+            Action::ReverseSort => self.sort_secrets(true),
             Action::OpenProjectSelector => self.open_project_selector().await?,
+            Action::OpenConfigSelector => self.open_config_selector(),
+            Action::OpenLocationSelector => self.open_location_selector().await?,
+            Action::History => self.open_history(),
             _ => {}
         }
         Ok(None)
@@ -363,12 +1068,19 @@ impl App {
             Action::Bottom => self.select_last_version(),
             Action::Refresh => self.load_versions().await?,
             Action::NewVersion => self.start_new_version(),
+            Action::SetExpiration => self.start_secret_input(InputMode::Expiration),
+            Action::SetDestroyTtl => self.start_secret_input(InputMode::DestroyTtl),
+            Action::Filter => self.start_filter(InputMode::VersionsFilter),
             Action::ToggleSecretValue => self.toggle_secret_value().await?,
             Action::Copy => self.copy_secret_value().await?,
             Action::Enable => self.enable_selected_version().await?,
             Action::Disable => self.disable_selected_version().await?,
             Action::Delete => self.confirm_destroy_version(),
+            Action::SwitchTab => self.open_iam_tab().await?,
             Action::OpenProjectSelector => self.open_project_selector().await?,
+            Action::OpenConfigSelector => self.open_config_selector(),
+            Action::OpenLocationSelector => self.open_location_selector().await?,
+            Action::History => self.open_history(),
             _ => {}
         }
         Ok(None)
@@ -390,6 +1102,11 @@ impl App {
             Action::Enter => {
                 self.submit_input(mode).await?;
             }
+            Action::Up | Action::Down
+                if matches!(mode, InputMode::SecretsFilter | InputMode::VersionsFilter) =>
+            {
+                self.recall_filter(action == Action::Up);
+            }
             Action::Char(c) => {
                 self.input_char(c);
             }
@@ -413,20 +1130,102 @@ impl App {
         action: Action,
         confirm: ConfirmAction,
     ) -> Result<Option<AppAction>> {
+        // Discard keys that were typed before the dialog could be read
+        let cancel = matches!(action, Action::Back | Action::Quit);
+        if !cancel
+            && self
+                .confirm_opened_at
+                .is_some_and(|opened| opened.elapsed() < CONFIRM_INPUT_DELAY)
+        {
+            return Ok(None);
+        }
+
         match action {
             Action::Enter => {
-                // User confirmed the action
-                self.execute_confirmed_action(confirm).await?;
+                let phrase = self.confirm_phrase(&confirm);
+                if self.input_buffer == phrase {
+                    // User confirmed the action
+                    self.input_buffer.clear();
+                    self.cursor_position = 0;
+                    self.confirm_opened_at = None;
+                    self.execute_confirmed_action(confirm).await?;
+                } else {
+                    self.set_status(&format!("Type '{phrase}' to confirm"), true);
+                }
             }
             Action::Back | Action::Quit => {
                 // User cancelled
+                self.input_buffer.clear();
+                self.cursor_position = 0;
+                self.confirm_opened_at = None;
+                self.go_back();
+            }
+            Action::Char(c) => self.input_char(c),
+            Action::Backspace => self.input_backspace(),
+            Action::CursorLeft => self.cursor_left(),
+            Action::CursorRight => self.cursor_right(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Handles actions in the conflict dialog.
+    ///
+    /// Confirming retries the rejected write with the etag of the target as
+    /// now stored, overwriting the other change; a delete or destroy first
+    /// goes back through its typed confirmation. Cancelling drops the write
+    /// and leaves the target as it is.
+    async fn handle_conflict_action(
+        &mut self,
+        action: Action,
+        conflict: Conflict,
+    ) -> Result<Option<AppAction>> {
+        // Discard keys that were typed before the dialog could be read
+        let cancel = matches!(action, Action::Back | Action::Quit);
+        if !cancel
+            && self
+                .confirm_opened_at
+                .is_some_and(|opened| opened.elapsed() < CONFIRM_INPUT_DELAY)
+        {
+            return Ok(None);
+        }
+
+        match action {
+            Action::Enter => {
+                self.confirm_opened_at = None;
+                if let Some(confirm) = conflict.reconfirm() {
+                    self.go_back();
+                    self.open_confirm(confirm);
+                } else {
+                    self.perform_write(conflict.write, conflict.etag).await?;
+                }
+            }
+            Action::Back | Action::Quit => {
+                self.confirm_opened_at = None;
                 self.go_back();
+                self.set_status("Nothing was changed", false);
             }
             _ => {}
         }
         Ok(None)
     }
 
+    /// Returns the text the user must type to confirm a destructive action.
+    ///
+    /// This is the secret name, or the project ID for protected projects.
+    pub fn confirm_phrase(&self, confirm: &ConfirmAction) -> String {
+        if self.config.is_protected_project(&self.project_id) {
+            return self.project_id.clone();
+        }
+        match confirm {
+            ConfirmAction::DeleteSecret { secret: name, .. }
+            | ConfirmAction::DestroyVersion { secret: name, .. }
+            | ConfirmAction::RemoveIamMember { secret: name, .. }
+            | ConfirmAction::GrantPublicAccess { secret: name, .. }
+            | ConfirmAction::UnbindTag { secret: name, .. } => name.clone(),
+        }
+    }
+
     // --- Navigation helpers ---
 
     fn select_previous_secret(&mut self) {
@@ -559,66 +1358,876 @@ impl App {
                     return Ok(());
                 }
 
-                // Switch to the new project
-                self.project_id.clone_from(&new_project_id);
-                self.client = None; // Clear the client to force reinitialization
-                self.secrets.clear();
-                self.secrets_state = ListState::default();
-                self.current_secret = None;
-                self.versions.clear();
-                self.versions_state = ListState::default();
-                self.revealed_value = None;
+                self.switch_project(new_project_id).await?;
+            }
+        }
+        Ok(())
+    }
 
-                self.set_status(&format!("Switched to project: {new_project_id}"), false);
-                self.current_view = View::SecretsList;
-                self.previous_view = None;
+    /// Switches to another project and loads its secrets.
+    async fn switch_project(&mut self, new_project_id: String) -> Result<()> {
+        self.project_id.clone_from(&new_project_id);
+        self.apply_project_settings();
+        self.clear_loaded_secrets();
 
-                // Load secrets for the new project
-                self.load_secrets().await?;
+        self.set_status(&format!("Switched to project: {new_project_id}"), false);
+
+        // Load secrets for the new project
+        self.load_secrets().await
+    }
+
+    /// Drops the clients and everything loaded through them, returning to
+    /// the secrets list.
+    fn clear_loaded_secrets(&mut self) {
+        self.clients.clear(); // Clear the clients to force reinitialization
+        self.tag_clients.clear();
+        self.project_client = None;
+        self.location_client = None;
+        self.secrets.clear();
+        self.granted_permissions.clear();
+        self.secrets_state = TableState::default();
+        self.current_secret = None;
+        self.versions.clear();
+        self.versions_state = ListState::default();
+        self.revealed_value = None;
+        self.current_view = View::SecretsList;
+        self.previous_view = None;
+    }
+
+    // --- Location selector helpers ---
+
+    /// Opens the location selector.
+    ///
+    /// Offers global, the configured locations and the regions the API
+    /// reports for the project. If the regions cannot be listed, the
+    /// selector still opens with the known locations.
+    async fn open_location_selector(&mut self) -> Result<()> {
+        let mut available = vec![None];
+        for location in self.config.locations.iter().chain(&self.locations) {
+            if !available.contains(location) {
+                available.push(location.clone());
+            }
+        }
+
+        self.is_loading = true;
+        self.set_status("Loading locations...", false);
+        let regions = async { self.global_client().await?.list_locations().await }.await;
+        self.is_loading = false;
+        match regions {
+            Ok(regions) => {
+                for region in regions.into_iter().map(Some) {
+                    if !available.contains(&region) {
+                        available.push(region);
+                    }
+                }
+                let scope = Scope::LocationSelector;
+                let hint = match (
+                    self.keymap.key(scope, &Action::ToggleSelection),
+                    self.keymap.key(scope, &Action::Enter),
+                ) {
+                    (Some(mark), Some(apply)) => {
+                        format!("{mark} marks locations, {apply} lists their secrets")
+                    }
+                    _ => "Mark locations to list their secrets".to_string(),
+                };
+                self.set_status(&hint, false);
             }
+            Err(e) => self.report_error("Error loading locations", &e),
         }
+
+        // Global first, then regions alphabetically
+        available.sort();
+        self.available_locations = available;
+        self.location_selection.clone_from(&self.locations);
+        self.locations_state.select(Some(0));
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::LocationSelector;
         Ok(())
     }
 
-    /// Enters the detail view for the selected secret.
-    async fn enter_secret_detail(&mut self) -> Result<()> {
-        if let Some(idx) = self.secrets_state.selected() {
-            if let Some(secret) = self.secrets.get(idx) {
-                self.current_secret = Some(secret.clone());
-                self.previous_view = Some(View::SecretsList);
-                self.current_view = View::SecretDetail;
-                self.versions_state = ListState::default();
-                self.revealed_value = None;
-                self.load_versions().await?;
+    /// Handles actions in the location selector.
+    async fn handle_location_selector_action(
+        &mut self,
+        action: Action,
+    ) -> Result<Option<AppAction>> {
+        let len = self.available_locations.len();
+        let current = self.locations_state.selected().unwrap_or(0);
+        match action {
+            Action::Quit => return Ok(Some(AppAction::Quit)),
+            Action::Back => self.go_back(),
+            Action::Up if len > 0 => {
+                self.locations_state
+                    .select(Some(if current == 0 { len - 1 } else { current - 1 }));
+            }
+            Action::Down if len > 0 => {
+                self.locations_state
+                    .select(Some(if current >= len - 1 { 0 } else { current + 1 }));
             }
+            Action::Top if len > 0 => self.locations_state.select(Some(0)),
+            Action::Bottom if len > 0 => self.locations_state.select(Some(len - 1)),
+            Action::ToggleSelection => self.toggle_location(),
+            Action::Enter => self.apply_locations().await?,
+            _ => {}
         }
-        Ok(())
+        Ok(None)
     }
 
-    /// Goes back to the previous view.
-    fn go_back(&mut self) {
-        if let Some(prev) = self.previous_view.take() {
-            self.current_view = prev;
+    /// Marks or unmarks the highlighted location.
+    fn toggle_location(&mut self) {
+        let Some(location) = self
+            .locations_state
+            .selected()
+            .and_then(|idx| self.available_locations.get(idx))
+            .cloned()
+        else {
+            return;
+        };
+        if let Some(pos) = self.location_selection.iter().position(|l| *l == location) {
+            self.location_selection.remove(pos);
         } else {
-            self.current_view = View::SecretsList;
+            self.location_selection.push(location);
         }
-        self.revealed_value = None;
     }
 
-    // --- Input handling ---
+    /// Lists the secrets of the marked locations.
+    async fn apply_locations(&mut self) -> Result<()> {
+        // Keep the selector's order so that the first location is predictable
+        let selected: Vec<Option<String>> = self
+            .available_locations
+            .iter()
+            .filter(|l| self.location_selection.contains(l))
+            .cloned()
+            .collect();
+        if selected.is_empty() {
+            let hint = self
+                .keymap
+                .key(Scope::LocationSelector, &Action::ToggleSelection)
+                .map_or_else(
+                    || "Mark at least one location".to_string(),
+                    |key| format!("Mark at least one location with {key}"),
+                );
+            self.set_status(&hint, true);
+            return Ok(());
+        }
+        if selected == self.locations {
+            self.go_back();
+            return Ok(());
+        }
 
-    fn start_new_secret(&mut self) {
-        self.input_buffer.clear();
-        self.cursor_position = 0;
-        self.previous_view = Some(self.current_view.clone());
-        self.current_view = View::Input(InputMode::NewSecretName);
+        self.locations = selected;
+        self.clear_loaded_secrets();
+        self.load_secrets().await
     }
 
-    fn start_new_version(&mut self) {
-        self.input_buffer.clear();
-        self.cursor_position = 0;
+    // --- gcloud configuration helpers ---
+
+    /// Opens the gcloud configuration selector.
+    fn open_config_selector(&mut self) {
+        self.gcloud_configs = gcloud::list();
+        if self.gcloud_configs.is_empty() {
+            self.set_status("No gcloud configurations found", true);
+            return;
+        }
+        let current = self
+            .gcloud_configs
+            .iter()
+            .position(|c| c.name == self.gcloud_config)
+            .unwrap_or(0);
+        self.configs_state.select(Some(current));
         self.previous_view = Some(self.current_view.clone());
-        self.current_view = View::Input(InputMode::NewVersionValue);
+        self.current_view = View::ConfigSelector;
+    }
+
+    /// Handles actions in the gcloud configuration selector.
+    async fn handle_config_selector_action(&mut self, action: Action) -> Result<Option<AppAction>> {
+        let len = self.gcloud_configs.len();
+        let current = self.configs_state.selected().unwrap_or(0);
+        match action {
+            Action::Quit => return Ok(Some(AppAction::Quit)),
+            Action::Back => self.go_back(),
+            Action::Up if len > 0 => {
+                self.configs_state
+                    .select(Some(if current == 0 { len - 1 } else { current - 1 }));
+            }
+            Action::Down if len > 0 => {
+                self.configs_state
+                    .select(Some(if current >= len - 1 { 0 } else { current + 1 }));
+            }
+            Action::Top if len > 0 => self.configs_state.select(Some(0)),
+            Action::Bottom if len > 0 => self.configs_state.select(Some(len - 1)),
+            Action::Enter => self.select_config().await?,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Switches to the selected gcloud configuration's project.
+    ///
+    /// A configuration without a project opens the project selector.
+    async fn select_config(&mut self) -> Result<()> {
+        let Some(config) = self
+            .configs_state
+            .selected()
+            .and_then(|idx| self.gcloud_configs.get(idx))
+            .cloned()
+        else {
+            return Ok(());
+        };
+        self.gcloud_config = config.name.clone();
+        match config.project {
+            Some(project) if project == self.project_id => {
+                self.set_status(
+                    &format!("Using gcloud configuration: {}", config.name),
+                    false,
+                );
+                self.go_back();
+                Ok(())
+            }
+            Some(project) => self.switch_project(project).await,
+            None => {
+                self.set_status(
+                    &format!("gcloud configuration '{}' has no project", config.name),
+                    false,
+                );
+                self.current_view = View::SecretsList;
+                self.open_project_selector().await
+            }
+        }
+    }
+
+    /// Enters the detail view for the selected secret.
+    async fn enter_secret_detail(&mut self) -> Result<()> {
+        if let Some(idx) = self.secrets_state.selected() {
+            if let Some(secret) = self.secrets.get(idx) {
+                self.current_secret = Some(secret.clone());
+                self.previous_view = Some(View::SecretsList);
+                self.current_view = View::SecretDetail;
+                self.versions_state = ListState::default();
+                self.iam_policy = None;
+                self.iam_state = ListState::default();
+                self.tag_bindings = None;
+                self.tags_state = ListState::default();
+                self.revealed_value = None;
+                self.load_permissions().await;
+                self.load_versions().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Tests which of the commands' permissions the caller holds on the
+    /// current secret.
+    ///
+    /// Failures are only logged: the commands then stay available and the
+    /// API reports what is missing when they are used.
+    async fn load_permissions(&mut self) {
+        let Some(secret) = &self.current_secret else {
+            return;
+        };
+        let (name, location) = (secret.short_name.clone(), secret.location.clone());
+
+        let result = async {
+            self.secret_client()?
+                .test_iam_permissions(&name, &iam::SECRET_PERMISSIONS)
+                .await
+        }
+        .await;
+        match result {
+            Ok(granted) => {
+                let resource = self.secret_resource(&name, location.as_deref());
+                self.granted_permissions.insert(resource, granted);
+            }
+            Err(e) => logging::error(&format!("Could not test permissions on {name}: {e:#}")),
+        }
+    }
+
+    /// Returns the permission the caller lacks for an action on the selected
+    /// secret in the current view.
+    ///
+    /// `None` if the action needs no permission, or the secret's permissions
+    /// have not been tested.
+    pub fn missing_permission(&self, action: &Action) -> Option<&'static str> {
+        let permission = iam::required_permission(Scope::from(&self.current_view), action)?;
+        let secret = self.focused_secret()?;
+        let granted = self
+            .granted_permissions
+            .get(&self.secret_resource(&secret.short_name, secret.location.as_deref()))?;
+        (!granted.iter().any(|p| p == permission)).then_some(permission)
+    }
+
+    // --- IAM tab ---
+
+    /// Switches from the versions to the IAM tab and loads the policy.
+    async fn open_iam_tab(&mut self) -> Result<()> {
+        self.revealed_value = None;
+        self.current_view = View::SecretIam;
+        self.load_iam_policy().await
+    }
+
+    /// Loads the IAM policy of the current secret.
+    pub async fn load_iam_policy(&mut self) -> Result<()> {
+        let secret_name = match &self.current_secret {
+            Some(s) => s.short_name.clone(),
+            None => return Ok(()),
+        };
+
+        self.is_loading = true;
+        self.set_status("Loading IAM policy...", false);
+
+        let result = async { self.secret_client()?.get_iam_policy(&secret_name).await }.await;
+        match result {
+            Ok(policy) => {
+                let count = policy.bindings.len();
+                let public = policy.is_public();
+                self.iam_policy = Some(policy);
+                self.clamp_iam_selection();
+                if public {
+                    self.set_status(
+                        "Warning: this secret is public (allUsers or allAuthenticatedUsers)",
+                        true,
+                    );
+                } else {
+                    self.set_status(&format!("Loaded {count} role bindings"), false);
+                }
+            }
+            Err(e) => {
+                self.iam_policy = None;
+                self.report_error("Error loading IAM policy", &e);
+            }
+        }
+
+        self.is_loading = false;
+        Ok(())
+    }
+
+    /// Keeps the member selection within the loaded policy.
+    fn clamp_iam_selection(&mut self) {
+        let rows = self.iam_policy.as_ref().map_or(0, |p| p.rows().len());
+        let selected = self.iam_state.selected().unwrap_or(0);
+        self.iam_state
+            .select((rows > 0).then(|| selected.min(rows - 1)));
+    }
+
+    /// Handles actions in the IAM tab.
+    async fn handle_secret_iam_action(&mut self, action: Action) -> Result<Option<AppAction>> {
+        let len = self.iam_policy.as_ref().map_or(0, |p| p.rows().len());
+        let current = self.iam_state.selected().unwrap_or(0);
+        match action {
+            Action::Quit => return Ok(Some(AppAction::Quit)),
+            Action::Back => self.go_back(),
+            Action::Up if len > 0 => {
+                self.iam_state
+                    .select(Some(if current == 0 { len - 1 } else { current - 1 }));
+            }
+            Action::Down if len > 0 => {
+                self.iam_state
+                    .select(Some(if current >= len - 1 { 0 } else { current + 1 }));
+            }
+            Action::Top if len > 0 => self.iam_state.select(Some(0)),
+            Action::Bottom if len > 0 => self.iam_state.select(Some(len - 1)),
+            Action::Refresh => self.load_iam_policy().await?,
+            Action::SwitchTab => self.open_tags_tab().await?,
+            Action::AddMember => self.start_grant(),
+            Action::Delete => self.confirm_revoke(),
+            Action::OpenProjectSelector => self.open_project_selector().await?,
+            Action::OpenConfigSelector => self.open_config_selector(),
+            Action::OpenLocationSelector => self.open_location_selector().await?,
+            Action::History => self.open_history(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Asks for a member (and optional role) to grant.
+    fn start_grant(&mut self) {
+        if self.iam_policy.is_none() {
+            let retry = self
+                .keymap
+                .key(Scope::SecretIam, &Action::Refresh)
+                .map_or_else(
+                    || "refresh to retry".to_string(),
+                    |key| format!("press '{key}' to retry"),
+                );
+            self.set_status(&format!("IAM policy not loaded - {retry}"), true);
+            return;
+        }
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(InputMode::GrantMember);
+    }
+
+    /// Asks for confirmation before removing the selected member.
+    fn confirm_revoke(&mut self) {
+        let confirm = match (&self.iam_policy, &self.current_secret) {
+            (Some(policy), Some(secret)) => self
+                .iam_state
+                .selected()
+                .and_then(|idx| policy.rows().get(idx).copied())
+                .map(|(binding, member)| ConfirmAction::RemoveIamMember {
+                    secret: secret.short_name.clone(),
+                    binding,
+                    role: policy.bindings[binding].role.clone(),
+                    member: member.to_string(),
+                }),
+            _ => None,
+        };
+        if let Some(confirm) = confirm {
+            self.open_confirm(confirm);
+        }
+    }
+
+    /// Grants `role` to `member` on the current secret.
+    async fn grant_iam_member(&mut self, role: &str, member: &str) -> Result<()> {
+        let Some(mut policy) = self.iam_policy.clone() else {
+            return Ok(());
+        };
+        if !policy.add_member(role, member) {
+            self.set_status(&format!("{member} already has {role}"), false);
+            return Ok(());
+        }
+        self.write_iam_policy(policy, AuditAction::GrantAccess, role, member)
+            .await
+    }
+
+    /// Removes `member` from the binding at `binding` of the current secret.
+    async fn revoke_iam_member(&mut self, binding: usize, role: &str, member: &str) -> Result<()> {
+        let Some(mut policy) = self.iam_policy.clone() else {
+            return Ok(());
+        };
+        let same_binding = policy.bindings.get(binding).is_some_and(|b| b.role == role);
+        if !same_binding || !policy.remove_member(binding, member) {
+            self.set_status(&format!("{member} no longer has {role}"), false);
+            return Ok(());
+        }
+        self.write_iam_policy(policy, AuditAction::RevokeAccess, role, member)
+            .await
+    }
+
+    /// Writes an edited policy and records the change in the audit log.
+    ///
+    /// The write carries the etag the policy was read with. If someone else
+    /// changed the policy in the meantime, it is reloaded so that the edit
+    /// can be repeated against the current bindings.
+    async fn write_iam_policy(
+        &mut self,
+        policy: IamPolicy,
+        action: AuditAction,
+        role: &str,
+        member: &str,
+    ) -> Result<()> {
+        let Some(secret) = self.current_secret.clone() else {
+            return Ok(());
+        };
+
+        self.is_loading = true;
+        let (result, error) = ApiError::classify_result(
+            async {
+                self.secret_client()?
+                    .set_iam_policy(&secret.short_name, &policy)
+                    .await
+            }
+            .await,
+        );
+        match result {
+            Ok(stored) => {
+                self.iam_policy = Some(stored);
+                self.clamp_iam_selection();
+                if action == AuditAction::RevokeAccess {
+                    self.set_status(&format!("Revoked {role} from {member}"), false);
+                } else if iam::is_public_member(member) {
+                    self.set_status(
+                        &format!("Granted {role} to {member} - the secret is now public"),
+                        true,
+                    );
+                } else {
+                    self.set_status(&format!("Granted {role} to {member}"), false);
+                }
+            }
+            Err(api_error) => {
+                if api_error.kind == ErrorKind::Conflict {
+                    self.load_iam_policy().await?;
+                    self.set_status(
+                        "IAM policy changed since it was loaded - reloaded, try again",
+                        true,
+                    );
+                } else {
+                    self.set_status(&format!("Failed to update IAM policy: {api_error}"), true);
+                }
+            }
+        }
+        let resource = self.secret_resource(&secret.short_name, secret.location.as_deref());
+        self.write_audit(action, &resource, Some(&format!("{role} {member}")), error);
+        self.is_loading = false;
+        Ok(())
+    }
+
+    // --- Tags tab ---
+
+    /// Switches from the IAM to the tags tab and loads the bindings.
+    async fn open_tags_tab(&mut self) -> Result<()> {
+        self.current_view = View::SecretTags;
+        self.load_tag_bindings().await
+    }
+
+    /// Returns a client for the global location, reusing the browsed one
+    /// when global is among the browsed locations.
+    async fn global_client(&mut self) -> Result<&SecretClient> {
+        if let Some(idx) = self.clients.iter().position(|c| c.location().is_none()) {
+            return Ok(&self.clients[idx]);
+        }
+        let client = if let Some(client) = self.location_client.take() {
+            client
+        } else {
+            let connection = self.connection.at_location(None);
+            SecretClient::new(self.project_id.clone(), &connection).await?
+        };
+        Ok(self.location_client.insert(client))
+    }
+
+    /// Returns the projects client, creating it on first use.
+    async fn project_client(&mut self) -> Result<&ProjectClient> {
+        let client = match self.project_client.take() {
+            Some(client) => client,
+            None => ProjectClient::new(&self.connection).await?,
+        };
+        Ok(self.project_client.insert(client))
+    }
+
+    /// Returns the tag bindings client for the location of the current
+    /// secret, creating it on first use.
+    async fn tag_client(&mut self) -> Result<&TagClient> {
+        let location = self
+            .current_secret
+            .as_ref()
+            .and_then(|s| s.location.clone());
+        let idx = if let Some(idx) = self.tag_clients.iter().position(|(l, _)| *l == location) {
+            idx
+        } else {
+            let client = TagClient::new(&self.connection.at_location(location.clone())).await?;
+            self.tag_clients.push((location, client));
+            self.tag_clients.len() - 1
+        };
+        Ok(&self.tag_clients[idx].1)
+    }
+
+    /// Returns the resource name of the current secret.
+    fn current_secret_resource(&self) -> Option<String> {
+        self.current_secret
+            .as_ref()
+            .map(|s| self.secret_resource(&s.short_name, s.location.as_deref()))
+    }
+
+    /// Loads the tag values bound to the current secret.
+    pub async fn load_tag_bindings(&mut self) -> Result<()> {
+        let Some(resource) = self.current_secret_resource() else {
+            return Ok(());
+        };
+
+        self.is_loading = true;
+        self.set_status("Loading tags...", false);
+
+        let result = async { self.tag_client().await?.list_tag_bindings(&resource).await }.await;
+        match result {
+            Ok(bindings) => {
+                let count = bindings.len();
+                self.tag_bindings = Some(bindings);
+                self.clamp_tags_selection();
+                self.set_status(&format!("Loaded {count} tag bindings"), false);
+            }
+            Err(e) => {
+                self.tag_bindings = None;
+                self.report_error("Error loading tags", &e);
+            }
+        }
+
+        self.is_loading = false;
+        Ok(())
+    }
+
+    /// Keeps the tag selection within the loaded bindings.
+    fn clamp_tags_selection(&mut self) {
+        let rows = self.tag_bindings.as_ref().map_or(0, Vec::len);
+        let selected = self.tags_state.selected().unwrap_or(0);
+        self.tags_state
+            .select((rows > 0).then(|| selected.min(rows - 1)));
+    }
+
+    /// Handles actions in the tags tab.
+    async fn handle_secret_tags_action(&mut self, action: Action) -> Result<Option<AppAction>> {
+        let len = self.tag_bindings.as_ref().map_or(0, Vec::len);
+        let current = self.tags_state.selected().unwrap_or(0);
+        match action {
+            Action::Quit => return Ok(Some(AppAction::Quit)),
+            Action::Back => self.go_back(),
+            Action::Up if len > 0 => {
+                self.tags_state
+                    .select(Some(if current == 0 { len - 1 } else { current - 1 }));
+            }
+            Action::Down if len > 0 => {
+                self.tags_state
+                    .select(Some(if current >= len - 1 { 0 } else { current + 1 }));
+            }
+            Action::Top if len > 0 => self.tags_state.select(Some(0)),
+            Action::Bottom if len > 0 => self.tags_state.select(Some(len - 1)),
+            Action::Refresh => self.load_tag_bindings().await?,
+            Action::SwitchTab => self.current_view = View::SecretDetail,
+            Action::BindTag => self.start_bind_tag(),
+            Action::Delete => self.confirm_unbind_tag(),
+            Action::OpenProjectSelector => self.open_project_selector().await?,
+            Action::OpenConfigSelector => self.open_config_selector(),
+            Action::OpenLocationSelector => self.open_location_selector().await?,
+            Action::History => self.open_history(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Asks for a tag value to bind.
+    fn start_bind_tag(&mut self) {
+        if self.tag_bindings.is_none() {
+            let retry = self
+                .keymap
+                .key(Scope::SecretTags, &Action::Refresh)
+                .map_or_else(
+                    || "refresh to retry".to_string(),
+                    |key| format!("press '{key}' to retry"),
+                );
+            self.set_status(&format!("Tags not loaded - {retry}"), true);
+            return;
+        }
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(InputMode::BindTag);
+    }
+
+    /// Asks for confirmation before unbinding the selected tag value.
+    fn confirm_unbind_tag(&mut self) {
+        let confirm = match (&self.tag_bindings, &self.current_secret) {
+            (Some(bindings), Some(secret)) => self
+                .tags_state
+                .selected()
+                .and_then(|idx| bindings.get(idx))
+                .map(|binding| ConfirmAction::UnbindTag {
+                    secret: secret.short_name.clone(),
+                    binding: binding.clone(),
+                }),
+            _ => None,
+        };
+        if let Some(confirm) = confirm {
+            self.open_confirm(confirm);
+        }
+    }
+
+    /// Binds a tag value to the current secret and records it in the audit log.
+    async fn bind_tag(&mut self, value: &str) -> Result<()> {
+        let Some(resource) = self.current_secret_resource() else {
+            return Ok(());
+        };
+        if let Some(bindings) = &self.tag_bindings {
+            if bindings
+                .iter()
+                .any(|b| b.tag_value == value || b.namespaced_name == value)
+            {
+                self.set_status(&format!("{value} is already bound"), false);
+                return Ok(());
+            }
+        }
+
+        self.is_loading = true;
+        self.set_status(&format!("Binding {value}..."), false);
+        let (result, error) = ApiError::classify_result(
+            async { self.tag_client().await?.bind_tag(&resource, value).await }.await,
+        );
+        match result {
+            Ok(_) => {
+                self.load_tag_bindings().await?;
+                self.set_status(&format!("Bound {value}"), false);
+            }
+            Err(api_error) => self.set_status(&format!("Failed to bind tag: {api_error}"), true),
+        }
+        self.write_audit(AuditAction::BindTag, &resource, Some(value), error);
+        self.is_loading = false;
+        Ok(())
+    }
+
+    /// Unbinds a tag value from the current secret and records it in the
+    /// audit log.
+    async fn unbind_tag(&mut self, binding: &TagBinding) -> Result<()> {
+        let Some(resource) = self.current_secret_resource() else {
+            return Ok(());
+        };
+
+        self.is_loading = true;
+        self.set_status(&format!("Unbinding {}...", binding.label()), false);
+        let (result, error) = ApiError::classify_result(
+            async { self.tag_client().await?.unbind_tag(binding).await }.await,
+        );
+        match result {
+            Ok(()) => {
+                self.load_tag_bindings().await?;
+                self.set_status(&format!("Unbound {}", binding.label()), false);
+            }
+            Err(api_error) => {
+                self.set_status(&format!("Failed to unbind tag: {api_error}"), true);
+            }
+        }
+        self.write_audit(
+            AuditAction::UnbindTag,
+            &resource,
+            Some(binding.label()),
+            error,
+        );
+        self.is_loading = false;
+        Ok(())
+    }
+
+    /// Goes back to the previous view.
+    fn go_back(&mut self) {
+        if let Some(prev) = self.previous_view.take() {
+            self.current_view = prev;
+        } else {
+            self.current_view = View::SecretsList;
+        }
+        self.revealed_value = None;
+    }
+
+    // --- Input handling ---
+
+    /// Asks for the name of a new secret, created in the location of the
+    /// selected secret, or else in the first browsed location.
+    fn start_new_secret(&mut self) {
+        let location = match self
+            .secrets_state
+            .selected()
+            .and_then(|i| self.secrets.get(i))
+        {
+            Some(secret) => secret.location.clone(),
+            None => self.locations.first().cloned().flatten(),
+        };
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(InputMode::NewSecretName { location });
+    }
+
+    /// Sorts the secrets list by the next column, or (`reverse`) reverses the
+    /// order, keeping the selected secret selected.
+    fn sort_secrets(&mut self, reverse: bool) {
+        let sort = match self.sort {
+            Some(sort) if reverse => SecretSort {
+                descending: !sort.descending,
+                ..sort
+            },
+            None if reverse => SecretSort {
+                column: Column::Name,
+                descending: true,
+            },
+            current => SecretSort::next(current, &self.config.columns),
+        };
+        self.sort = Some(sort);
+
+        let selected = self
+            .secrets_state
+            .selected()
+            .and_then(|idx| self.secrets.get(idx))
+            .map(|s| (s.short_name.clone(), s.location.clone()));
+        self.secrets.sort_by(|a, b| sort.compare(a, b));
+        if let Some((name, location)) = selected {
+            let idx = self
+                .secrets
+                .iter()
+                .position(|s| s.short_name == name && s.location == location);
+            self.secrets_state.select(idx);
+        }
+
+        let direction = if sort.descending {
+            "descending"
+        } else {
+            "ascending"
+        };
+        self.set_status(
+            &format!("Sorted by {} ({direction})", sort.column.title()),
+            false,
+        );
+    }
+
+    /// Opens the filter prompt with the current expression to edit.
+    fn start_filter(&mut self, mode: InputMode) {
+        let current = if mode == InputMode::SecretsFilter {
+            &self.filter.secrets
+        } else {
+            &self.filter.versions
+        };
+        self.input_buffer = current.clone().unwrap_or_default();
+        self.cursor_position = self.input_buffer.chars().count();
+        self.filter_recall = None;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(mode);
+    }
+
+    /// Replaces the filter prompt's input with an older (`older`) or newer
+    /// recently used filter.
+    fn recall_filter(&mut self, older: bool) {
+        let count = self.filter_history.recent().len();
+        let recall = match (self.filter_recall, older) {
+            (None, true) if count > 0 => Some(0),
+            (Some(i), true) => Some((i + 1).min(count.saturating_sub(1))),
+            (Some(i), false) => i.checked_sub(1),
+            _ => None,
+        };
+        self.filter_recall = recall;
+        self.input_buffer = recall
+            .and_then(|i| self.filter_history.recent().get(i).cloned())
+            .unwrap_or_default();
+        self.cursor_position = self.input_buffer.chars().count();
+    }
+
+    /// Sets (or with an empty expression clears) a list filter, remembers it
+    /// and reloads the filtered list.
+    async fn apply_filter(&mut self, mode: &InputMode, expression: &str) -> Result<()> {
+        let filter = (!expression.is_empty()).then(|| expression.to_string());
+        if filter.is_some() {
+            if let Err(e) = self.filter_history.add(expression) {
+                logging::error(&format!("Could not save the filter history: {e:#}"));
+            }
+        }
+        if *mode == InputMode::SecretsFilter {
+            self.filter.secrets = filter;
+            self.load_secrets().await
+        } else {
+            self.filter.versions = filter;
+            self.versions_state = ListState::default();
+            self.load_versions().await
+        }
+    }
+
+    /// Returns the secret selected in the list, or the one being viewed.
+    fn focused_secret(&self) -> Option<&SecretInfo> {
+        if self.current_view == View::SecretsList {
+            self.secrets.get(self.secrets_state.selected()?)
+        } else {
+            self.current_secret.as_ref()
+        }
+    }
+
+    /// Opens an input that changes the focused secret.
+    fn start_secret_input(&mut self, mode: InputMode) {
+        if self.focused_secret().is_none() {
+            return;
+        }
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(mode);
+    }
+
+    fn start_new_version(&mut self) {
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Input(InputMode::NewVersionValue);
     }
 
     async fn submit_input(&mut self, mode: InputMode) -> Result<()> {
@@ -626,14 +2235,20 @@ impl App {
         self.input_buffer.clear();
         self.cursor_position = 0;
 
-        if input.is_empty() {
+        // An empty filter clears it
+        let is_filter = matches!(mode, InputMode::SecretsFilter | InputMode::VersionsFilter);
+        if input.is_empty() && !is_filter {
             self.set_status("Input cannot be empty", true);
             self.go_back();
             return Ok(());
         }
 
         match mode {
-            InputMode::NewSecretName => {
+            InputMode::SecretsFilter | InputMode::VersionsFilter => {
+                self.go_back();
+                self.apply_filter(&mode, input.trim()).await?;
+            }
+            InputMode::NewSecretName { location } => {
                 // Validate secret name before API call
                 if let Err(e) = crate::validation::validate_secret_name(&input) {
                     self.set_status(&e, true);
@@ -642,40 +2257,124 @@ impl App {
                 }
 
                 self.is_loading = true;
-                match self.client.as_ref().unwrap().create_secret(&input).await {
+                let (result, error) = ApiError::classify_result(
+                    async {
+                        self.client(location.as_deref())?
+                            .create_secret(&input)
+                            .await
+                    }
+                    .await,
+                );
+                match result {
                     Ok(_) => {
-                        self.set_status(&format!("Created secret: {input}"), false);
+                        self.set_status(
+                            &format!(
+                                "Created secret: {input} ({})",
+                                location_label(location.as_deref())
+                            ),
+                            false,
+                        );
                         self.go_back();
                         self.load_secrets().await?;
                     }
-                    Err(e) => {
-                        self.set_status(&format!("Failed to create secret: {e}"), true);
+                    Err(api_error) => {
+                        self.set_status(&format!("Failed to create secret: {api_error}"), true);
                         self.go_back();
                     }
                 }
+                self.record_audit(
+                    AuditAction::CreateSecret,
+                    &input,
+                    location.as_deref(),
+                    None,
+                    None,
+                    error,
+                );
                 self.is_loading = false;
             }
+            InputMode::Expiration | InputMode::DestroyTtl => {
+                self.go_back();
+                let Some(secret) = self.focused_secret() else {
+                    return Ok(());
+                };
+                let kind = if mode == InputMode::Expiration {
+                    expiration::parse_expiration(&input, secret.expire_time, Utc::now().timestamp())
+                        .map(WriteKind::SetExpiration)
+                } else {
+                    expiration::parse_destroy_ttl(&input).map(WriteKind::SetDestroyTtl)
+                };
+                match kind {
+                    Err(e) => self.set_status(&e, true),
+                    Ok(kind) => {
+                        let write = Write {
+                            kind,
+                            secret: secret.short_name.clone(),
+                            location: secret.location.clone(),
+                            version: None,
+                        };
+                        let etag = secret.etag.clone();
+                        self.perform_write(write, etag).await?;
+                    }
+                }
+            }
+            InputMode::BindTag => {
+                self.go_back();
+                let value = input.trim();
+                match crate::validation::validate_tag_value(value) {
+                    Err(e) => self.set_status(&e, true),
+                    Ok(()) => self.bind_tag(value).await?,
+                }
+            }
+            InputMode::GrantMember => {
+                self.go_back();
+                match iam::parse_grant(&input) {
+                    Err(e) => self.set_status(&e, true),
+                    Ok((member, role)) if iam::is_public_member(&member) => {
+                        if let Some(secret) = &self.current_secret {
+                            let secret = secret.short_name.clone();
+                            self.open_confirm(ConfirmAction::GrantPublicAccess {
+                                secret,
+                                role,
+                                member,
+                            });
+                        }
+                    }
+                    Ok((member, role)) => self.grant_iam_member(&role, &member).await?,
+                }
+            }
             InputMode::NewVersionValue => {
                 if let Some(secret) = &self.current_secret {
                     let secret_name = secret.short_name.clone();
+                    let location = secret.location.clone();
                     self.is_loading = true;
-                    match self
-                        .client
-                        .as_ref()
-                        .unwrap()
-                        .add_version(&secret_name, &input)
-                        .await
-                    {
+                    let (result, error) = ApiError::classify_result(
+                        async {
+                            self.secret_client()?
+                                .add_version(&secret_name, &input)
+                                .await
+                        }
+                        .await,
+                    );
+                    let version = result.as_ref().ok().map(|v| v.version.clone());
+                    match result {
                         Ok(v) => {
                             self.set_status(&format!("Added version: {}", v.version), false);
                             self.go_back();
                             self.load_versions().await?;
                         }
-                        Err(e) => {
-                            self.set_status(&format!("Failed to add version: {e}"), true);
+                        Err(api_error) => {
+                            self.set_status(&format!("Failed to add version: {api_error}"), true);
                             self.go_back();
                         }
                     }
+                    self.record_audit(
+                        AuditAction::AddVersion,
+                        &secret_name,
+                        location.as_deref(),
+                        version.as_deref(),
+                        None,
+                        error,
+                    );
                     self.is_loading = false;
                 }
             }
@@ -729,9 +2428,11 @@ impl App {
     fn confirm_delete_secret(&mut self) {
         if let Some(idx) = self.secrets_state.selected() {
             if let Some(secret) = self.secrets.get(idx) {
-                let secret_name = secret.short_name.clone();
-                self.previous_view = Some(self.current_view.clone());
-                self.current_view = View::Confirm(ConfirmAction::DeleteSecret(secret_name));
+                self.open_confirm(ConfirmAction::DeleteSecret {
+                    secret: secret.short_name.clone(),
+                    location: secret.location.clone(),
+                    etag: secret.etag.clone(),
+                });
             }
         }
     }
@@ -739,53 +2440,90 @@ impl App {
     fn confirm_destroy_version(&mut self) {
         if let (Some(secret), Some(idx)) = (&self.current_secret, self.versions_state.selected()) {
             if let Some(version) = self.versions.get(idx) {
-                let secret_name = secret.short_name.clone();
-                let version_num = version.version.clone();
-                self.previous_view = Some(self.current_view.clone());
-                self.current_view =
-                    View::Confirm(ConfirmAction::DestroyVersion(secret_name, version_num));
+                self.open_confirm(ConfirmAction::DestroyVersion {
+                    secret: secret.short_name.clone(),
+                    version: version.version.clone(),
+                    location: secret.location.clone(),
+                    etag: version.etag.clone(),
+                });
             }
         }
     }
 
+    fn open_confirm(&mut self, confirm: ConfirmAction) {
+        self.previous_view = Some(self.current_view.clone());
+        self.current_view = View::Confirm(confirm);
+        self.input_buffer.clear();
+        self.cursor_position = 0;
+        self.confirm_opened_at = Some(Instant::now());
+    }
+
+    /// Performs a delete or destroy confirmed in a dialog, with the etag
+    /// its target had when the dialog opened.
+    ///
+    /// Without an etag the write could remove a target changed since it was
+    /// shown, so it is refused.
+    async fn perform_confirmed_write(&mut self, write: Write, etag: String) -> Result<()> {
+        if etag.is_empty() {
+            self.go_back();
+            self.set_status(
+                &format!(
+                    "Cannot {} '{}': it was loaded without an etag - refresh and try again",
+                    write.kind.verb(),
+                    write.secret
+                ),
+                true,
+            );
+            return Ok(());
+        }
+        self.perform_write(write, etag).await
+    }
+
     async fn execute_confirmed_action(&mut self, action: ConfirmAction) -> Result<()> {
         match action {
-            ConfirmAction::DeleteSecret(name) => {
-                self.is_loading = true;
-                match self.client.as_ref().unwrap().delete_secret(&name).await {
-                    Ok(()) => {
-                        self.set_status(&format!("Deleted secret: {name}"), false);
-                        self.current_view = View::SecretsList;
-                        self.previous_view = None;
-                        self.load_secrets().await?;
-                    }
-                    Err(e) => {
-                        self.set_status(&format!("Failed to delete: {e}"), true);
-                        self.go_back();
-                    }
-                }
-                self.is_loading = false;
+            ConfirmAction::DeleteSecret {
+                secret,
+                location,
+                etag,
+            } => {
+                let write = Write {
+                    kind: WriteKind::DeleteSecret,
+                    secret,
+                    location,
+                    version: None,
+                };
+                self.perform_confirmed_write(write, etag).await?;
             }
-            ConfirmAction::DestroyVersion(secret_name, version) => {
-                self.is_loading = true;
-                match self
-                    .client
-                    .as_ref()
-                    .unwrap()
-                    .destroy_version(&secret_name, &version)
-                    .await
-                {
-                    Ok(_) => {
-                        self.set_status(&format!("Destroyed version: {version}"), false);
-                        self.go_back();
-                        self.load_versions().await?;
-                    }
-                    Err(e) => {
-                        self.set_status(&format!("Failed to destroy: {e}"), true);
-                        self.go_back();
-                    }
-                }
-                self.is_loading = false;
+            ConfirmAction::DestroyVersion {
+                secret,
+                version,
+                location,
+                etag,
+            } => {
+                let write = Write {
+                    kind: WriteKind::DestroyVersion,
+                    secret,
+                    location,
+                    version: Some(version),
+                };
+                self.perform_confirmed_write(write, etag).await?;
+            }
+            ConfirmAction::RemoveIamMember {
+                binding,
+                role,
+                member,
+                ..
+            } => {
+                self.go_back();
+                self.revoke_iam_member(binding, &role, &member).await?;
+            }
+            ConfirmAction::GrantPublicAccess { role, member, .. } => {
+                self.go_back();
+                self.grant_iam_member(&role, &member).await?;
+            }
+            ConfirmAction::UnbindTag { binding, .. } => {
+                self.go_back();
+                self.unbind_tag(&binding).await?;
             }
         }
         Ok(())
@@ -813,7 +2551,7 @@ impl App {
                         return Ok(());
                     }
                     VersionState::Disabled => {
-                        self.set_status("Version is disabled - press 'e' to enable it first", true);
+                        self.set_status(&self.disabled_version_hint(), true);
                         return Ok(());
                     }
                     _ => {}
@@ -823,19 +2561,28 @@ impl App {
                 let version_num = version.version.clone();
 
                 self.is_loading = true;
-                match self
-                    .client
-                    .as_ref()
-                    .unwrap()
-                    .access_version(&secret_name, &version_num)
-                    .await
-                {
+                let result = async {
+                    self.secret_client()?
+                        .access_version(&secret_name, &version_num)
+                        .await
+                }
+                .await;
+                match result {
                     Ok(value) => {
                         self.revealed_value = Some(value);
-                        self.set_status("Press 's' to hide value", false);
+                        self.reveal_deadline =
+                            self.config.reveal_timeout.map(|t| Instant::now() + t);
+                        let hint = self
+                            .keymap
+                            .key(Scope::SecretDetail, &Action::ToggleSecretValue)
+                            .map_or_else(
+                                || "Value shown".to_string(),
+                                |key| format!("Press '{key}' to hide value"),
+                            );
+                        self.set_status(&hint, false);
                     }
                     Err(e) => {
-                        self.set_status(&format!("Failed to access: {e}"), true);
+                        self.report_error("Failed to access", &e);
                     }
                 }
                 self.is_loading = false;
@@ -856,7 +2603,7 @@ impl App {
                         return Ok(());
                     }
                     VersionState::Disabled => {
-                        self.set_status("Version is disabled - press 'e' to enable it first", true);
+                        self.set_status(&self.disabled_version_hint(), true);
                         return Ok(());
                     }
                     _ => {}
@@ -866,19 +2613,31 @@ impl App {
                 let version_num = version.version.clone();
 
                 self.is_loading = true;
-                match self
-                    .client
-                    .as_ref()
-                    .unwrap()
-                    .access_version(&secret_name, &version_num)
-                    .await
-                {
+                let result = async {
+                    self.secret_client()?
+                        .access_version(&secret_name, &version_num)
+                        .await
+                }
+                .await;
+                match result {
                     Ok(value) => {
                         // Try to copy to clipboard
                         match arboard::Clipboard::new() {
                             Ok(mut clipboard) => {
                                 if clipboard.set_text(&value).is_ok() {
-                                    self.set_status("Copied to clipboard!", false);
+                                    if let Some(timeout) = self.config.clipboard_timeout {
+                                        self.clipboard_deadline =
+                                            Some((Instant::now() + timeout, value));
+                                        self.set_status(
+                                            &format!(
+                                                "Copied to clipboard! (clears in {}s)",
+                                                timeout.as_secs()
+                                            ),
+                                            false,
+                                        );
+                                    } else {
+                                        self.set_status("Copied to clipboard!", false);
+                                    }
                                 } else {
                                     self.set_status("Failed to copy to clipboard", true);
                                 }
@@ -889,7 +2648,7 @@ impl App {
                         }
                     }
                     Err(e) => {
-                        self.set_status(&format!("Failed to access: {e}"), true);
+                        self.report_error("Failed to access", &e);
                     }
                 }
                 self.is_loading = false;
@@ -908,26 +2667,14 @@ impl App {
                     return Ok(());
                 }
 
-                let secret_name = secret.short_name.clone();
-                let version_num = version.version.clone();
-
-                self.is_loading = true;
-                match self
-                    .client
-                    .as_ref()
-                    .unwrap()
-                    .enable_version(&secret_name, &version_num)
-                    .await
-                {
-                    Ok(_) => {
-                        self.set_status(&format!("Enabled version: {version_num}"), false);
-                        self.load_versions().await?;
-                    }
-                    Err(e) => {
-                        self.set_status(&format!("Failed to enable: {e}"), true);
-                    }
-                }
-                self.is_loading = false;
+                let write = Write {
+                    kind: WriteKind::EnableVersion,
+                    secret: secret.short_name.clone(),
+                    location: secret.location.clone(),
+                    version: Some(version.version.clone()),
+                };
+                let etag = version.etag.clone();
+                self.perform_write(write, etag).await?;
             }
         }
         Ok(())
@@ -941,38 +2688,404 @@ impl App {
                     return Ok(());
                 }
 
-                let secret_name = secret.short_name.clone();
-                let version_num = version.version.clone();
+                let write = Write {
+                    kind: WriteKind::DisableVersion,
+                    secret: secret.short_name.clone(),
+                    location: secret.location.clone(),
+                    version: Some(version.version.clone()),
+                };
+                let etag = version.etag.clone();
+                self.perform_write(write, etag).await?;
+            }
+        }
+        Ok(())
+    }
 
-                self.is_loading = true;
-                match self
-                    .client
-                    .as_ref()
-                    .unwrap()
-                    .disable_version(&secret_name, &version_num)
-                    .await
-                {
-                    Ok(_) => {
-                        self.set_status(&format!("Disabled version: {version_num}"), false);
-                        self.load_versions().await?;
+    // --- Writes with etags ---
+
+    /// Sends a write with the etag its target was loaded with.
+    ///
+    /// If the target changed in the meantime, the conflict dialog shows it
+    /// as now stored and offers to retry.
+    async fn perform_write(&mut self, write: Write, etag: String) -> Result<()> {
+        // Writes are confirmed in a dialog, or retried from the conflict dialog
+        if matches!(self.current_view, View::Confirm(_) | View::Conflict(_)) {
+            self.go_back();
+        }
+
+        self.is_loading = true;
+        let (result, error) = ApiError::classify_result(self.send_write(&write, &etag).await);
+        match result {
+            Ok(updated) => match write.kind {
+                WriteKind::DeleteSecret => {
+                    self.set_status(&format!("Deleted secret: {}", write.secret), false);
+                    self.current_view = View::SecretsList;
+                    self.previous_view = None;
+                    self.load_secrets().await?;
+                }
+                WriteKind::SetExpiration(expire_time) => {
+                    if let Some(updated) = updated {
+                        self.replace_secret(updated);
                     }
-                    Err(e) => {
-                        self.set_status(&format!("Failed to disable: {e}"), true);
+                    let status = match expire_time {
+                        Some(expire_time) => format!(
+                            "{} expires {} ({})",
+                            write.secret,
+                            expiration::format_time(expire_time),
+                            expiration::countdown(expire_time, Utc::now().timestamp())
+                        ),
+                        None => format!("{} no longer expires", write.secret),
+                    };
+                    self.set_status(&status, false);
+                }
+                WriteKind::SetDestroyTtl(ttl) => {
+                    if let Some(updated) = updated {
+                        self.replace_secret(updated);
                     }
+                    let status = match ttl {
+                        Some(ttl) => format!(
+                            "{}: destroyed versions now stay disabled for {} first",
+                            write.secret,
+                            format_ttl(ttl)
+                        ),
+                        None => format!("{}: versions are now destroyed immediately", write.secret),
+                    };
+                    self.set_status(&status, false);
+                }
+                WriteKind::EnableVersion
+                | WriteKind::DisableVersion
+                | WriteKind::DestroyVersion => {
+                    let version = write.version.as_deref().unwrap_or_default();
+                    let was_scheduled = self
+                        .find_version(version)
+                        .is_some_and(|v| v.scheduled_destroy_time.is_some());
+                    self.load_versions().await?;
+                    let scheduled = self
+                        .find_version(version)
+                        .and_then(|v| v.scheduled_destroy_time);
+                    let status = match (write.kind, scheduled) {
+                        (WriteKind::EnableVersion, _) if was_scheduled => {
+                            format!("Cancelled destruction of version {version} (enabled again)")
+                        }
+                        (WriteKind::EnableVersion, _) => format!("Enabled version: {version}"),
+                        (WriteKind::DisableVersion, _) => format!("Disabled version: {version}"),
+                        (_, Some(time)) => format!(
+                            "Version {version} is destroyed {} ({}){}",
+                            expiration::format_time(time),
+                            expiration::countdown(time, Utc::now().timestamp()),
+                            self.keymap
+                                .key(Scope::SecretDetail, &Action::Enable)
+                                .map_or_else(String::new, |key| format!(
+                                    " - press {key} before then to cancel"
+                                ))
+                        ),
+                        (_, None) => format!("Destroyed version: {version}"),
+                    };
+                    self.set_status(&status, false);
+                }
+            },
+            Err(api_error) => {
+                if api_error.kind == ErrorKind::Conflict {
+                    self.open_conflict(write.clone()).await;
+                } else {
+                    self.set_status(
+                        &format!("Failed to {}: {api_error}", write.kind.verb()),
+                        true,
+                    );
                 }
-                self.is_loading = false;
             }
         }
+        self.record_audit(
+            write.kind.audit_action(),
+            &write.secret,
+            write.location.as_deref(),
+            write.version.as_deref(),
+            write.kind.detail().as_deref(),
+            error,
+        );
+        self.is_loading = false;
         Ok(())
     }
 
+    /// Sends a write through the client for the secret's location.
+    ///
+    /// Returns the secret as stored after writes to the secret itself.
+    async fn send_write(&self, write: &Write, etag: &str) -> Result<Option<SecretInfo>> {
+        let client = self.client(write.location.as_deref())?;
+        let version = write.version.as_deref().unwrap_or_default();
+        match write.kind {
+            WriteKind::EnableVersion => {
+                client.enable_version(&write.secret, version, etag).await?;
+            }
+            WriteKind::DisableVersion => {
+                client.disable_version(&write.secret, version, etag).await?;
+            }
+            WriteKind::DestroyVersion => {
+                client.destroy_version(&write.secret, version, etag).await?;
+            }
+            WriteKind::DeleteSecret => client.delete_secret(&write.secret, etag).await?,
+            WriteKind::SetExpiration(expire_time) => {
+                let updated = client
+                    .update_expiration(&write.secret, expire_time, etag)
+                    .await?;
+                return Ok(Some(updated));
+            }
+            WriteKind::SetDestroyTtl(ttl) => {
+                let updated = client
+                    .update_version_destroy_ttl(&write.secret, ttl, etag)
+                    .await?;
+                return Ok(Some(updated));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the loaded version with the given number.
+    fn find_version(&self, version: &str) -> Option<&VersionInfo> {
+        self.versions.iter().find(|v| v.version == version)
+    }
+
+    /// Replaces the loaded copies of a secret with the secret as stored.
+    fn replace_secret(&mut self, fresh: SecretInfo) {
+        let same =
+            |s: &SecretInfo| s.short_name == fresh.short_name && s.location == fresh.location;
+        if let Some(current) = self.current_secret.as_mut().filter(|s| same(s)) {
+            *current = fresh.clone();
+        }
+        if let Some(stale) = self.secrets.iter_mut().find(|s| same(s)) {
+            *stale = fresh;
+        }
+    }
+
+    /// Loads the target of a rejected write and opens the conflict dialog.
+    ///
+    /// The loaded state also replaces the stale copy in the lists.
+    async fn open_conflict(&mut self, write: Write) {
+        let client = match self.client(write.location.as_deref()) {
+            Ok(client) => client,
+            Err(e) => {
+                self.report_error("Could not load the current state", &e);
+                return;
+            }
+        };
+        let conflict = match &write.version {
+            Some(version) => client
+                .get_version(&write.secret, version)
+                .await
+                .map(|fresh| {
+                    let conflict = Conflict::from_version(write.clone(), &fresh);
+                    if let Some(stale) = self.versions.iter_mut().find(|v| v.version == *version) {
+                        *stale = fresh;
+                    }
+                    conflict
+                }),
+            None => client.get_secret(&write.secret).await.map(|fresh| {
+                let conflict = Conflict::from_secret(write.clone(), &fresh);
+                self.replace_secret(fresh);
+                conflict
+            }),
+        };
+
+        match conflict {
+            Ok(conflict) => {
+                self.set_status(
+                    &format!(
+                        "Could not {} - changed since it was loaded",
+                        write.kind.verb()
+                    ),
+                    true,
+                );
+                self.previous_view = Some(self.current_view.clone());
+                self.current_view = View::Conflict(conflict);
+                self.confirm_opened_at = Some(Instant::now());
+            }
+            Err(e) => self.set_status(
+                &format!(
+                    "Could not {} - changed since it was loaded, and reloading failed: {}",
+                    write.kind.verb(),
+                    ApiError::classify(&e)
+                ),
+                true,
+            ),
+        }
+    }
+
+    // --- Audit history ---
+
+    /// Records a modifying action in the audit log.
+    ///
+    /// A failed write is reported in the status bar, replacing the message
+    /// about the action itself.
+    fn record_audit(
+        &mut self,
+        action: AuditAction,
+        secret: &str,
+        location: Option<&str>,
+        version: Option<&str>,
+        detail: Option<&str>,
+        error: Option<String>,
+    ) {
+        let mut resource = self.secret_resource(secret, location);
+        if let Some(version) = version {
+            resource = format!("{resource}/versions/{version}");
+        }
+        self.write_audit(action, &resource, detail, error);
+    }
+
+    /// Appends a record to the audit log, reporting a failed write.
+    fn write_audit(
+        &mut self,
+        action: AuditAction,
+        resource: &str,
+        detail: Option<&str>,
+        error: Option<String>,
+    ) {
+        if let Err(e) =
+            self.audit
+                .record_with_detail(&self.project_id, action, resource, detail, error)
+        {
+            self.set_status(&format!("Failed to write audit log: {e:#}"), true);
+        }
+    }
+
+    /// Returns the full resource name of a secret in the current project.
+    fn secret_resource(&self, secret: &str, location: Option<&str>) -> String {
+        match location {
+            Some(location) => format!(
+                "projects/{}/locations/{location}/secrets/{secret}",
+                self.project_id
+            ),
+            None => format!("projects/{}/secrets/{secret}", self.project_id),
+        }
+    }
+
+    /// Loads the audit log and shows the history view.
+    fn open_history(&mut self) {
+        match self.audit.read() {
+            Ok(records) => {
+                self.history = records;
+                self.history_state.select(if self.history.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                });
+                if self.current_view != View::History {
+                    self.previous_view = Some(self.current_view.clone());
+                    self.current_view = View::History;
+                }
+            }
+            Err(e) => self.set_status(&format!("Failed to read audit log: {e:#}"), true),
+        }
+    }
+
+    /// Handles actions in the history view.
+    fn handle_history_action(&mut self, action: &Action) -> Option<AppAction> {
+        let len = self.history.len();
+        let current = self.history_state.selected().unwrap_or(0);
+        match action {
+            Action::Quit => return Some(AppAction::Quit),
+            Action::Back => self.go_back(),
+            Action::Refresh => self.open_history(),
+            Action::Up if len > 0 => {
+                self.history_state
+                    .select(Some(if current == 0 { len - 1 } else { current - 1 }));
+            }
+            Action::Down if len > 0 => {
+                self.history_state
+                    .select(Some(if current >= len - 1 { 0 } else { current + 1 }));
+            }
+            Action::Top if len > 0 => self.history_state.select(Some(0)),
+            Action::Bottom if len > 0 => self.history_state.select(Some(len - 1)),
+            _ => {}
+        }
+        None
+    }
+
+    // --- Client helpers ---
+
+    /// Returns the client for secrets in `location`.
+    ///
+    /// Fails when no clients were created, e.g. because the first listing
+    /// of secrets failed, or when `location` is not browsed: a write must
+    /// never reach a secret of the same name in another location.
+    fn client(&self, location: Option<&str>) -> Result<&SecretClient> {
+        if self.clients.is_empty() {
+            bail!("Not connected to Secret Manager - refresh the secrets list first");
+        }
+        self.clients
+            .iter()
+            .find(|c| c.location() == location)
+            .with_context(|| format!("Not browsing location {}", location_label(location)))
+    }
+
+    /// Returns the client for the secret being viewed.
+    fn secret_client(&self) -> Result<&SecretClient> {
+        self.client(
+            self.current_secret
+                .as_ref()
+                .and_then(|s| s.location.as_deref()),
+        )
+    }
+
     // --- Status message helpers ---
 
+    /// Reports a failed list call, switching to the auth screen only when
+    /// credentials are the problem.
+    fn report_load_error(&mut self, err: &anyhow::Error) {
+        let api_error = ApiError::classify(err);
+        self.set_status(&api_error.to_string(), true);
+        if api_error.is_auth() {
+            self.auth_error = Some(api_error.to_string());
+            self.current_view = View::AuthRequired;
+        }
+    }
+
+    /// Status message for reading a disabled version, naming the enable key.
+    fn disabled_version_hint(&self) -> String {
+        self.keymap
+            .key(Scope::SecretDetail, &Action::Enable)
+            .map_or_else(
+                || "Version is disabled - enable it first".to_string(),
+                |key| format!("Version is disabled - press '{key}' to enable it first"),
+            )
+    }
+
+    /// Reports a failed call in the status bar as `context: error`.
+    fn report_error(&mut self, context: &str, err: &anyhow::Error) {
+        self.set_status(&format!("{context}: {}", ApiError::classify(err)), true);
+    }
+
+    /// Sets the status message, noting any retries the last API call needed.
     fn set_status(&mut self, text: &str, is_error: bool) {
-        self.status = Some(StatusMessage {
-            text: text.to_string(),
-            is_error,
-        });
+        let retries: u32 = self
+            .clients
+            .iter()
+            .map(SecretClient::take_retries)
+            .sum::<u32>()
+            + self
+                .tag_clients
+                .iter()
+                .map(|(_, c)| c.take_retries())
+                .sum::<u32>()
+            + self
+                .project_client
+                .as_ref()
+                .map_or(0, ProjectClient::take_retries)
+            + self
+                .location_client
+                .as_ref()
+                .map_or(0, SecretClient::take_retries);
+        let text = match retries {
+            0 => text.to_string(),
+            1 => format!("{text} (after 1 retry)"),
+            n => format!("{text} (after {n} retries)"),
+        };
+        if is_error {
+            logging::error(&text);
+        } else {
+            logging::info(&text);
+        }
+        self.status = Some(StatusMessage { text, is_error });
     }
 }
 
@@ -993,6 +3106,11 @@ mod tests {
             version_aliases: vec![],
             rotation: None,
             version_destroy_ttl: None,
+            location: None,
+            etag: String::new(),
+            expire_time: None,
+            encryption: vec![],
+            versions: None,
         }
     }
 
@@ -1005,7 +3123,7 @@ mod tests {
         assert_eq!(app.project_id, "my-project");
         assert_eq!(app.current_view, View::SecretsList);
         assert!(app.secrets.is_empty());
-        assert!(app.client.is_none());
+        assert!(app.clients.is_empty());
     }
 
     #[test]
@@ -1080,6 +3198,8 @@ mod tests {
                 destroy_time: None,
                 scheduled_destroy_time: None,
                 has_checksum: false,
+                kms_key_versions: vec![],
+                etag: String::new(),
             },
             VersionInfo {
                 version: "2".to_string(),
@@ -1088,6 +3208,8 @@ mod tests {
                 destroy_time: None,
                 scheduled_destroy_time: None,
                 has_checksum: false,
+                kms_key_versions: vec![],
+                etag: String::new(),
             },
         ];
         app.versions_state.select(Some(0));
@@ -1124,6 +3246,461 @@ mod tests {
         assert_eq!(app.current_view, View::SecretsList); // Default fallback
     }
 
+    #[test]
+    fn test_read_only_from_project_pattern() {
+        let mut app = App::new(Some("payments-prod".to_string()));
+        app.config.read_only_projects = vec!["*-prod".to_string()];
+        assert!(app.is_read_only());
+
+        app.project_id = "payments-dev".to_string();
+        assert!(!app.is_read_only());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_refuses_mutating_actions() {
+        let mut app = App::new(Some("test".to_string()));
+        app.read_only = true;
+
+        app.handle_event(Action::NewSecret).await.unwrap();
+
+        assert_eq!(app.current_view, View::SecretsList);
+        assert!(app.status.as_ref().is_some_and(|s| s.is_error));
+    }
+
+    #[test]
+    fn test_reveal_timeout_hides_value() {
+        let mut app = App::new(Some("test".to_string()));
+        app.revealed_value = Some("secret-value".to_string());
+        app.reveal_deadline = Some(Instant::now());
+
+        app.on_tick();
+
+        assert!(app.revealed_value.is_none());
+    }
+
+    fn open_delete_dialog(app: &mut App) {
+        app.secrets = vec![mock_secret("api-key")];
+        app.secrets_state.select(Some(0));
+        app.confirm_delete_secret();
+        // Pretend the input delay has passed
+        app.confirm_opened_at = Instant::now().checked_sub(CONFIRM_INPUT_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_requires_typed_name() {
+        let mut app = App::new(Some("test".to_string()));
+        open_delete_dialog(&mut app);
+
+        // Enter alone does not confirm
+        app.handle_event(Action::Enter).await.unwrap();
+        assert!(matches!(app.current_view, View::Confirm(_)));
+        assert!(app.status.as_ref().is_some_and(|s| s.is_error));
+
+        // Wrong name does not confirm either
+        for c in "api".chars() {
+            app.handle_event(Action::Char(c)).await.unwrap();
+        }
+        app.handle_event(Action::Enter).await.unwrap();
+        assert!(matches!(app.current_view, View::Confirm(_)));
+        assert_eq!(app.input_buffer, "api");
+    }
+
+    #[tokio::test]
+    async fn test_confirm_ignores_buffered_keys() {
+        let mut app = App::new(Some("test".to_string()));
+        open_delete_dialog(&mut app);
+        app.confirm_opened_at = Some(Instant::now());
+
+        app.handle_event(Action::Char('x')).await.unwrap();
+        assert!(app.input_buffer.is_empty());
+
+        // Cancelling is always allowed
+        app.handle_event(Action::Back).await.unwrap();
+        assert_eq!(app.current_view, View::SecretsList);
+    }
+
+    #[tokio::test]
+    async fn test_delete_without_etag_is_refused() {
+        let mut app = App::new(Some("test".to_string()));
+        let mut secret = mock_secret("api-key");
+        secret.location = Some("europe-west1".to_string());
+        app.secrets = vec![secret];
+        app.secrets_state.select(Some(0));
+        app.confirm_delete_secret();
+        app.confirm_opened_at = Instant::now().checked_sub(CONFIRM_INPUT_DELAY);
+        assert_eq!(
+            app.current_view,
+            View::Confirm(ConfirmAction::DeleteSecret {
+                secret: "api-key".to_string(),
+                location: Some("europe-west1".to_string()),
+                etag: String::new(),
+            })
+        );
+
+        for c in "api-key".chars() {
+            app.handle_event(Action::Char(c)).await.unwrap();
+        }
+        app.handle_event(Action::Enter).await.unwrap();
+
+        assert_eq!(app.current_view, View::SecretsList);
+        let status = app.status.as_ref().unwrap();
+        assert!(status.is_error);
+        assert!(status.text.contains("without an etag"), "{}", status.text);
+    }
+
+    #[test]
+    fn test_protected_project_requires_project_id() {
+        let mut app = App::new(Some("payments-prod".to_string()));
+        let confirm = ConfirmAction::DeleteSecret {
+            secret: "api-key".to_string(),
+            location: None,
+            etag: "\"1\"".to_string(),
+        };
+        assert_eq!(app.confirm_phrase(&confirm), "api-key");
+
+        app.config.protected_projects = vec!["*-prod".to_string()];
+        assert_eq!(app.confirm_phrase(&confirm), "payments-prod");
+    }
+
+    #[tokio::test]
+    async fn test_history_view_opens_and_closes() {
+        let mut app = App::new(Some("test".to_string()));
+
+        app.handle_event(Action::History).await.unwrap();
+        assert_eq!(app.current_view, View::History);
+        assert!(app.history.is_empty());
+
+        app.handle_event(Action::Back).await.unwrap();
+        assert_eq!(app.current_view, View::SecretsList);
+    }
+
+    #[tokio::test]
+    async fn test_location_selection_toggles_and_needs_one() {
+        let mut app = App::new(Some("test".to_string()));
+        app.available_locations = vec![None, Some("europe-west1".to_string())];
+        app.location_selection = vec![None];
+        app.current_view = View::LocationSelector;
+        app.locations_state.select(Some(1));
+
+        app.handle_event(Action::ToggleSelection).await.unwrap();
+        assert_eq!(
+            app.location_selection,
+            vec![None, Some("europe-west1".to_string())]
+        );
+
+        app.location_selection.clear();
+        app.handle_event(Action::Enter).await.unwrap();
+        assert_eq!(app.current_view, View::LocationSelector);
+        assert_eq!(app.locations, vec![None]);
+        assert!(app.status.as_ref().is_some_and(|s| s.is_error));
+    }
+
+    #[test]
+    fn test_conflict_shows_version_as_stored() {
+        let write = Write {
+            kind: WriteKind::DisableVersion,
+            secret: "api-key".to_string(),
+            location: None,
+            version: Some("2".to_string()),
+        };
+        let stored = VersionInfo {
+            version: "2".to_string(),
+            state: VersionState::Disabled,
+            create_time: "2024-01-02".to_string(),
+            destroy_time: None,
+            scheduled_destroy_time: None,
+            has_checksum: false,
+            kms_key_versions: vec![],
+            etag: "\"16f2a\"".to_string(),
+        };
+
+        let conflict = Conflict::from_version(write, &stored);
+
+        assert_eq!(conflict.etag, "\"16f2a\"");
+        assert_eq!(conflict.current[0], ("State", "Disabled".to_string()));
+        assert_eq!(conflict.write.kind.verb(), "disable");
+    }
+
+    #[test]
+    fn test_secret_is_cmek_only_when_every_replica_has_a_key() {
+        use crate::secret_client::ReplicaEncryption;
+
+        let replica = |location: &str, key: Option<&str>| ReplicaEncryption {
+            location: location.to_string(),
+            kms_key: key.map(str::to_string),
+        };
+        let mut secret = mock_secret("api-key");
+        assert!(!secret.is_cmek());
+
+        secret.encryption = vec![
+            replica(
+                "europe-west1",
+                Some("projects/p/locations/europe-west1/keyRings/r/cryptoKeys/k"),
+            ),
+            replica("europe-west4", None),
+        ];
+        assert!(!secret.is_cmek());
+
+        secret.encryption[1].kms_key =
+            Some("projects/p/locations/europe-west4/keyRings/r/cryptoKeys/k".to_string());
+        assert!(secret.is_cmek());
+    }
+
+    #[test]
+    fn test_destroy_ttl_write_is_audited_with_ttl() {
+        let kind = WriteKind::SetDestroyTtl(Some(7 * 86_400));
+        assert_eq!(kind.audit_action(), AuditAction::UpdateDestroyTtl);
+        assert_eq!(
+            kind.detail().as_deref(),
+            Some(format_ttl(7 * 86_400).as_str())
+        );
+        assert_eq!(
+            WriteKind::SetDestroyTtl(None).detail().as_deref(),
+            Some("off")
+        );
+
+        let mut secret = mock_secret("api-key");
+        secret.version_destroy_ttl = Some(format_ttl(86_400));
+        let write = Write {
+            kind,
+            secret: "api-key".to_string(),
+            location: None,
+            version: None,
+        };
+        let conflict = Conflict::from_secret(write, &secret);
+        assert!(conflict
+            .current
+            .contains(&("Destroy TTL", format_ttl(86_400))));
+    }
+
+    #[tokio::test]
+    async fn test_conflict_dialog_waits_then_cancels() {
+        let mut app = App::new(Some("test".to_string()));
+        let mut secret = mock_secret("api-key");
+        secret.etag = "\"1\"".to_string();
+        let write = Write {
+            kind: WriteKind::DeleteSecret,
+            secret: "api-key".to_string(),
+            location: None,
+            version: None,
+        };
+        app.previous_view = Some(View::SecretsList);
+        app.current_view = View::Conflict(Conflict::from_secret(write, &secret));
+        app.confirm_opened_at = Some(Instant::now());
+
+        // A key typed ahead does not retry
+        app.handle_event(Action::Enter).await.unwrap();
+        assert!(matches!(app.current_view, View::Conflict(_)));
+
+        app.handle_event(Action::Back).await.unwrap();
+        assert_eq!(app.current_view, View::SecretsList);
+        assert!(!app.status.as_ref().unwrap().is_error);
+    }
+
+    #[tokio::test]
+    async fn test_conflict_on_delete_asks_for_the_name_again() {
+        // No client is connected, so a delete sent here would fail loudly
+        let mut app = App::new(Some("test".to_string()));
+        let mut secret = mock_secret("api-key");
+        secret.etag = "\"2\"".to_string();
+        let write = Write {
+            kind: WriteKind::DeleteSecret,
+            secret: "api-key".to_string(),
+            location: None,
+            version: None,
+        };
+        app.previous_view = Some(View::SecretsList);
+        app.current_view = View::Conflict(Conflict::from_secret(write, &secret));
+        app.confirm_opened_at = None;
+
+        app.handle_event(Action::Enter).await.unwrap();
+        assert_eq!(
+            app.current_view,
+            View::Confirm(ConfirmAction::DeleteSecret {
+                secret: "api-key".to_string(),
+                location: None,
+                etag: "\"2\"".to_string(),
+            })
+        );
+        assert_eq!(app.previous_view, Some(View::SecretsList));
+        assert!(app.status.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_iam_tab_revoke_asks_for_confirmation() {
+        let mut app = App::new(Some("test".to_string()));
+        app.current_secret = Some(mock_secret("api-key"));
+        app.previous_view = Some(View::SecretsList);
+        app.current_view = View::SecretIam;
+        let mut policy = IamPolicy::default();
+        policy.add_member(iam::DEFAULT_ROLE, "user:ana@example.com");
+        app.iam_policy = Some(policy);
+        app.iam_state.select(Some(0));
+
+        app.handle_event(Action::Delete).await.unwrap();
+        let View::Confirm(confirm) = app.current_view.clone() else {
+            panic!("expected a confirmation dialog");
+        };
+        assert_eq!(
+            confirm,
+            ConfirmAction::RemoveIamMember {
+                secret: "api-key".to_string(),
+                binding: 0,
+                role: iam::DEFAULT_ROLE.to_string(),
+                member: "user:ana@example.com".to_string(),
+            }
+        );
+        assert_eq!(app.confirm_phrase(&confirm), "api-key");
+
+        app.handle_event(Action::Back).await.unwrap();
+        assert_eq!(app.current_view, View::SecretIam);
+    }
+
+    #[tokio::test]
+    async fn test_tags_tab_unbind_asks_for_confirmation() {
+        let mut app = App::new(Some("test".to_string()));
+        app.current_secret = Some(mock_secret("api-key"));
+        app.previous_view = Some(View::SecretsList);
+        app.current_view = View::SecretTags;
+        let binding = TagBinding {
+            name: "tagBindings/x".to_string(),
+            tag_value: "tagValues/281484".to_string(),
+            namespaced_name: "123456789/env/prod".to_string(),
+        };
+        app.tag_bindings = Some(vec![binding.clone()]);
+        app.tags_state.select(Some(0));
+
+        app.handle_event(Action::Delete).await.unwrap();
+        let View::Confirm(confirm) = app.current_view.clone() else {
+            panic!("expected a confirmation dialog");
+        };
+        assert_eq!(
+            confirm,
+            ConfirmAction::UnbindTag {
+                secret: "api-key".to_string(),
+                binding,
+            }
+        );
+
+        // The last tab switches back to the versions
+        app.handle_event(Action::Back).await.unwrap();
+        app.handle_event(Action::SwitchTab).await.unwrap();
+        assert_eq!(app.current_view, View::SecretDetail);
+    }
+
+    #[tokio::test]
+    async fn test_filter_prompt_edits_current_filter_and_recalls_recent() {
+        let mut app = App::new(Some("test".to_string()));
+        app.filter.secrets = Some("name:api".to_string());
+        app.filter_history.add("labels.team=payments").unwrap();
+        app.filter_history.add("name:api").unwrap();
+
+        app.handle_event(Action::Filter).await.unwrap();
+        assert_eq!(app.current_view, View::Input(InputMode::SecretsFilter));
+        assert_eq!(app.input_buffer, "name:api");
+        assert_eq!(app.cursor_position, 8);
+
+        app.handle_event(Action::Up).await.unwrap();
+        app.handle_event(Action::Up).await.unwrap();
+        assert_eq!(app.input_buffer, "labels.team=payments");
+        app.handle_event(Action::Up).await.unwrap();
+        assert_eq!(app.input_buffer, "labels.team=payments");
+        app.handle_event(Action::Down).await.unwrap();
+        assert_eq!(app.input_buffer, "name:api");
+        app.handle_event(Action::Down).await.unwrap();
+        assert!(app.input_buffer.is_empty());
+        assert_eq!(app.cursor_position, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sort_cycles_columns_and_keeps_selection() {
+        let mut app = App::new(Some("test".to_string()));
+        app.config.columns = vec![Column::Index, Column::Name, Column::Expiration];
+        app.secrets = vec![
+            mock_secret("b"),
+            mock_secret("a"),
+            mock_secret("c"),
+            mock_secret("d"),
+        ];
+        app.secrets[0].expire_time = Some(300);
+        app.secrets[2].expire_time = Some(100);
+        app.secrets_state.select(Some(0));
+        let names = |app: &App| {
+            app.secrets
+                .iter()
+                .map(|s| s.short_name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // The index column is skipped
+        app.handle_event(Action::Sort).await.unwrap();
+        assert_eq!(names(&app), ["a", "b", "c", "d"]);
+        assert_eq!(app.secrets_state.selected(), Some(1));
+
+        // Secrets that do not expire are listed last in both directions
+        app.handle_event(Action::Sort).await.unwrap();
+        assert_eq!(names(&app), ["c", "b", "a", "d"]);
+        app.handle_event(Action::ReverseSort).await.unwrap();
+        assert_eq!(names(&app), ["b", "c", "a", "d"]);
+        assert_eq!(app.secrets_state.selected(), Some(0));
+
+        app.handle_event(Action::Sort).await.unwrap();
+        assert_eq!(
+            app.sort,
+            Some(SecretSort {
+                column: Column::Name,
+                descending: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_without_clients_reports_instead_of_panicking() {
+        // As after a failed first listing: no clients were created
+        let mut app = App::new(Some("test".to_string()));
+        app.handle_event(Action::NewSecret).await.unwrap();
+        for c in "api-key".chars() {
+            app.handle_event(Action::Char(c)).await.unwrap();
+        }
+        app.handle_event(Action::Enter).await.unwrap();
+
+        assert_eq!(app.current_view, View::SecretsList);
+        let status = app.status.as_ref().unwrap();
+        assert!(status.is_error);
+        assert!(status.text.contains("Not connected"), "{}", status.text);
+    }
+
+    #[tokio::test]
+    async fn test_commands_without_permission_are_refused() {
+        let mut app = App::new(Some("test".to_string()));
+        app.secrets = vec![mock_secret("api-key")];
+        app.secrets_state.select(Some(0));
+        app.granted_permissions.insert(
+            "projects/test/secrets/api-key".to_string(),
+            vec!["secretmanager.versions.access".to_string()],
+        );
+
+        assert_eq!(
+            app.missing_permission(&Action::Delete),
+            Some("secretmanager.secrets.delete")
+        );
+        app.handle_event(Action::Delete).await.unwrap();
+        assert_eq!(app.current_view, View::SecretsList);
+        assert!(app.status.as_ref().unwrap().is_error);
+
+        app.current_secret = Some(mock_secret("api-key"));
+        app.current_view = View::SecretDetail;
+        assert_eq!(app.missing_permission(&Action::Copy), None);
+        assert_eq!(
+            app.missing_permission(&Action::NewVersion),
+            Some("secretmanager.versions.add")
+        );
+
+        // Untested secrets keep every command
+        app.current_secret = Some(mock_secret("other"));
+        assert_eq!(app.missing_permission(&Action::NewVersion), None);
+    }
+
     // --- Mode Transition Tests ---
 
     #[test]
@@ -1135,10 +3712,71 @@ mod tests {
         app.start_new_secret();
 
         assert!(app.input_buffer.is_empty()); // Buffer cleared
-        assert_eq!(app.current_view, View::Input(InputMode::NewSecretName));
+        assert_eq!(
+            app.current_view,
+            View::Input(InputMode::NewSecretName { location: None })
+        );
         assert_eq!(app.previous_view, Some(View::SecretsList)); // Saved for go_back
     }
 
+    #[test]
+    fn test_grant_without_policy_names_the_bound_refresh_key() {
+        use crate::keymap::{KeyOverride, KeySequence};
+
+        let mut app = App::new(Some("test".to_string()));
+        app.current_view = View::SecretIam;
+        (app.keymap, _) = Keymap::new(&[KeyOverride {
+            scope: None,
+            action: Action::Refresh,
+            keys: vec![KeySequence::parse("f5").unwrap()],
+        }]);
+
+        app.start_grant();
+
+        let status = app.status.as_ref().unwrap();
+        assert_eq!(status.text, "IAM policy not loaded - press 'F5' to retry");
+    }
+
+    #[test]
+    fn test_disabled_version_hint_names_the_bound_enable_key() {
+        use crate::keymap::{KeyOverride, KeySequence};
+
+        let mut app = App::new(Some("test".to_string()));
+        assert_eq!(
+            app.disabled_version_hint(),
+            "Version is disabled - press 'e' to enable it first"
+        );
+
+        (app.keymap, _) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretDetail),
+            action: Action::Enable,
+            keys: vec![KeySequence::parse("ctrl+e").unwrap()],
+        }]);
+        assert_eq!(
+            app.disabled_version_hint(),
+            "Version is disabled - press 'Ctrl+e' to enable it first"
+        );
+    }
+
+    #[test]
+    fn test_new_secret_goes_to_the_selected_location() {
+        let mut app = App::new(Some("test".to_string()));
+        app.locations = vec![None, Some("europe-west1".to_string())];
+        let mut regional = mock_secret("db-password");
+        regional.location = Some("europe-west1".to_string());
+        app.secrets = vec![mock_secret("api-key"), regional];
+        app.secrets_state.select(Some(1));
+
+        app.start_new_secret();
+
+        assert_eq!(
+            app.current_view,
+            View::Input(InputMode::NewSecretName {
+                location: Some("europe-west1".to_string())
+            })
+        );
+    }
+
     // --- Cursor Movement Tests ---
 
     #[test]
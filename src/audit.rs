@@ -0,0 +1,323 @@
+//! Local audit log of modifying actions.
+//!
+//! Every create, add-version, enable, disable, destroy, delete, IAM and tag
+//! change is appended as one JSON object per line to
+//! `$XDG_STATE_HOME/gsmtui/audit.jsonl` (falling back to
+//! `~/.local/state/gsmtui/audit.jsonl`). Records hold who did what to which
+//! resource and whether it worked; secret payloads are never written.
+
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Kind of modifying action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// A secret was created
+    CreateSecret,
+    /// A version was added
+    AddVersion,
+    /// A version was enabled
+    EnableVersion,
+    /// A version was disabled
+    DisableVersion,
+    /// A version was destroyed
+    DestroyVersion,
+    /// A secret was deleted
+    DeleteSecret,
+    /// A role on a secret was granted to a member
+    GrantAccess,
+    /// A role on a secret was revoked from a member
+    RevokeAccess,
+    /// The expiration of a secret was set, extended or removed
+    UpdateExpiration,
+    /// The version destroy TTL of a secret was set or removed
+    UpdateDestroyTtl,
+    /// A tag value was bound to a secret
+    BindTag,
+    /// A tag value was unbound from a secret
+    UnbindTag,
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::CreateSecret => "create secret",
+            Self::AddVersion => "add version",
+            Self::EnableVersion => "enable version",
+            Self::DisableVersion => "disable version",
+            Self::DestroyVersion => "destroy version",
+            Self::DeleteSecret => "delete secret",
+            Self::GrantAccess => "grant access",
+            Self::RevokeAccess => "revoke access",
+            Self::UpdateExpiration => "update expiration",
+            Self::UpdateDestroyTtl => "update destroy TTL",
+            Self::BindTag => "bind tag",
+            Self::UnbindTag => "unbind tag",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Returns the directory gsmtui keeps its state in:
+/// `$XDG_STATE_HOME/gsmtui`, falling back to `~/.local/state/gsmtui`.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(base.join("gsmtui"))
+}
+
+/// Whether the action succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The API call succeeded
+    Success,
+    /// The API call failed
+    Failure,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    /// Local OS user
+    pub user: String,
+    /// Google principal the call was made as, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    /// Project ID
+    pub project: String,
+    /// What was done
+    pub action: AuditAction,
+    /// Full resource name
+    pub resource: String,
+    /// What changed beyond the resource, e.g. the role and member of an IAM change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Result
+    pub outcome: Outcome,
+    /// Error text for failures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Appends records to (and reads them back from) the audit file.
+///
+/// A log without a path records nothing; this is what tests use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    principal: Option<String>,
+}
+
+impl AuditLog {
+    /// Creates a log writing to `path` on behalf of `principal`.
+    pub fn new(path: Option<PathBuf>, principal: Option<String>) -> Self {
+        Self { path, principal }
+    }
+
+    /// Returns the default audit file location.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(state_dir()?.join("audit.jsonl"))
+    }
+
+    /// Changes the principal recorded for future actions.
+    pub fn set_principal(&mut self, principal: Option<String>) {
+        self.principal = principal;
+    }
+
+    /// Returns the audit file path, if logging is enabled.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Appends a record for an action.
+    ///
+    /// `error` is the failure message, or `None` if the action succeeded.
+    pub fn record(
+        &self,
+        project: &str,
+        action: AuditAction,
+        resource: &str,
+        error: Option<String>,
+    ) -> Result<()> {
+        self.record_with_detail(project, action, resource, None, error)
+    }
+
+    /// Appends a record for an action, noting what changed on the resource.
+    pub fn record_with_detail(
+        &self,
+        project: &str,
+        action: AuditAction,
+        resource: &str,
+        detail: Option<&str>,
+        error: Option<String>,
+    ) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let record = AuditRecord {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            user: local_user(),
+            principal: self.principal.clone(),
+            project: project.to_string(),
+            action,
+            resource: resource.to_string(),
+            detail: detail.map(str::to_string),
+            outcome: if error.is_some() {
+                Outcome::Failure
+            } else {
+                Outcome::Success
+            },
+            error,
+        };
+        let mut line = serde_json::to_string(&record).context("Failed to serialize record")?;
+        line.push('\n');
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Reads all records, newest first. Unparseable lines are skipped.
+    pub fn read(&self) -> Result<Vec<AuditRecord>> {
+        let Some(path) = &self.path else {
+            return Ok(Vec::new());
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        let mut records: Vec<AuditRecord> = text
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        records.reverse();
+        Ok(records)
+    }
+}
+
+/// Returns the local OS user name.
+fn local_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> AuditLog {
+        let path = env::temp_dir()
+            .join(format!("gsmtui-audit-{}-{name}", std::process::id()))
+            .join("audit.jsonl");
+        let _ = fs::remove_file(&path);
+        AuditLog::new(Some(path), Some("sa@p.iam.gserviceaccount.com".to_string()))
+    }
+
+    #[test]
+    fn test_records_round_trip_newest_first() {
+        let log = temp_log("round-trip");
+        log.record("p", AuditAction::CreateSecret, "projects/p/secrets/a", None)
+            .unwrap();
+        log.record(
+            "p",
+            AuditAction::DeleteSecret,
+            "projects/p/secrets/a",
+            Some("permission denied".to_string()),
+        )
+        .unwrap();
+
+        let records = log.read().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].action, AuditAction::DeleteSecret);
+        assert_eq!(records[0].outcome, Outcome::Failure);
+        assert_eq!(records[0].error.as_deref(), Some("permission denied"));
+        assert_eq!(records[1].outcome, Outcome::Success);
+        assert_eq!(
+            records[1].principal.as_deref(),
+            Some("sa@p.iam.gserviceaccount.com")
+        );
+
+        let _ = fs::remove_dir_all(log.path().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn test_record_format_is_json_lines() {
+        let log = temp_log("format");
+        log.record(
+            "p",
+            AuditAction::AddVersion,
+            "projects/p/secrets/a/versions/2",
+            None,
+        )
+        .unwrap();
+
+        let text = fs::read_to_string(log.path().unwrap()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(text.trim_end()).unwrap();
+        assert_eq!(value["action"], "add_version");
+        assert_eq!(value["outcome"], "success");
+        assert!(value.get("error").is_none());
+
+        let _ = fs::remove_dir_all(log.path().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn test_record_detail() {
+        let log = temp_log("detail");
+        log.record_with_detail(
+            "p",
+            AuditAction::GrantAccess,
+            "projects/p/secrets/a",
+            Some("roles/secretmanager.secretAccessor user:ana@example.com"),
+            None,
+        )
+        .unwrap();
+
+        let records = log.read().unwrap();
+        assert_eq!(records[0].action, AuditAction::GrantAccess);
+        assert_eq!(
+            records[0].detail.as_deref(),
+            Some("roles/secretmanager.secretAccessor user:ana@example.com")
+        );
+
+        let _ = fs::remove_dir_all(log.path().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn test_disabled_log_records_nothing() {
+        let log = AuditLog::default();
+        log.record("p", AuditAction::CreateSecret, "r", None)
+            .unwrap();
+        assert!(log.read().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,787 @@
+//! Encrypted local backup and restore of a project's secrets.
+//!
+//! A backup holds the metadata of every secret plus the payload of every
+//! version that could be accessed (enabled versions). It is serialized as
+//! JSON and sealed with AES-256-GCM under a key derived from a passphrase
+//! with PBKDF2-HMAC-SHA256.
+//!
+//! File layout: `MAGIC | iterations (u32 BE) | salt | nonce | ciphertext+tag`.
+//! The header is authenticated as associated data.
+
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::api_error::ApiError;
+use crate::audit::{AuditAction, AuditLog};
+use crate::connection::location_label;
+use crate::secret_client::{SecretClient, SecretInfo, VersionState};
+
+/// Identifies a gsmtui backup file (and its format revision).
+const MAGIC: &[u8; 8] = b"GSMTUI\x00\x01";
+
+/// Salt length for key derivation.
+const SALT_LEN: usize = 16;
+
+/// PBKDF2 iterations used for new backups.
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Environment variable that supplies the passphrase non-interactively.
+pub const PASSPHRASE_ENV: &str = "GSMTUI_BACKUP_PASSPHRASE";
+
+/// Contents of a backup file (after decryption).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    /// Project the backup was taken from
+    pub project_id: String,
+    /// When the backup was taken (RFC 3339)
+    pub created_at: String,
+    /// Every secret in the backed-up locations
+    pub secrets: Vec<SecretBackup>,
+}
+
+/// A single secret and its accessible versions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretBackup {
+    /// Secret metadata
+    pub info: SecretInfo,
+    /// Accessible versions, oldest first
+    pub versions: Vec<VersionBackup>,
+    /// Versions that exist but could not be backed up (disabled/destroyed)
+    pub skipped_versions: Vec<String>,
+}
+
+/// Payload of a single secret version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionBackup {
+    /// Version number in the source project
+    pub version: String,
+    /// Creation time in the source project
+    pub create_time: String,
+    /// Payload, base64-encoded
+    pub payload: String,
+}
+
+impl VersionBackup {
+    /// Decodes the stored payload.
+    pub fn data(&self) -> Result<Vec<u8>> {
+        base64::engine::general_purpose::STANDARD
+            .decode(&self.payload)
+            .context("Corrupt payload in backup")
+    }
+}
+
+/// A single change a restore would make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreStep {
+    /// Create a secret that does not exist in the target project
+    CreateSecret {
+        /// Secret name
+        secret: String,
+        /// Region of the secret (`None` for global)
+        location: Option<String>,
+        /// Settings of the backed-up secret that are not recreated
+        not_restored: Vec<&'static str>,
+    },
+    /// Add a backed-up version payload to a secret
+    AddVersion {
+        /// Secret name
+        secret: String,
+        /// Region of the secret (`None` for global)
+        location: Option<String>,
+        /// Version number in the backup
+        source_version: String,
+    },
+}
+
+impl std::fmt::Display for RestoreStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateSecret {
+                secret,
+                location,
+                not_restored,
+            } => {
+                write!(
+                    f,
+                    "create secret {secret}{}",
+                    in_location(location.as_deref())
+                )?;
+                if !not_restored.is_empty() {
+                    write!(f, " (not restored: {})", not_restored.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::AddVersion {
+                secret,
+                location,
+                source_version,
+            } => write!(
+                f,
+                "add version to {secret}{} (from backup v{source_version})",
+                in_location(location.as_deref())
+            ),
+        }
+    }
+}
+
+/// Returns the settings of a backed-up secret that restoring does not
+/// recreate: only labels, annotations and replication are copied.
+///
+/// Keys, topics and expirations may not exist or be valid in the target
+/// project, so they are left for the user to set again.
+pub fn not_restored(info: &SecretInfo) -> Vec<&'static str> {
+    let mut settings = Vec::new();
+    if info.encryption.iter().any(|r| r.kms_key.is_some()) {
+        settings.push("CMEK keys");
+    }
+    if info.expire_time.is_some() {
+        settings.push("expiration");
+    }
+    if info.version_destroy_ttl.is_some() {
+        settings.push("version destroy TTL");
+    }
+    if info.rotation.is_some() {
+        settings.push("rotation");
+    }
+    if !info.topics.is_empty() {
+        settings.push("topics");
+    }
+    if !info.version_aliases.is_empty() {
+        settings.push("version aliases");
+    }
+    settings
+}
+
+/// Names the region of a regional secret in restore output.
+fn in_location(location: Option<&str>) -> String {
+    location.map(|l| format!(" in {l}")).unwrap_or_default()
+}
+
+impl Backup {
+    /// Moves every secret to `location`, for restoring into a single location.
+    ///
+    /// Fails if two secrets would end up with the same name.
+    pub fn retarget(&mut self, location: Option<&str>) -> Result<()> {
+        for (i, secret) in self.secrets.iter().enumerate() {
+            let name = &secret.info.short_name;
+            if self.secrets[..i].iter().any(|s| s.info.short_name == *name) {
+                bail!(
+                    "'{name}' is backed up from more than one location and cannot be restored into {} alone",
+                    location_label(location)
+                );
+            }
+        }
+        for secret in &mut self.secrets {
+            secret.info.location = location.map(str::to_string);
+        }
+        Ok(())
+    }
+
+    /// Returns the locations the backed-up secrets belong to.
+    pub fn locations(&self) -> Vec<Option<String>> {
+        let mut locations = Vec::new();
+        for secret in &self.secrets {
+            if !locations.contains(&secret.info.location) {
+                locations.push(secret.info.location.clone());
+            }
+        }
+        locations
+    }
+}
+
+/// Reads every secret and accessible version payload from the clients'
+/// locations.
+pub async fn collect(clients: &[SecretClient], project_id: &str) -> Result<Backup> {
+    let mut secrets = Vec::new();
+
+    for client in clients {
+        secrets.extend(collect_location(client).await?);
+    }
+
+    Ok(Backup {
+        project_id: project_id.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        secrets,
+    })
+}
+
+/// Reads the secrets of a single location.
+async fn collect_location(client: &SecretClient) -> Result<Vec<SecretBackup>> {
+    let mut secrets = Vec::new();
+
+    for info in client.list_secrets(None).await? {
+        let mut versions = client.list_versions(&info.short_name, None).await?;
+        versions.sort_by_key(|v| v.version.parse::<u64>().unwrap_or(u64::MAX));
+
+        let mut backed_up = Vec::new();
+        let mut skipped_versions = Vec::new();
+        for version in versions {
+            if version.state != VersionState::Enabled {
+                skipped_versions.push(version.version);
+                continue;
+            }
+            let data = client
+                .access_version_bytes(&info.short_name, &version.version)
+                .await
+                .with_context(|| {
+                    format!("Failed to read {} v{}", info.short_name, version.version)
+                })?;
+            backed_up.push(VersionBackup {
+                version: version.version,
+                create_time: version.create_time,
+                payload: base64::engine::general_purpose::STANDARD.encode(data),
+            });
+        }
+
+        secrets.push(SecretBackup {
+            info,
+            versions: backed_up,
+            skipped_versions,
+        });
+    }
+
+    Ok(secrets)
+}
+
+/// Computes the steps needed to bring the target project in line with a backup.
+///
+/// Each secret is restored into its own location, through the client for
+/// that location. Missing secrets are created with all their versions. For
+/// existing secrets, only payloads not already present among the target's
+/// enabled versions are added, so running a restore twice is a no-op.
+pub async fn plan_restore(clients: &[SecretClient], backup: &Backup) -> Result<Vec<RestoreStep>> {
+    let mut existing: Vec<(Option<String>, Vec<String>)> = Vec::new();
+    for location in backup.locations() {
+        let names = client_for(clients, location.as_deref())?
+            .list_secrets(None)
+            .await?
+            .into_iter()
+            .map(|s| s.short_name)
+            .collect();
+        existing.push((location, names));
+    }
+
+    let mut steps = Vec::new();
+    for secret in &backup.secrets {
+        let name = &secret.info.short_name;
+        let location = &secret.info.location;
+        let client = client_for(clients, location.as_deref())?;
+        let mut present: Vec<Vec<u8>> = Vec::new();
+
+        if existing
+            .iter()
+            .any(|(l, names)| l == location && names.contains(name))
+        {
+            for version in client.list_versions(name, None).await? {
+                if version.state == VersionState::Enabled {
+                    present.push(client.access_version_bytes(name, &version.version).await?);
+                }
+            }
+        } else {
+            steps.push(RestoreStep::CreateSecret {
+                secret: name.clone(),
+                location: location.clone(),
+                not_restored: not_restored(&secret.info),
+            });
+        }
+
+        steps.extend(missing_versions(secret, &present)?);
+    }
+
+    Ok(steps)
+}
+
+/// Returns add-version steps for backed-up payloads not in `present`.
+fn missing_versions(secret: &SecretBackup, present: &[Vec<u8>]) -> Result<Vec<RestoreStep>> {
+    let mut steps = Vec::new();
+    for version in &secret.versions {
+        if !present.contains(&version.data()?) {
+            steps.push(RestoreStep::AddVersion {
+                secret: secret.info.short_name.clone(),
+                location: secret.info.location.clone(),
+                source_version: version.version.clone(),
+            });
+        }
+    }
+    Ok(steps)
+}
+
+/// Applies restore steps to the clients' project, printing progress.
+///
+/// Each API call is recorded in the audit log; failing to record one is
+/// reported as a warning and does not stop the restore.
+pub async fn apply_restore(
+    clients: &[SecretClient],
+    backup: &Backup,
+    steps: &[RestoreStep],
+    audit: &AuditLog,
+) -> Result<()> {
+    for step in steps {
+        match step {
+            RestoreStep::CreateSecret {
+                secret: name,
+                location,
+                ..
+            } => {
+                let client = client_for(clients, location.as_deref())?;
+                let secret = find_secret(backup, name, location.as_deref())?;
+                let (result, error) =
+                    ApiError::classify_result(client.create_secret_from_info(&secret.info).await);
+                record(
+                    audit,
+                    client,
+                    AuditAction::CreateSecret,
+                    &client.secret_path(name),
+                    error,
+                );
+                result?;
+            }
+            RestoreStep::AddVersion {
+                secret,
+                location,
+                source_version,
+            } => {
+                let client = client_for(clients, location.as_deref())?;
+                let data = find_secret(backup, secret, location.as_deref())?
+                    .versions
+                    .iter()
+                    .find(|v| &v.version == source_version)
+                    .context("Version missing from backup")?
+                    .data()?;
+                let result = client.add_version_bytes(secret, &data).await;
+                let mut resource = client.secret_path(secret);
+                if let Ok(version) = &result {
+                    resource = format!("{resource}/versions/{}", version.version);
+                }
+                let (result, error) = ApiError::classify_result(result);
+                record(audit, client, AuditAction::AddVersion, &resource, error);
+                result?;
+            }
+        }
+        println!("  done: {step}");
+    }
+    Ok(())
+}
+
+/// Records a restore call in the audit log, warning if that fails.
+fn record(
+    audit: &AuditLog,
+    client: &SecretClient,
+    action: AuditAction,
+    resource: &str,
+    error: Option<String>,
+) {
+    if let Err(e) = audit.record(client.project_id(), action, resource, error) {
+        eprintln!("  warning: failed to write audit log: {e:#}");
+    }
+}
+
+/// Looks up a secret in the backup by name and location.
+fn find_secret<'a>(
+    backup: &'a Backup,
+    name: &str,
+    location: Option<&str>,
+) -> Result<&'a SecretBackup> {
+    backup
+        .secrets
+        .iter()
+        .find(|s| s.info.short_name == name && s.info.location.as_deref() == location)
+        .with_context(|| format!("Secret '{name}' missing from backup"))
+}
+
+/// Returns the client for a location of the restore.
+fn client_for<'a>(clients: &'a [SecretClient], location: Option<&str>) -> Result<&'a SecretClient> {
+    clients
+        .iter()
+        .find(|c| c.location() == location)
+        .with_context(|| format!("No client for location {}", location_label(location)))
+}
+
+/// Serializes and encrypts a backup, writing it to `path`.
+pub fn write_file(path: &Path, backup: &Backup, passphrase: &str) -> Result<()> {
+    let plaintext = serde_json::to_vec(backup).context("Failed to serialize backup")?;
+    let sealed = encrypt(&plaintext, passphrase, PBKDF2_ITERATIONS)?;
+
+    let mut file = create_private_file(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(&sealed)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Reads and decrypts a backup from `path`.
+pub fn read_file(path: &Path, passphrase: &str) -> Result<Backup> {
+    let sealed =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let plaintext = decrypt(&sealed, passphrase)?;
+    serde_json::from_slice(&plaintext).context("Backup contents are not valid")
+}
+
+/// Creates a file readable only by the current user (where supported).
+///
+/// An existing file is overwritten and its permissions are narrowed too.
+fn create_private_file(path: &Path) -> std::io::Result<std::fs::File> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(file)
+}
+
+/// Derives an AES-256 key from a passphrase.
+fn derive_key(passphrase: &str, salt: &[u8], iterations: NonZeroU32) -> Result<LessSafeKey> {
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let unbound = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| anyhow::anyhow!("Bad key"))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// Encrypts `plaintext` into the backup file format.
+fn encrypt(plaintext: &[u8], passphrase: &str, iterations: u32) -> Result<Vec<u8>> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|()| rng.fill(&mut nonce))
+        .map_err(|_| anyhow::anyhow!("Failed to generate random salt"))?;
+
+    let iterations = NonZeroU32::new(iterations).context("Iterations must be non-zero")?;
+    let key = derive_key(passphrase, &salt, iterations)?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + 4 + SALT_LEN + NONCE_LEN + plaintext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&iterations.get().to_be_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    let header_len = out.len();
+
+    let mut body = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(&out[..header_len]),
+        &mut body,
+    )
+    .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+    out.extend_from_slice(&body);
+
+    Ok(out)
+}
+
+/// Decrypts data in the backup file format.
+fn decrypt(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let header_len = MAGIC.len() + 4 + SALT_LEN + NONCE_LEN;
+    if sealed.len() < header_len || &sealed[..MAGIC.len()] != MAGIC {
+        bail!("Not a gsmtui backup file");
+    }
+
+    let (header, body) = sealed.split_at(header_len);
+    let mut offset = MAGIC.len();
+    let iterations = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let salt = &header[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce: [u8; NONCE_LEN] = header[offset..].try_into()?;
+
+    let iterations = NonZeroU32::new(iterations).context("Corrupt backup header")?;
+    let key = derive_key(passphrase, salt, iterations)?;
+
+    let mut body = body.to_vec();
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(header),
+            &mut body,
+        )
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted backup"))?;
+
+    Ok(plaintext.to_vec())
+}
+
+/// Obtains the backup passphrase.
+///
+/// Uses `GSMTUI_BACKUP_PASSPHRASE` if set, otherwise prompts on the terminal
+/// without echo. When `confirm` is set, the passphrase must be typed twice.
+pub fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        if passphrase.is_empty() {
+            bail!("{PASSPHRASE_ENV} is set but empty");
+        }
+        return Ok(passphrase);
+    }
+
+    let passphrase = prompt_hidden("Backup passphrase: ")?;
+    if passphrase.is_empty() {
+        bail!("Passphrase cannot be empty");
+    }
+    if confirm && prompt_hidden("Confirm passphrase: ")? != passphrase {
+        bail!("Passphrases do not match");
+    }
+    Ok(passphrase)
+}
+
+/// Reads a line from the terminal without echoing it.
+///
+/// Ctrl+U clears the input; other keys with Ctrl or Alt held are ignored.
+fn prompt_hidden(prompt: &str) -> Result<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let raw_mode = RawMode;
+    let mut input = String::new();
+    let result = loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => break Ok(input),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                break Err(anyhow::anyhow!("Cancelled"));
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                input.clear();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                input.push(c);
+            }
+            _ => {}
+        }
+    };
+    drop(raw_mode);
+    eprintln!();
+
+    result
+}
+
+/// Disables terminal raw mode when dropped, so that errors reading input do
+/// not leave the terminal in raw mode.
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret_client::{ReplicaEncryption, ReplicationPolicy};
+
+    fn mock_secret(name: &str, payloads: &[&str]) -> SecretBackup {
+        SecretBackup {
+            info: SecretInfo {
+                short_name: name.to_string(),
+                create_time: "2024-01-01".to_string(),
+                labels: vec![],
+                annotations: vec![],
+                replication: ReplicationPolicy::Automatic,
+                topics: vec![],
+                version_aliases: vec![],
+                rotation: None,
+                version_destroy_ttl: None,
+                location: None,
+                etag: String::new(),
+                expire_time: None,
+                encryption: vec![],
+                versions: None,
+            },
+            versions: payloads
+                .iter()
+                .enumerate()
+                .map(|(i, p)| VersionBackup {
+                    version: (i + 1).to_string(),
+                    create_time: "2024-01-01".to_string(),
+                    payload: base64::engine::general_purpose::STANDARD.encode(p),
+                })
+                .collect(),
+            skipped_versions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let sealed = encrypt(b"hello backup", "correct horse", 1_000).unwrap();
+        assert_eq!(decrypt(&sealed, "correct horse").unwrap(), b"hello backup");
+    }
+
+    #[test]
+    fn test_decrypt_wrong_passphrase_fails() {
+        let sealed = encrypt(b"hello backup", "correct horse", 1_000).unwrap();
+        assert!(decrypt(&sealed, "battery staple").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_header() {
+        let mut sealed = encrypt(b"hello backup", "pw", 1_000).unwrap();
+        sealed[MAGIC.len() + 5] ^= 0xff; // Flip a salt byte
+        assert!(decrypt(&sealed, "pw").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_foreign_file() {
+        assert!(decrypt(b"{\"not\": \"a backup\"}", "pw").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_overwritten_backup_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("gsmtui-backup-{}", std::process::id()));
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        drop(create_private_file(&path).unwrap());
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_missing_versions_skips_present_payloads() {
+        let secret = mock_secret("api-key", &["one", "two", "three"]);
+        let present = vec![b"two".to_vec()];
+
+        let steps = missing_versions(&secret, &present).unwrap();
+
+        assert_eq!(
+            steps,
+            vec![
+                RestoreStep::AddVersion {
+                    secret: "api-key".to_string(),
+                    location: None,
+                    source_version: "1".to_string(),
+                },
+                RestoreStep::AddVersion {
+                    secret: "api-key".to_string(),
+                    location: None,
+                    source_version: "3".to_string(),
+                },
+            ]
+        );
+    }
+
+    fn mock_backup(secrets: Vec<SecretBackup>) -> Backup {
+        Backup {
+            project_id: "test".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            secrets,
+        }
+    }
+
+    #[test]
+    fn test_locations_follow_the_backed_up_secrets() {
+        let mut regional = mock_secret("db-password", &["pw"]);
+        regional.info.location = Some("europe-west1".to_string());
+        let backup = mock_backup(vec![
+            mock_secret("api-key", &["one"]),
+            regional,
+            mock_secret("token", &["two"]),
+        ]);
+
+        assert_eq!(
+            backup.locations(),
+            vec![None, Some("europe-west1".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_retarget_moves_every_secret() {
+        let mut regional = mock_secret("db-password", &["pw"]);
+        regional.info.location = Some("europe-west1".to_string());
+        let mut backup = mock_backup(vec![mock_secret("api-key", &["one"]), regional]);
+
+        backup.retarget(Some("us-east1")).unwrap();
+
+        assert_eq!(backup.locations(), vec![Some("us-east1".to_string())]);
+    }
+
+    #[test]
+    fn test_retarget_refuses_names_from_several_locations() {
+        let mut regional = mock_secret("api-key", &["two"]);
+        regional.info.location = Some("europe-west1".to_string());
+        let mut backup = mock_backup(vec![mock_secret("api-key", &["one"]), regional]);
+
+        assert!(backup.retarget(None).is_err());
+    }
+
+    #[test]
+    fn test_restore_steps_name_regional_locations() {
+        let global = RestoreStep::CreateSecret {
+            secret: "api-key".to_string(),
+            location: None,
+            not_restored: vec![],
+        };
+        let regional = RestoreStep::CreateSecret {
+            secret: "api-key".to_string(),
+            location: Some("europe-west1".to_string()),
+            not_restored: vec![],
+        };
+
+        assert_eq!(global.to_string(), "create secret api-key");
+        assert_eq!(
+            regional.to_string(),
+            "create secret api-key in europe-west1"
+        );
+    }
+
+    #[test]
+    fn test_restore_steps_name_settings_not_restored() {
+        let mut secret = mock_secret("api-key", &["one"]);
+        assert!(not_restored(&secret.info).is_empty());
+
+        secret.info.encryption = vec![ReplicaEncryption {
+            location: "automatic".to_string(),
+            kms_key: Some("projects/p/locations/global/keyRings/r/cryptoKeys/k".to_string()),
+        }];
+        secret.info.expire_time = Some(1_700_000_000);
+        secret.info.topics = vec!["projects/p/topics/t".to_string()];
+        let step = RestoreStep::CreateSecret {
+            secret: "api-key".to_string(),
+            location: None,
+            not_restored: not_restored(&secret.info),
+        };
+
+        assert_eq!(
+            step.to_string(),
+            "create secret api-key (not restored: CMEK keys, expiration, topics)"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Command line interface definition.
+//!
+//! Options can also be supplied through environment variables; explicit
+//! flags always take precedence.
+
+use std::io;
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+/// Fallback environment variable for the project (set by `gcloud` tooling).
+const GCLOUD_PROJECT_ENV: &str = "CLOUDSDK_CORE_PROJECT";
+
+/// A Terminal User Interface for managing Google Cloud Secrets.
+///
+/// If no project is given, the project selector opens on startup.
+/// Make sure you have authenticated with: gcloud auth application-default login
+#[derive(Debug, Parser)]
+#[command(name = "gsmtui", version, about, long_about)]
+pub struct Cli {
+    /// Start with the specified GCP project
+    #[arg(
+        short,
+        long,
+        help = "Start with the specified GCP project [env: GSMTUI_PROJECT, CLOUDSDK_CORE_PROJECT]",
+        global = true,
+        value_name = "PROJECT_ID",
+        env = "GSMTUI_PROJECT",
+        hide_env = true
+    )]
+    pub project: Option<String>,
+
+    /// Project from the gcloud environment, used when no project is given
+    #[arg(skip)]
+    pub gcloud_project: Option<String>,
+
+    /// Disable all actions that modify secrets
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    /// Path to the configuration file
+    #[arg(long, global = true, value_name = "PATH", env = "GSMTUI_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Secret Manager location for regional secrets (default: global)
+    #[arg(long, global = true, value_name = "REGION", env = "GSMTUI_LOCATION")]
+    pub location: Option<String>,
+
+    /// Service account to impersonate for all API calls
+    #[arg(
+        long,
+        global = true,
+        value_name = "EMAIL",
+        env = "GSMTUI_IMPERSONATE_SERVICE_ACCOUNT"
+    )]
+    pub impersonate_service_account: Option<String>,
+
+    /// Append status messages and errors to this file
+    #[arg(long, global = true, value_name = "PATH", env = "GSMTUI_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Non-interactive subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Write an encrypted backup of every secret in the configured locations
+    Backup {
+        /// Backup file to write
+        file: PathBuf,
+    },
+    /// Recreate missing secrets and versions from a backup in the project
+    /// given with --project
+    Restore {
+        /// Backup file to read
+        file: PathBuf,
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+    /// Print the man page (roff) to stdout
+    Man,
+}
+
+impl Cli {
+    /// Parses the process arguments, applying environment fallbacks.
+    pub fn parse_args() -> Self {
+        let mut cli = Self::parse();
+        cli.gcloud_project = std::env::var(GCLOUD_PROJECT_ENV)
+            .ok()
+            .filter(|p| !p.is_empty());
+        cli
+    }
+}
+
+/// Writes a completion script for `shell` to stdout.
+pub fn print_completions(shell: Shell) {
+    let mut command = Cli::command();
+    clap_complete::generate(shell, &mut command, "gsmtui", &mut io::stdout());
+}
+
+/// Writes the man page to stdout.
+pub fn print_man_page() -> io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(&mut io::stdout())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_options_combine() {
+        let cli = Cli::try_parse_from([
+            "gsmtui",
+            "-p",
+            "my-project",
+            "--read-only",
+            "--location",
+            "europe-west1",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.project.as_deref(), Some("my-project"));
+        assert!(cli.read_only);
+        assert_eq!(cli.location.as_deref(), Some("europe-west1"));
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn test_global_project_after_subcommand() {
+        let cli =
+            Cli::try_parse_from(["gsmtui", "restore", "--dry-run", "-p", "p1", "f.bak"]).unwrap();
+
+        assert_eq!(cli.project.as_deref(), Some("p1"));
+        assert!(matches!(
+            cli.command,
+            Some(Command::Restore { dry_run: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_flag_is_rejected() {
+        assert!(Cli::try_parse_from(["gsmtui", "--bogus"]).is_err());
+    }
+}
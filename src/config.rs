@@ -0,0 +1,800 @@
+//! User configuration file.
+//!
+//! Read from `--config <PATH>`, or `$XDG_CONFIG_HOME/gsmtui/config.toml`
+//! (falling back to `~/.config/gsmtui/config.toml`). A missing default file
+//! is not an error. Problems are collected as messages and the offending
+//! setting falls back to its default, so a bad config never stops the app.
+//! A file that cannot be read or is not valid TOML, or project patterns that
+//! cannot be read, open every project read-only instead.
+//!
+//! ```toml
+//! default_project = "my-project"
+//! theme = "dark"            # dark, light, high-contrast, 16-color or a [themes] entry
+//! clipboard_timeout = 30      # seconds, 0 disables
+//! reveal_timeout = 60         # seconds, 0 disables
+//! request_timeout = 30        # seconds per API call attempt
+//! max_retries = 3             # retries of failed reads, 0 disables
+//! quota_project = "my-billing-project"
+//! read_only_projects = ["*-prod"]
+//! protected_projects = ["*-staging"]  # deleting requires typing the project ID
+//! columns = ["index", "name", "created"]
+//! locations = ["global", "europe-west1"]  # browsed together on start
+//!
+//! [impersonate_service_account]
+//! "*-prod" = "break-glass@ops.iam.gserviceaccount.com"
+//!
+//! [keybindings]
+//! refresh = ["r", "f5"]
+//! top = ["g g", "home"]
+//!
+//! [keybindings.detail]
+//! copy = ["y"]
+//!
+//! [themes.solarized]
+//! base = "light"
+//! primary = "#268bd2"
+//! ```
+//!
+//! Top-level keybindings apply in every view; a table named after a view
+//! (`auth`, `secrets`, `detail`, `iam`, `tags`, `projects`, `configs`,
+//! `locations`, `confirm`, `input`, `history`) only rebinds keys there.
+//! Multi-key sequences are written space-separated.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::event::Action;
+use crate::keymap::{KeyOverride, KeySequence, Scope};
+use crate::retry::RetryPolicy;
+use crate::ui::theme::Theme;
+
+/// A column in the secrets list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Row number
+    Index,
+    /// Secret name
+    Name,
+    /// Creation date
+    Created,
+    /// Labels (key=value)
+    Labels,
+    /// Replication policy
+    Replication,
+    /// Location badge (`global` or the region)
+    Location,
+    /// Highest version number
+    LatestVersion,
+    /// Number of enabled versions
+    EnabledVersions,
+    /// Next rotation time
+    Rotation,
+    /// Expiration countdown
+    Expiration,
+    /// Number of Pub/Sub topics notified
+    Topics,
+}
+
+impl Column {
+    /// Columns shown when none are configured.
+    pub const DEFAULT: [Self; 3] = [Self::Index, Self::Name, Self::Created];
+
+    /// Parses a column name from the config file.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "index" => Some(Self::Index),
+            "name" => Some(Self::Name),
+            "created" => Some(Self::Created),
+            "labels" => Some(Self::Labels),
+            "replication" => Some(Self::Replication),
+            "location" => Some(Self::Location),
+            "latest_version" => Some(Self::LatestVersion),
+            "enabled_versions" => Some(Self::EnabledVersions),
+            "rotation" => Some(Self::Rotation),
+            "expiration" => Some(Self::Expiration),
+            "topics" => Some(Self::Topics),
+            _ => None,
+        }
+    }
+
+    /// Returns the column's header in the secrets list.
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Index => "#",
+            Self::Name => "Name",
+            Self::Created => "Created",
+            Self::Labels => "Labels",
+            Self::Replication => "Replication",
+            Self::Location => "Location",
+            Self::LatestVersion => "Latest",
+            Self::EnabledVersions => "Enabled",
+            Self::Rotation => "Rotation",
+            Self::Expiration => "Expires",
+            Self::Topics => "Topics",
+        }
+    }
+
+    /// Returns true if the column needs the versions of every secret, which
+    /// costs one extra call per secret when listing.
+    pub fn needs_versions(self) -> bool {
+        matches!(self, Self::LatestVersion | Self::EnabledVersions)
+    }
+}
+
+/// The config file as written on disk, read one setting at a time.
+#[derive(Debug, Default)]
+struct RawConfig {
+    default_project: Option<String>,
+    theme: Option<String>,
+    themes: HashMap<String, HashMap<String, String>>,
+    clipboard_timeout: Option<u64>,
+    reveal_timeout: Option<u64>,
+    request_timeout: Option<u64>,
+    max_retries: Option<u32>,
+    quota_project: Option<String>,
+    read_only_projects: Vec<String>,
+    protected_projects: Vec<String>,
+    columns: Option<Vec<String>>,
+    locations: Vec<String>,
+    impersonate_service_account: HashMap<String, String>,
+    keybindings: HashMap<String, RawKeys>,
+}
+
+impl RawConfig {
+    /// Reads each known setting from a parsed file, reporting unknown keys
+    /// and values of the wrong type.
+    ///
+    /// A project pattern list that cannot be read falls back to matching
+    /// every project, so a typo never lifts a read-only or protection rule.
+    fn from_table(table: toml::Table, errors: &mut Vec<String>) -> Self {
+        let mut raw = Self::default();
+        for (key, value) in table {
+            let result = match key.as_str() {
+                "default_project" => value.try_into().map(|v| raw.default_project = Some(v)),
+                "theme" => value.try_into().map(|v| raw.theme = Some(v)),
+                "themes" => Self::entries(&key, value, errors).map(|v| raw.themes = v),
+                "clipboard_timeout" => value.try_into().map(|v| raw.clipboard_timeout = Some(v)),
+                "reveal_timeout" => value.try_into().map(|v| raw.reveal_timeout = Some(v)),
+                "request_timeout" => value.try_into().map(|v| raw.request_timeout = Some(v)),
+                "max_retries" => value.try_into().map(|v| raw.max_retries = Some(v)),
+                "quota_project" => value.try_into().map(|v| raw.quota_project = Some(v)),
+                "read_only_projects" => value.try_into().map(|v| raw.read_only_projects = v),
+                "protected_projects" => value.try_into().map(|v| raw.protected_projects = v),
+                "columns" => value.try_into().map(|v| raw.columns = Some(v)),
+                "locations" => value.try_into().map(|v| raw.locations = v),
+                "impersonate_service_account" => {
+                    Self::entries(&key, value, errors).map(|v| raw.impersonate_service_account = v)
+                }
+                "keybindings" => Self::entries(&key, value, errors).map(|v| raw.keybindings = v),
+                _ => {
+                    errors.push(format!("unknown setting '{key}'"));
+                    continue;
+                }
+            };
+            let Err(e) = result else {
+                continue;
+            };
+            if key == "read_only_projects" || key == "protected_projects" {
+                raw.read_only_projects.push("*".to_string());
+                errors.push(format!(
+                    "{key}: {}; every project opens read-only until this is fixed",
+                    e.message()
+                ));
+            } else {
+                errors.push(format!("{key}: {}", e.message()));
+            }
+        }
+        raw
+    }
+
+    /// Reads a table entry by entry, dropping and reporting the bad ones.
+    fn entries<T: serde::de::DeserializeOwned>(
+        setting: &str,
+        value: toml::Value,
+        errors: &mut Vec<String>,
+    ) -> Result<HashMap<String, T>, toml::de::Error> {
+        let table: toml::Table = value.try_into()?;
+        let mut entries = HashMap::new();
+        for (name, value) in table {
+            match value.try_into() {
+                Ok(entry) => {
+                    entries.insert(name, entry);
+                }
+                Err(e) => errors.push(format!("{setting}.{name}: {}", e.message())),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// A `[keybindings]` entry: keys for an action, or a per-view table.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawKeys {
+    Keys(Vec<String>),
+    View(HashMap<String, Vec<String>>),
+}
+
+/// Validated configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Project to open when none is given on the command line
+    pub default_project: Option<String>,
+    /// Color theme
+    pub theme: Theme,
+    /// Clear the clipboard this long after copying a secret
+    pub clipboard_timeout: Option<Duration>,
+    /// Hide a revealed secret value after this long
+    pub reveal_timeout: Option<Duration>,
+    /// API call timeout and retry settings
+    pub retry: RetryPolicy,
+    /// Project billed for API quota
+    pub quota_project: Option<String>,
+    /// Project ID patterns (`*` wildcard) that open read-only
+    pub read_only_projects: Vec<String>,
+    /// Project ID patterns where destructive actions require typing the project ID
+    pub protected_projects: Vec<String>,
+    /// Columns shown in the secrets list
+    pub columns: Vec<Column>,
+    /// Locations browsed on start (`None` is global); empty means global only
+    pub locations: Vec<Option<String>>,
+    /// Service account to impersonate per project ID pattern
+    pub impersonate_service_accounts: Vec<(String, String)>,
+    /// Key overrides per action
+    pub keybindings: Vec<KeyOverride>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_project: None,
+            theme: Theme::default(),
+            clipboard_timeout: None,
+            reveal_timeout: None,
+            retry: RetryPolicy::default(),
+            quota_project: None,
+            read_only_projects: Vec::new(),
+            protected_projects: Vec::new(),
+            columns: Column::DEFAULT.to_vec(),
+            locations: Vec::new(),
+            impersonate_service_accounts: Vec::new(),
+            keybindings: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Returns the default config file location.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("gsmtui").join("config.toml"))
+    }
+
+    /// Loads the config, returning it along with any problems found.
+    ///
+    /// An explicitly given `path` must exist; the default path may be absent.
+    pub fn load(path: Option<&Path>) -> (Self, Vec<String>) {
+        let (path, explicit) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(p) => (p, false),
+                None => return (Self::default(), Vec::new()),
+            },
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !explicit => {
+                (Self::default(), Vec::new())
+            }
+            Err(e) => Self::unreadable(&format!("cannot read {}: {e}", path.display())),
+        }
+    }
+
+    /// Parses and validates config file contents.
+    ///
+    /// Each setting is read on its own, so an unknown key or a value of the
+    /// wrong type only drops that setting. A file that is not valid TOML
+    /// cannot be read at all and opens every project read-only.
+    pub fn parse(text: &str) -> (Self, Vec<String>) {
+        let table = match text.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => return Self::unreadable(e.message()),
+        };
+        let mut errors = Vec::new();
+        let raw = RawConfig::from_table(table, &mut errors);
+        let (config, more) = Self::validate(raw);
+        errors.extend(more);
+        (config, errors)
+    }
+
+    /// The config used when the file cannot be read: defaults, with every
+    /// project read-only so that protections it may set are not lost.
+    fn unreadable(reason: &str) -> (Self, Vec<String>) {
+        let config = Self {
+            read_only_projects: vec!["*".to_string()],
+            ..Self::default()
+        };
+        let error = format!("{reason}; every project opens read-only until this is fixed");
+        (config, vec![error])
+    }
+
+    /// Converts the raw file into a `Config`, dropping invalid settings.
+    fn validate(raw: RawConfig) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut config = Self {
+            default_project: raw.default_project.filter(|p| !p.is_empty()),
+            quota_project: raw.quota_project.filter(|p| !p.is_empty()),
+            ..Self::default()
+        };
+
+        let mut themes: Vec<_> = raw.themes.into_iter().collect();
+        themes.sort_by(|a, b| a.0.cmp(&b.0));
+        let custom: Vec<(String, Theme)> = themes
+            .into_iter()
+            .map(|(name, colors)| {
+                let theme = Self::build_theme(&name, colors, &mut errors);
+                (name, theme)
+            })
+            .collect();
+
+        if let Some(name) = raw.theme {
+            let theme = custom
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, theme)| *theme)
+                .or_else(|| Theme::builtin(&name));
+            if let Some(theme) = theme {
+                config.theme = theme;
+            } else {
+                let available: Vec<&str> = Theme::BUILTIN
+                    .into_iter()
+                    .chain(custom.iter().map(|(n, _)| n.as_str()))
+                    .collect();
+                errors.push(format!(
+                    "unknown theme '{name}' (available: {})",
+                    available.join(", ")
+                ));
+            }
+        }
+
+        config.clipboard_timeout = raw
+            .clipboard_timeout
+            .filter(|s| *s > 0)
+            .map(Duration::from_secs);
+        config.reveal_timeout = raw
+            .reveal_timeout
+            .filter(|s| *s > 0)
+            .map(Duration::from_secs);
+
+        match raw.request_timeout {
+            Some(0) => errors.push("request_timeout must be at least 1 second".to_string()),
+            Some(secs) => config.retry.timeout = Duration::from_secs(secs),
+            None => {}
+        }
+        if let Some(retries) = raw.max_retries {
+            config.retry.max_attempts = retries.saturating_add(1);
+        }
+
+        config.read_only_projects =
+            Self::patterns("read_only_projects", raw.read_only_projects, &mut errors);
+        config.protected_projects =
+            Self::patterns("protected_projects", raw.protected_projects, &mut errors);
+
+        let mut impersonation: Vec<(String, String)> =
+            raw.impersonate_service_account.into_iter().collect();
+        // Exact project IDs win over patterns; otherwise the order is alphabetical
+        impersonation.sort_by(|a, b| (a.0.contains('*'), &a.0).cmp(&(b.0.contains('*'), &b.0)));
+        for (pattern, account) in impersonation {
+            if pattern.is_empty() {
+                errors.push("impersonate_service_account contains an empty pattern".to_string());
+            } else if !account.contains('@') {
+                errors.push(format!(
+                    "impersonate_service_account for '{pattern}' is not a service account email"
+                ));
+            } else {
+                config.impersonate_service_accounts.push((pattern, account));
+            }
+        }
+
+        if let Some(names) = raw.columns {
+            let mut columns = Vec::new();
+            for name in names {
+                match Column::from_name(&name) {
+                    Some(column) if !columns.contains(&column) => columns.push(column),
+                    Some(_) => errors.push(format!("column '{name}' listed twice")),
+                    None => errors.push(format!("unknown column '{name}'")),
+                }
+            }
+            if columns.contains(&Column::Name) {
+                config.columns = columns;
+            } else {
+                errors.push("columns must include 'name'".to_string());
+            }
+        }
+
+        for location in raw.locations {
+            let valid = !location.is_empty()
+                && location
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            let location = (location != "global").then_some(location);
+            if !valid {
+                errors.push(format!(
+                    "invalid location '{}'",
+                    location.as_deref().unwrap_or_default()
+                ));
+            } else if config.locations.contains(&location) {
+                errors.push(format!(
+                    "location '{}' listed twice",
+                    location.as_deref().unwrap_or("global")
+                ));
+            } else {
+                config.locations.push(location);
+            }
+        }
+
+        let mut entries: Vec<_> = raw.keybindings.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, entry) in entries {
+            match entry {
+                RawKeys::Keys(keys) => {
+                    Self::add_override(&mut config, &mut errors, None, &name, keys);
+                }
+                RawKeys::View(actions) => {
+                    let Some(scope) = Scope::from_name(&name) else {
+                        errors.push(format!("unknown view '{name}' in [keybindings]"));
+                        continue;
+                    };
+                    let mut actions: Vec<_> = actions.into_iter().collect();
+                    actions.sort();
+                    for (action, keys) in actions {
+                        Self::add_override(&mut config, &mut errors, Some(scope), &action, keys);
+                    }
+                }
+            }
+        }
+
+        (config, errors)
+    }
+
+    /// Builds a user theme from a `[themes.<name>]` table.
+    ///
+    /// Colors not listed are taken from `base` (default: dark).
+    fn build_theme(
+        name: &str,
+        mut colors: HashMap<String, String>,
+        errors: &mut Vec<String>,
+    ) -> Theme {
+        let mut theme = match colors.remove("base") {
+            Some(base) => Theme::builtin(&base).unwrap_or_else(|| {
+                errors.push(format!("theme '{name}': unknown base '{base}'"));
+                Theme::default()
+            }),
+            None => Theme::default(),
+        };
+
+        let mut colors: Vec<_> = colors.into_iter().collect();
+        colors.sort();
+        for (key, value) in colors {
+            let result = value
+                .parse::<Color>()
+                .map_err(|_| format!("invalid color '{value}' for {key}"))
+                .and_then(|color| theme.set_color(&key, color));
+            if let Err(e) = result {
+                errors.push(format!("theme '{name}': {e}"));
+            }
+        }
+        theme
+    }
+
+    /// Validates one `action = [keys]` entry and records it.
+    fn add_override(
+        config: &mut Self,
+        errors: &mut Vec<String>,
+        scope: Option<Scope>,
+        name: &str,
+        keys: Vec<String>,
+    ) {
+        let Some(action) = Action::from_name(name) else {
+            errors.push(format!("unknown action '{name}' in [keybindings]"));
+            return;
+        };
+        let mut sequences = Vec::new();
+        for key in keys {
+            match KeySequence::parse(&key) {
+                Ok(sequence) => sequences.push(sequence),
+                Err(e) => errors.push(format!("{e} for action '{name}'")),
+            }
+        }
+        if !sequences.is_empty() {
+            config.keybindings.push(KeyOverride {
+                scope,
+                action,
+                keys: sequences,
+            });
+        }
+    }
+
+    /// Drops empty project patterns, reporting them.
+    fn patterns(setting: &str, patterns: Vec<String>, errors: &mut Vec<String>) -> Vec<String> {
+        let (valid, empty): (Vec<_>, Vec<_>) = patterns.into_iter().partition(|p| !p.is_empty());
+        if !empty.is_empty() {
+            errors.push(format!("{setting} contains an empty pattern"));
+        }
+        valid
+    }
+
+    /// Returns true if `project_id` matches one of the read-only patterns.
+    pub fn is_read_only_project(&self, project_id: &str) -> bool {
+        self.read_only_projects
+            .iter()
+            .any(|pattern| wildcard_match(pattern, project_id))
+    }
+
+    /// Returns the service account to impersonate for `project_id`, if any.
+    pub fn impersonation_for(&self, project_id: &str) -> Option<&str> {
+        self.impersonate_service_accounts
+            .iter()
+            .find(|(pattern, _)| wildcard_match(pattern, project_id))
+            .map(|(_, account)| account.as_str())
+    }
+
+    /// Returns true if `project_id` matches one of the protected patterns.
+    pub fn is_protected_project(&self, project_id: &str) -> bool {
+        self.protected_projects
+            .iter()
+            .any(|pattern| wildcard_match(pattern, project_id))
+    }
+}
+
+/// Matches `text` against a pattern where `*` matches any run of characters.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard at all: must match exactly
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let (config, errors) = Config::parse("");
+        assert!(errors.is_empty());
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_full_config() {
+        let (config, errors) = Config::parse(
+            r#"
+            default_project = "my-project"
+            theme = "dark"
+            clipboard_timeout = 30
+            reveal_timeout = 0
+            request_timeout = 10
+            max_retries = 0
+            quota_project = "billing"
+            read_only_projects = ["*-prod"]
+            columns = ["name", "labels", "location", "latest_version", "expiration"]
+            locations = ["global", "europe-west1"]
+
+            [keybindings]
+            refresh = ["f5"]
+            "#,
+        );
+
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(config.default_project.as_deref(), Some("my-project"));
+        assert_eq!(config.clipboard_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.reveal_timeout, None);
+        assert_eq!(config.retry.timeout, Duration::from_secs(10));
+        assert_eq!(config.retry.max_attempts, 1);
+        assert_eq!(config.quota_project.as_deref(), Some("billing"));
+        assert_eq!(
+            config.columns,
+            vec![
+                Column::Name,
+                Column::Labels,
+                Column::Location,
+                Column::LatestVersion,
+                Column::Expiration,
+            ]
+        );
+        assert_eq!(
+            config.locations,
+            vec![None, Some("europe-west1".to_string())]
+        );
+        assert_eq!(config.keybindings.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_reported_not_fatal() {
+        let (config, errors) = Config::parse(
+            r#"
+            theme = "neon"
+            columns = ["index", "bogus"]
+            locations = ["Europe West"]
+
+            [keybindings]
+            launch = ["l"]
+            quit = ["hyper+q"]
+            "#,
+        );
+
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert_eq!(config.theme, Theme::dark());
+        assert!(config.locations.is_empty());
+        assert_eq!(config.columns, Column::DEFAULT.to_vec());
+        assert!(config.keybindings.is_empty());
+    }
+
+    #[test]
+    fn test_custom_theme() {
+        let (config, errors) = Config::parse(
+            r##"
+            theme = "mine"
+
+            [themes.mine]
+            base = "light"
+            primary = "#112233"
+            key = "red"
+
+            [themes.broken]
+            primary = "not-a-color"
+            sparkle = "red"
+            "##,
+        );
+
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert_eq!(config.theme.primary, Color::Rgb(0x11, 0x22, 0x33));
+        assert_eq!(config.theme.key, Color::Red);
+        assert_eq!(config.theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn test_builtin_themes_by_name() {
+        for name in Theme::BUILTIN {
+            let (config, errors) = Config::parse(&format!("theme = \"{name}\""));
+            assert!(errors.is_empty(), "{errors:?}");
+            assert_eq!(Some(config.theme), Theme::builtin(name));
+        }
+    }
+
+    #[test]
+    fn test_per_view_keybindings() {
+        let (config, errors) = Config::parse(
+            r#"
+            [keybindings]
+            top = ["g g"]
+
+            [keybindings.detail]
+            copy = ["y"]
+
+            [keybindings.nowhere]
+            copy = ["y"]
+            "#,
+        );
+
+        assert_eq!(errors, vec!["unknown view 'nowhere' in [keybindings]"]);
+        assert_eq!(config.keybindings.len(), 2);
+        assert_eq!(config.keybindings[0].scope, Some(Scope::SecretDetail));
+        assert_eq!(config.keybindings[0].action, Action::Copy);
+        assert_eq!(config.keybindings[1].scope, None);
+    }
+
+    #[test]
+    fn test_syntax_error_opens_every_project_read_only() {
+        let (config, errors) = Config::parse("theme = ");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config.theme, Theme::default());
+        assert!(config.is_read_only_project("any-project"));
+    }
+
+    #[test]
+    fn test_unknown_setting_is_reported() {
+        let (_, errors) = Config::parse("colour = \"red\"");
+        assert_eq!(errors, vec!["unknown setting 'colour'"]);
+    }
+
+    #[test]
+    fn test_bad_settings_keep_the_others() {
+        let (config, errors) = Config::parse(
+            r#"
+            colour = "red"
+            reveal_timeout = "soon"
+            read_only_projects = ["*-prod"]
+            protected_projects = ["*-staging"]
+
+            [keybindings]
+            copy = "y"
+            refresh = ["f5"]
+            "#,
+        );
+
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(config.is_read_only_project("x-prod"));
+        assert!(!config.is_read_only_project("x-dev"));
+        assert!(config.is_protected_project("x-staging"));
+        assert_eq!(config.keybindings.len(), 1);
+        assert_eq!(config.keybindings[0].action, Action::Refresh);
+    }
+
+    #[test]
+    fn test_unreadable_project_patterns_fail_closed() {
+        let (config, errors) = Config::parse("protected_projects = \"*-staging\"");
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(config.is_read_only_project("any-project"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*-prod", "payments-prod"));
+        assert!(!wildcard_match("*-prod", "payments-prod-eu"));
+        assert!(wildcard_match("prod-*", "prod-eu"));
+        assert!(wildcard_match("*prod*", "my-prod-eu"));
+        assert!(wildcard_match("exact", "exact"));
+        assert!(!wildcard_match("exact", "exactly"));
+        assert!(wildcard_match("*", "anything"));
+    }
+
+    #[test]
+    fn test_read_only_project_patterns() {
+        let config = Config {
+            read_only_projects: vec!["*-prod".to_string(), "billing".to_string()],
+            ..Config::default()
+        };
+        assert!(config.is_read_only_project("web-prod"));
+        assert!(config.is_read_only_project("billing"));
+        assert!(!config.is_read_only_project("web-dev"));
+    }
+
+    #[test]
+    fn test_impersonation_per_project() {
+        let (config, errors) = Config::parse(
+            r#"
+            [impersonate_service_account]
+            "*-prod" = "break-glass@ops.iam.gserviceaccount.com"
+            "payments-prod" = "payments@ops.iam.gserviceaccount.com"
+            "dev" = "not-an-email"
+            "#,
+        );
+
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(
+            config.impersonation_for("payments-prod"),
+            Some("payments@ops.iam.gserviceaccount.com")
+        );
+        assert_eq!(
+            config.impersonation_for("web-prod"),
+            Some("break-glass@ops.iam.gserviceaccount.com")
+        );
+        assert_eq!(config.impersonation_for("dev"), None);
+    }
+}
@@ -0,0 +1,561 @@
+//! Connection settings shared by the Google Cloud clients.
+//!
+//! Both the Secret Manager and Resource Manager clients are built from the
+//! same options so that credentials (e.g. impersonation) apply consistently.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use google_cloud_auth::credentials::{self, impersonated, Credentials};
+use google_cloud_gax::error::CredentialsError;
+
+use crate::constants::METADATA_PROBE_TIMEOUT;
+use crate::retry::RetryPolicy;
+
+/// Where Application Default Credentials are loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// Credentials file named by `GOOGLE_APPLICATION_CREDENTIALS`
+    EnvFile(PathBuf, CredentialKind),
+    /// The file written by `gcloud auth application-default login`
+    AdcFile(PathBuf, CredentialKind),
+    /// The metadata server of the VM or container we run on
+    MetadataServer,
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EnvFile(_, kind) => write!(f, "GOOGLE_APPLICATION_CREDENTIALS, {kind}"),
+            Self::AdcFile(_, kind) => write!(f, "ADC file, {kind}"),
+            Self::MetadataServer => write!(f, "metadata server"),
+        }
+    }
+}
+
+/// The kind of credentials in a file, from its `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialKind {
+    /// `authorized_user`: a signed-in user
+    User,
+    /// `service_account`: a service account key
+    ServiceAccountKey,
+    /// `impersonated_service_account`: a service account impersonated by
+    /// the file's source credentials
+    ImpersonatedServiceAccount,
+    /// `external_account`: workload identity federation
+    ExternalAccount,
+    /// A type the auth library does not load, or an unreadable file
+    Unknown,
+}
+
+impl fmt::Display for CredentialKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::ServiceAccountKey => write!(f, "service account key"),
+            Self::ImpersonatedServiceAccount => write!(f, "impersonated service account"),
+            Self::ExternalAccount => write!(f, "workload identity federation"),
+            Self::Unknown => write!(f, "unrecognized"),
+        }
+    }
+}
+
+impl CredentialKind {
+    /// Maps the `type` of a credentials file, as the auth library does.
+    fn from_type(kind: &str) -> Self {
+        match kind {
+            "authorized_user" => Self::User,
+            "service_account" => Self::ServiceAccountKey,
+            "impersonated_service_account" => Self::ImpersonatedServiceAccount,
+            "external_account" => Self::ExternalAccount,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Reads the kind of the credentials file at `path`.
+    fn of_file(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+            .and_then(|json| json["type"].as_str().map(Self::from_type))
+            .unwrap_or(Self::Unknown)
+    }
+}
+
+impl CredentialSource {
+    /// Finds the source the auth library will use.
+    ///
+    /// Follows the library's lookup: `GOOGLE_APPLICATION_CREDENTIALS` if it
+    /// is set at all, then the well-known ADC file if it exists, then the
+    /// metadata server. Like the library, `CLOUDSDK_CONFIG` is not honored.
+    pub fn detect() -> Self {
+        if let Ok(path) = env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            let path = PathBuf::from(path);
+            let kind = CredentialKind::of_file(&path);
+            return Self::EnvFile(path, kind);
+        }
+        match default_adc_path() {
+            Some(path) if path.exists() => {
+                let kind = CredentialKind::of_file(&path);
+                Self::AdcFile(path, kind)
+            }
+            _ => Self::MetadataServer,
+        }
+    }
+
+    /// Describes each place credentials are looked for, in lookup order.
+    ///
+    /// `found` is `None` where presence cannot be known without a probe.
+    pub fn checks() -> Vec<SourceCheck> {
+        let env_file = match env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            Ok(path) => file_check("GOOGLE_APPLICATION_CREDENTIALS", Path::new(&path)),
+            Err(_) => SourceCheck {
+                name: "GOOGLE_APPLICATION_CREDENTIALS",
+                detail: "not set".to_string(),
+                found: Some(false),
+            },
+        };
+        let adc_file = match default_adc_path() {
+            Some(path) => file_check("ADC file", &path),
+            None => SourceCheck {
+                name: "ADC file",
+                detail: "no home directory".to_string(),
+                found: Some(false),
+            },
+        };
+        let metadata = SourceCheck {
+            name: "Metadata server",
+            detail: "GCE, GKE, Cloud Run and Cloud Shell only".to_string(),
+            found: None,
+        };
+        vec![env_file, adc_file, metadata]
+    }
+
+    /// Returns the credentials file, if any.
+    pub fn path(&self) -> Option<&PathBuf> {
+        match self {
+            Self::EnvFile(path, _) | Self::AdcFile(path, _) => Some(path),
+            Self::MetadataServer => None,
+        }
+    }
+}
+
+/// The outcome of looking in one credential source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceCheck {
+    /// Source name
+    pub name: &'static str,
+    /// Path or explanation
+    pub detail: String,
+    /// Whether credentials were found there
+    pub found: Option<bool>,
+}
+
+/// Checks whether a credentials file exists.
+fn file_check(name: &'static str, path: &Path) -> SourceCheck {
+    let found = path.exists();
+    SourceCheck {
+        name,
+        detail: if found {
+            format!("{} ({})", path.display(), CredentialKind::of_file(path))
+        } else {
+            format!("{} (not found)", path.display())
+        },
+        found: Some(found),
+    }
+}
+
+/// Principals already identified, by the credentials they were identified with.
+static IDENTITIES: Mutex<BTreeMap<CredentialKey, Option<String>>> = Mutex::new(BTreeMap::new());
+
+/// One set of credentials: the file they are loaded from, when it was last
+/// written, and the service account impersonated with them.
+///
+/// Logging in again rewrites the file, so its principal is identified anew.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct CredentialKey {
+    /// Credentials file (`None` for the metadata server)
+    file: Option<PathBuf>,
+    /// When the file was last written
+    modified: Option<SystemTime>,
+    /// Service account impersonated with the credentials
+    impersonate: Option<String>,
+}
+
+impl CredentialKey {
+    /// Returns the key of the credentials `options` currently resolve to.
+    fn current(options: &ConnectionOptions) -> Self {
+        let file = CredentialSource::detect().path().cloned();
+        let modified = file
+            .as_ref()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .and_then(|metadata| metadata.modified().ok());
+        Self {
+            file,
+            modified,
+            impersonate: options.impersonate_service_account.clone(),
+        }
+    }
+}
+
+/// Options that control how clients connect and authenticate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// Secret Manager location (`None` for global secrets)
+    pub location: Option<String>,
+    /// Service account to impersonate instead of using ADC directly
+    pub impersonate_service_account: Option<String>,
+    /// Per-call timeout and retry settings
+    pub retry: RetryPolicy,
+    /// Project billed for API quota, from the config file
+    pub quota_project: Option<String>,
+}
+
+impl ConnectionOptions {
+    /// Builds credentials for these options.
+    ///
+    /// Returns `None` when plain Application Default Credentials should be
+    /// used, leaving credential discovery to the client library.
+    pub fn credentials(&self) -> Result<Option<Credentials>> {
+        let Some(target) = &self.impersonate_service_account else {
+            if self.quota_project.is_none() {
+                return Ok(None);
+            }
+            return self
+                .adc_builder()
+                .build()
+                .context("Failed to load Application Default Credentials")
+                .map(Some);
+        };
+
+        let source = self
+            .adc_builder()
+            .build()
+            .context("Failed to load Application Default Credentials")?;
+
+        let impersonated = self
+            .impersonated_builder(source, target)
+            .build()
+            .with_context(|| format!("Failed to impersonate {target}"))?;
+
+        Ok(Some(impersonated))
+    }
+
+    /// Returns the project billed for API quota.
+    ///
+    /// `GOOGLE_CLOUD_QUOTA_PROJECT` wins over the config file, which wins over
+    /// the `quota_project_id` recorded in the ADC file.
+    pub fn effective_quota_project(&self) -> Option<String> {
+        env::var("GOOGLE_CLOUD_QUOTA_PROJECT")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .or_else(|| self.quota_project.clone())
+            .or_else(|| {
+                let text = std::fs::read_to_string(CredentialSource::detect().path()?).ok()?;
+                let adc: serde_json::Value = serde_json::from_str(&text).ok()?;
+                adc["quota_project_id"].as_str().map(str::to_string)
+            })
+    }
+
+    /// Returns an ADC builder carrying the configured quota project.
+    fn adc_builder(&self) -> credentials::Builder {
+        let builder = credentials::Builder::default();
+        match &self.quota_project {
+            Some(project) => builder.with_quota_project_id(project),
+            None => builder,
+        }
+    }
+
+    /// Returns an impersonation builder carrying the effective quota project.
+    ///
+    /// Impersonated credentials do not inherit the quota project of their
+    /// source, so it is set explicitly.
+    fn impersonated_builder(&self, source: Credentials, target: &str) -> impersonated::Builder {
+        let builder =
+            impersonated::Builder::from_source_credentials(source).with_target_principal(target);
+        match self.effective_quota_project() {
+            Some(project) => builder.with_quota_project_id(project),
+            None => builder,
+        }
+    }
+
+    /// Returns the principal API calls are made as, when it can be known
+    /// without a network call.
+    ///
+    /// This is the impersonated service account, or the service account in
+    /// the ADC file. User credentials do not record an email address.
+    pub fn principal(&self) -> Option<String> {
+        if let Some(target) = &self.impersonate_service_account {
+            return Some(target.clone());
+        }
+        let text = std::fs::read_to_string(CredentialSource::detect().path()?).ok()?;
+        principal_from_adc(&serde_json::from_str(&text).ok()?)
+    }
+
+    /// Fetches an access token to prove the credentials work, and returns
+    /// the principal they belong to.
+    ///
+    /// Fails if the credentials are missing, expired or revoked. For user
+    /// credentials the email address is looked up with the token. The
+    /// principal is remembered per set of credentials, so switching projects
+    /// does not look it up again.
+    pub async fn identify(&self) -> Result<Option<String>> {
+        let key = CredentialKey::current(self);
+        let cached = IDENTITIES
+            .lock()
+            .ok()
+            .and_then(|identities| identities.get(&key).cloned());
+        if let Some(principal) = cached {
+            return Ok(principal);
+        }
+
+        let principal = self.fetch_identity().await?;
+        if let Ok(mut identities) = IDENTITIES.lock() {
+            identities.insert(key, principal.clone());
+        }
+        Ok(principal)
+    }
+
+    /// Fetches an access token and looks up whose it is, for `identify`.
+    async fn fetch_identity(&self) -> Result<Option<String>> {
+        let credentials = match &self.impersonate_service_account {
+            Some(target) => {
+                let source = self
+                    .adc_builder()
+                    .build()
+                    .context("Failed to load Application Default Credentials")?;
+                self.impersonated_builder(source, target)
+                    .build_access_token_credentials()
+                    .with_context(|| format!("Failed to impersonate {target}"))?
+            }
+            None => self
+                .adc_builder()
+                .build_access_token_credentials()
+                .context("Failed to load Application Default Credentials")?,
+        };
+        // Without a credentials file the library falls back to the metadata
+        // server, which only exists on Google Cloud; probe it briefly.
+        let on_metadata = CredentialSource::detect() == CredentialSource::MetadataServer;
+        let timeout = if on_metadata {
+            METADATA_PROBE_TIMEOUT.min(self.retry.timeout)
+        } else {
+            self.retry.timeout
+        };
+        let token = match tokio::time::timeout(timeout, credentials.access_token()).await {
+            Ok(Ok(token)) => token,
+            Ok(Err(_)) | Err(_) if on_metadata => {
+                return Err(CredentialsError::from_msg(
+                    false,
+                    "no credentials file found and no metadata server answered",
+                )
+                .into())
+            }
+            Ok(Err(e)) => return Err(e).context("Failed to refresh credentials"),
+            Err(elapsed) => {
+                return Err(anyhow!(elapsed).context("Timed out refreshing credentials"))
+            }
+        };
+
+        if let Some(principal) = self.principal() {
+            return Ok(Some(principal));
+        }
+        Ok(self.token_email(&token.token).await)
+    }
+
+    /// Looks up the email address an OAuth access token was issued to.
+    ///
+    /// The token is sent in the request body rather than the URL, which
+    /// proxies and servers may log.
+    async fn token_email(&self, token: &str) -> Option<String> {
+        let response = reqwest::Client::new()
+            .post("https://oauth2.googleapis.com/tokeninfo")
+            .form(&[("access_token", token)])
+            .timeout(self.retry.timeout)
+            .send()
+            .await
+            .ok()?;
+        let info: serde_json::Value = serde_json::from_str(&response.text().await.ok()?).ok()?;
+        info["email"].as_str().map(str::to_string)
+    }
+
+    /// Returns the Secret Manager endpoint for the configured location.
+    ///
+    /// Regional secrets are served from `secretmanager.{region}.rep.googleapis.com`.
+    pub fn secret_manager_endpoint(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .map(|region| format!("https://secretmanager.{region}.rep.googleapis.com"))
+    }
+
+    /// Returns the Resource Manager endpoint for tags of resources in the
+    /// configured location.
+    ///
+    /// Tags of regional resources are managed at `{region}-cloudresourcemanager.googleapis.com`.
+    pub fn resource_manager_endpoint(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .map(|region| format!("https://{region}-cloudresourcemanager.googleapis.com"))
+    }
+
+    /// Returns these options targeting another location.
+    pub fn at_location(&self, location: Option<String>) -> Self {
+        Self {
+            location,
+            ..self.clone()
+        }
+    }
+}
+
+/// Returns the well-known ADC file the auth library reads.
+#[cfg(target_os = "windows")]
+fn default_adc_path() -> Option<PathBuf> {
+    let root = env::var("APPDATA").ok()?;
+    Some(PathBuf::from(root).join("gcloud/application_default_credentials.json"))
+}
+
+/// Returns the well-known ADC file the auth library reads.
+#[cfg(not(target_os = "windows"))]
+fn default_adc_path() -> Option<PathBuf> {
+    let home = env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/gcloud/application_default_credentials.json"))
+}
+
+/// Extracts the principal from a parsed ADC file.
+fn principal_from_adc(adc: &serde_json::Value) -> Option<String> {
+    if let Some(email) = adc["client_email"].as_str() {
+        return Some(email.to_string());
+    }
+    // impersonated_service_account: .../serviceAccounts/{email}:generateAccessToken
+    adc["service_account_impersonation_url"]
+        .as_str()?
+        .rsplit_once("/serviceAccounts/")?
+        .1
+        .split(':')
+        .next()
+        .map(str::to_string)
+}
+
+/// Normalizes a user-supplied location (`global` means no location).
+pub fn normalize_location(location: Option<String>) -> Option<String> {
+    location.filter(|l| !l.is_empty() && l != "global")
+}
+
+/// Returns the display name of a location (`global` for `None`).
+pub fn location_label(location: Option<&str>) -> &str {
+    location.unwrap_or("global")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_location_has_no_endpoint() {
+        let options = ConnectionOptions::default();
+        assert_eq!(options.secret_manager_endpoint(), None);
+    }
+
+    #[test]
+    fn test_regional_endpoint() {
+        let options = ConnectionOptions {
+            location: Some("europe-west1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.secret_manager_endpoint().as_deref(),
+            Some("https://secretmanager.europe-west1.rep.googleapis.com")
+        );
+    }
+
+    #[test]
+    fn test_normalize_location() {
+        assert_eq!(normalize_location(None), None);
+        assert_eq!(normalize_location(Some("global".to_string())), None);
+        assert_eq!(normalize_location(Some(String::new())), None);
+        assert_eq!(
+            normalize_location(Some("us-east1".to_string())),
+            Some("us-east1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_principal_from_adc() {
+        let service_account = serde_json::json!({
+            "type": "service_account",
+            "client_email": "ci@p.iam.gserviceaccount.com",
+        });
+        assert_eq!(
+            principal_from_adc(&service_account).as_deref(),
+            Some("ci@p.iam.gserviceaccount.com")
+        );
+
+        let impersonated = serde_json::json!({
+            "type": "impersonated_service_account",
+            "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/ops@p.iam.gserviceaccount.com:generateAccessToken",
+        });
+        assert_eq!(
+            principal_from_adc(&impersonated).as_deref(),
+            Some("ops@p.iam.gserviceaccount.com")
+        );
+
+        let user = serde_json::json!({ "type": "authorized_user" });
+        assert_eq!(principal_from_adc(&user), None);
+    }
+
+    #[test]
+    fn test_no_impersonation_uses_default_credentials() {
+        let options = ConnectionOptions::default();
+        assert!(options.credentials().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_missing_file_check() {
+        let check = file_check("ADC file", Path::new("/nonexistent/adc.json"));
+        assert_eq!(check.found, Some(false));
+        assert_eq!(check.detail, "/nonexistent/adc.json (not found)");
+    }
+
+    #[test]
+    fn test_impersonation_is_identified_separately() {
+        let direct = ConnectionOptions::default();
+        let impersonating = ConnectionOptions {
+            impersonate_service_account: Some("ops@p.iam.gserviceaccount.com".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            CredentialKey::current(&direct),
+            CredentialKey::current(&direct)
+        );
+        assert_ne!(
+            CredentialKey::current(&direct),
+            CredentialKey::current(&impersonating)
+        );
+    }
+
+    #[test]
+    fn test_credential_files_are_labeled_by_type() {
+        let source = |kind| CredentialSource::AdcFile(PathBuf::from("adc.json"), kind);
+        assert_eq!(
+            source(CredentialKind::from_type("external_account")).to_string(),
+            "ADC file, workload identity federation"
+        );
+        assert_eq!(
+            source(CredentialKind::from_type("impersonated_service_account")).to_string(),
+            "ADC file, impersonated service account"
+        );
+        assert_eq!(
+            CredentialKind::from_type("external_account_authorized_user"),
+            CredentialKind::Unknown
+        );
+        assert_eq!(
+            CredentialKind::of_file(Path::new("/nonexistent/adc.json")),
+            CredentialKind::Unknown
+        );
+    }
+}
@@ -7,6 +7,18 @@ use std::time::Duration;
 /// Event polling timeout - balances responsiveness with CPU usage.
 pub const POLL_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// Keys pressed this soon after a confirmation dialog opens are discarded,
+/// so keystrokes buffered before it appeared cannot confirm it.
+pub const CONFIRM_INPUT_DELAY: Duration = Duration::from_millis(500);
+
+/// How long to wait for the metadata server when no credentials file exists.
+/// Off Google Cloud nothing answers, so this bounds the startup delay.
+pub const METADATA_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How many secrets have their versions listed at once when the secrets
+/// list shows version columns.
+pub const VERSION_SUMMARY_CONCURRENCY: usize = 8;
+
 /// Layout dimensions for the main UI structure.
 pub mod layout {
     /// Header height including ASCII art and info panel.
@@ -30,7 +42,7 @@ pub mod dialog {
     /// Confirm dialog width percentage.
     pub const CONFIRM_WIDTH: u16 = 55;
     /// Confirm dialog height percentage.
-    pub const CONFIRM_HEIGHT: u16 = 35;
+    pub const CONFIRM_HEIGHT: u16 = 45;
     /// Project selector width percentage.
     pub const PROJECT_SELECTOR_WIDTH: u16 = 60;
     /// Project selector height percentage.
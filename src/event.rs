@@ -8,6 +8,7 @@ use std::time::Duration;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use crate::constants::POLL_TIMEOUT;
+use crate::keymap::{Keymap, Lookup, Scope};
 
 /// Represents the different actions a user can take in the application.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -46,6 +47,32 @@ pub enum Action {
     Disable,
     /// Open the project selector
     OpenProjectSelector,
+    /// Show the audit history
+    History,
+    /// Open the gcloud configuration selector
+    OpenConfigSelector,
+    /// Open the location selector
+    OpenLocationSelector,
+    /// Mark or unmark the selected item in a multi-select list
+    ToggleSelection,
+    /// Switch between the versions and IAM tabs of a secret
+    SwitchTab,
+    /// Grant a role on the current secret
+    AddMember,
+    /// Set, extend or remove the expiration of a secret
+    SetExpiration,
+    /// Set or remove the delay before versions of a secret are destroyed
+    SetDestroyTtl,
+    /// Only list secrets not protected by a customer-managed key, or all again
+    ToggleCmekFilter,
+    /// Bind a tag value to the current secret
+    BindTag,
+    /// Filter the secrets or versions list with an API expression
+    Filter,
+    /// Sort the secrets list by the next column
+    Sort,
+    /// Reverse the order of the secrets list
+    ReverseSort,
     /// Character input (for text entry mode)
     Char(char),
     /// Backspace key (for text entry mode)
@@ -56,10 +83,147 @@ pub enum Action {
     CursorRight,
 }
 
+impl Action {
+    /// Actions that can be rebound from the config file, by name.
+    pub const BINDABLE: [(&'static str, Self); 30] = [
+        ("quit", Self::Quit),
+        ("up", Self::Up),
+        ("down", Self::Down),
+        ("top", Self::Top),
+        ("bottom", Self::Bottom),
+        ("enter", Self::Enter),
+        ("back", Self::Back),
+        ("refresh", Self::Refresh),
+        ("new_secret", Self::NewSecret),
+        ("new_version", Self::NewVersion),
+        ("delete", Self::Delete),
+        ("copy", Self::Copy),
+        ("toggle_value", Self::ToggleSecretValue),
+        ("help", Self::Help),
+        ("enable", Self::Enable),
+        ("disable", Self::Disable),
+        ("project_selector", Self::OpenProjectSelector),
+        ("history", Self::History),
+        ("config_selector", Self::OpenConfigSelector),
+        ("location_selector", Self::OpenLocationSelector),
+        ("toggle", Self::ToggleSelection),
+        ("switch_tab", Self::SwitchTab),
+        ("add_member", Self::AddMember),
+        ("expiration", Self::SetExpiration),
+        ("destroy_ttl", Self::SetDestroyTtl),
+        ("cmek_filter", Self::ToggleCmekFilter),
+        ("bind_tag", Self::BindTag),
+        ("filter", Self::Filter),
+        ("sort", Self::Sort),
+        ("sort_reverse", Self::ReverseSort),
+    ];
+
+    /// Looks up a bindable action by its config name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::BINDABLE
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, action)| action.clone())
+    }
+
+    /// Returns the config name of a bindable action (`?` for the others).
+    pub fn name(&self) -> &'static str {
+        Self::BINDABLE
+            .iter()
+            .find(|(_, action)| action == self)
+            .map_or("?", |(name, _)| name)
+    }
+
+    /// Returns true if this action creates, changes or deletes data.
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Self::NewSecret
+                | Self::NewVersion
+                | Self::Delete
+                | Self::Enable
+                | Self::Disable
+                | Self::AddMember
+                | Self::SetExpiration
+                | Self::SetDestroyTtl
+                | Self::BindTag
+        )
+    }
+}
+
+/// A single key press with modifiers, as written in the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// The key
+    pub code: KeyCode,
+    /// Modifier keys (only Ctrl and Alt are significant)
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parses a key description such as `q`, `G`, `ctrl+r`, `f5` or `enter`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(stripped) = lower.strip_prefix("ctrl+") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else if let Some(stripped) = lower.strip_prefix("alt+") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[rest.len() - stripped.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "delete" | "del" => KeyCode::Delete,
+                f if f.starts_with('f') => f[1..]
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|n| (1..=12).contains(n))
+                    .map(KeyCode::F)
+                    .ok_or_else(|| format!("unknown key '{text}'"))?,
+                _ => return Err(format!("unknown key '{text}'")),
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    /// Builds the binding for a key event, ignoring irrelevant modifiers.
+    pub fn from_event(key: KeyEvent) -> Self {
+        Self {
+            code: key.code,
+            modifiers: key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
 /// Handles terminal events and converts them to application actions.
 pub struct EventHandler {
     /// Timeout for polling events
     poll_timeout: Duration,
+    /// Keys typed so far of an unfinished sequence (e.g. the first `g` of `gg`)
+    pending: Vec<KeyBinding>,
 }
 
 impl EventHandler {
@@ -67,6 +231,7 @@ impl EventHandler {
     pub fn new() -> Self {
         Self {
             poll_timeout: POLL_TIMEOUT,
+            pending: Vec::new(),
         }
     }
 
@@ -74,14 +239,14 @@ impl EventHandler {
     ///
     /// Returns Ok(None) if no event is available within the timeout.
     /// Returns Ok(Some(action)) if a key event was converted to an action.
-    pub fn next(&self) -> io::Result<Option<Action>> {
+    pub fn next(&mut self, keymap: &Keymap, scope: Scope) -> io::Result<Option<Action>> {
         // Check if an event is available
         if event::poll(self.poll_timeout)? {
             // Read the event
             if let Event::Key(key_event) = event::read()? {
                 // Only process key press events (not releases)
                 if key_event.kind == KeyEventKind::Press {
-                    return Ok(self.key_to_action(key_event));
+                    return Ok(self.key_to_action(keymap, scope, key_event));
                 }
             }
         }
@@ -91,67 +256,100 @@ impl EventHandler {
     /// Polls for input-mode events (for text entry).
     ///
     /// This captures character input and special keys for text editing.
-    pub fn next_input(&self) -> io::Result<Option<Action>> {
+    pub fn next_input(&mut self, keymap: &Keymap, scope: Scope) -> io::Result<Option<Action>> {
         if event::poll(self.poll_timeout)? {
             if let Event::Key(key_event) = event::read()? {
                 if key_event.kind == KeyEventKind::Press {
-                    return Ok(self.key_to_input_action(key_event));
+                    return Ok(self.key_to_input_action(keymap, scope, key_event));
                 }
             }
         }
         Ok(None)
     }
 
+    /// Reads the keys pressed while a call is running, without waiting, and
+    /// returns true if one of them asks to go back or quit.
+    ///
+    /// Other keys are discarded so that nothing typed during the call acts
+    /// on whatever view the call leaves behind.
+    pub fn cancel_requested(&mut self, keymap: &Keymap, scope: Scope) -> io::Result<bool> {
+        let mut cancel = false;
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key_event) = event::read()? {
+                if key_event.kind == KeyEventKind::Press {
+                    cancel |= matches!(
+                        self.key_to_action(keymap, scope, key_event),
+                        Some(Action::Back | Action::Quit)
+                    );
+                }
+            }
+        }
+        self.pending.clear();
+        Ok(cancel)
+    }
+
     /// Converts a key event to an input-mode action.
-    pub(crate) fn key_to_input_action(&self, key: KeyEvent) -> Option<Action> {
+    pub(crate) fn key_to_input_action(
+        &mut self,
+        keymap: &Keymap,
+        scope: Scope,
+        key: KeyEvent,
+    ) -> Option<Action> {
+        self.pending.clear();
+
         // Check for Ctrl+C (quit)
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
             return Some(Action::Quit);
         }
 
+        // Submit / cancel keys come from the keymap
+        if let Lookup::Action(action) = keymap.lookup(scope, &[KeyBinding::from_event(key)]) {
+            return Some(action);
+        }
+
         match key.code {
-            KeyCode::Enter => Some(Action::Enter),
-            KeyCode::Esc => Some(Action::Back),
             KeyCode::Backspace => Some(Action::Backspace),
             KeyCode::Left => Some(Action::CursorLeft),
             KeyCode::Right => Some(Action::CursorRight),
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Down => Some(Action::Down),
             KeyCode::Char(c) => Some(Action::Char(c)),
             _ => None,
         }
     }
 
     /// Converts a key event to an application action.
-    pub(crate) fn key_to_action(&self, key: KeyEvent) -> Option<Action> {
-        // Check for Ctrl+C first (quit)
+    ///
+    /// Keys that start a longer sequence are held until the sequence is
+    /// complete; an unbound continuation is retried as a fresh key press.
+    pub(crate) fn key_to_action(
+        &mut self,
+        keymap: &Keymap,
+        scope: Scope,
+        key: KeyEvent,
+    ) -> Option<Action> {
+        // Ctrl+C always quits, whatever the keymap says
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.pending.clear();
             return Some(Action::Quit);
         }
 
-        // Map keys to actions
-        match key.code {
-            // Navigation
-            KeyCode::Up | KeyCode::Char('k') => Some(Action::Up),
-            KeyCode::Down | KeyCode::Char('j') => Some(Action::Down),
-            KeyCode::Home | KeyCode::Char('g') => Some(Action::Top),
-            KeyCode::End | KeyCode::Char('G') => Some(Action::Bottom),
-            KeyCode::Enter => Some(Action::Enter),
-            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('b') => Some(Action::Back),
-
-            // Actions
-            KeyCode::Char('q') => Some(Action::Quit),
-            KeyCode::Char('r') => Some(Action::Refresh),
-            KeyCode::Char('n') => Some(Action::NewSecret),
-            KeyCode::Char('a') => Some(Action::NewVersion),
-            KeyCode::Char('d') => Some(Action::Delete),
-            KeyCode::Char('c') => Some(Action::Copy),
-            KeyCode::Char('s') => Some(Action::ToggleSecretValue),
-            KeyCode::Char('?') | KeyCode::F(1) => Some(Action::Help),
-            KeyCode::Char('e') => Some(Action::Enable),
-            KeyCode::Char('x') => Some(Action::Disable),
-            KeyCode::Char('p') => Some(Action::OpenProjectSelector),
-
-            // No matching action
-            _ => None,
+        self.pending.push(KeyBinding::from_event(key));
+        match keymap.lookup(scope, &self.pending) {
+            Lookup::Action(action) => {
+                self.pending.clear();
+                Some(action)
+            }
+            Lookup::Pending => None,
+            Lookup::Unbound => {
+                let retry = self.pending.len() > 1;
+                self.pending.clear();
+                if retry {
+                    self.key_to_action(keymap, scope, key)
+                } else {
+                    None
+                }
+            }
         }
     }
 }
@@ -165,6 +363,7 @@ impl Default for EventHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::keymap::{KeyOverride, KeySequence};
     use crossterm::event::{KeyEventKind, KeyEventState};
 
     fn make_key_event(code: KeyCode) -> KeyEvent {
@@ -187,202 +386,393 @@ mod tests {
 
     #[test]
     fn test_vim_navigation_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('j'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('j'))
+            ),
             Some(Action::Down)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('k'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('k'))
+            ),
             Some(Action::Up)
         );
+        // 'g' alone starts a sequence, 'gg' completes it
+        assert_eq!(
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('g'))
+            ),
+            None
+        );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('g'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('g'))
+            ),
             Some(Action::Top)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('G'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('G'))
+            ),
             Some(Action::Bottom)
         );
     }
 
     #[test]
     fn test_arrow_navigation_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Up)),
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::Up)),
             Some(Action::Up)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Down)),
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::Down)),
             Some(Action::Down)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Home)),
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::Home)),
             Some(Action::Top)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::End)),
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::End)),
             Some(Action::Bottom)
         );
     }
 
     #[test]
     fn test_quit_actions() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('q'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('q'))
+            ),
             Some(Action::Quit)
         );
         assert_eq!(
-            handler.key_to_action(make_ctrl_key_event(KeyCode::Char('c'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_ctrl_key_event(KeyCode::Char('c'))
+            ),
             Some(Action::Quit)
         );
     }
 
     #[test]
     fn test_action_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('n'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('n'))
+            ),
             Some(Action::NewSecret)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('a'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('a'))
+            ),
             Some(Action::NewVersion)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('d'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('d'))
+            ),
             Some(Action::Delete)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('c'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('c'))
+            ),
             Some(Action::Copy)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('s'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('s'))
+            ),
             Some(Action::ToggleSecretValue)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('r'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('r'))
+            ),
             Some(Action::Refresh)
         );
     }
 
     #[test]
     fn test_help_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('?'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('?'))
+            ),
             Some(Action::Help)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::F(1))),
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::F(1))),
             Some(Action::Help)
         );
     }
 
     #[test]
     fn test_unknown_key_returns_none() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('z'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('z'))
+            ),
+            None
+        );
+        assert_eq!(
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::F(12))),
             None
         );
-        assert_eq!(handler.key_to_action(make_key_event(KeyCode::F(12))), None);
     }
 
     #[test]
     fn test_input_mode_actions() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Enter)),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Enter)),
             Some(Action::Enter)
         );
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Esc)),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Esc)),
             Some(Action::Back)
         );
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Backspace)),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Backspace)),
             Some(Action::Backspace)
         );
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Char('a'))),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Char('a'))),
             Some(Action::Char('a'))
         );
+        // Up and Down recall recent filters
+        assert_eq!(
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Up)),
+            Some(Action::Up)
+        );
     }
 
     #[test]
     fn test_input_mode_ctrl_c_quits() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_input_action(make_ctrl_key_event(KeyCode::Char('c'))),
+            handler.key_to_input_action(
+                &keymap,
+                Scope::Input,
+                make_ctrl_key_event(KeyCode::Char('c'))
+            ),
             Some(Action::Quit)
         );
     }
 
     #[test]
     fn test_enable_disable_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('e'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('e'))
+            ),
             Some(Action::Enable)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('x'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('x'))
+            ),
             Some(Action::Disable)
         );
     }
 
     #[test]
     fn test_project_selector_key() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('p'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('p'))
+            ),
             Some(Action::OpenProjectSelector)
         );
     }
 
     #[test]
     fn test_back_keys() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         // All three keys should map to Back action
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Esc)),
+            handler.key_to_action(&keymap, Scope::SecretDetail, make_key_event(KeyCode::Esc)),
             Some(Action::Back)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Backspace)),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Backspace)
+            ),
             Some(Action::Back)
         );
         assert_eq!(
-            handler.key_to_action(make_key_event(KeyCode::Char('b'))),
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretDetail,
+                make_key_event(KeyCode::Char('b'))
+            ),
             Some(Action::Back)
         );
     }
 
+    #[test]
+    fn test_parse_key_bindings() {
+        assert_eq!(
+            KeyBinding::parse("q"),
+            Ok(KeyBinding {
+                code: KeyCode::Char('q'),
+                modifiers: KeyModifiers::NONE
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl+r"),
+            Ok(KeyBinding {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL
+            })
+        );
+        assert_eq!(KeyBinding::parse("F5").map(|k| k.code), Ok(KeyCode::F(5)));
+        assert_eq!(
+            KeyBinding::parse("enter").map(|k| k.code),
+            Ok(KeyCode::Enter)
+        );
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(KeyBinding::parse("nope").is_err());
+    }
+
+    #[test]
+    fn test_keybinding_overrides_replace_defaults() {
+        let mut handler = EventHandler::new();
+        let (keymap, _) = Keymap::new(&[KeyOverride {
+            scope: None,
+            action: Action::Refresh,
+            keys: vec![KeySequence::parse("f5").unwrap()],
+        }]);
+
+        assert_eq!(
+            handler.key_to_action(&keymap, Scope::SecretsList, make_key_event(KeyCode::F(5))),
+            Some(Action::Refresh)
+        );
+        // Default key for the rebound action no longer works
+        assert_eq!(
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('r'))
+            ),
+            None
+        );
+        // Other defaults are untouched
+        assert_eq!(
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('q'))
+            ),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn test_unbound_continuation_is_retried() {
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
+
+        // 'g' then 'j' is not a sequence, so 'j' acts on its own
+        assert_eq!(
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('g'))
+            ),
+            None
+        );
+        assert_eq!(
+            handler.key_to_action(
+                &keymap,
+                Scope::SecretsList,
+                make_key_event(KeyCode::Char('j'))
+            ),
+            Some(Action::Down)
+        );
+    }
+
     #[test]
     fn test_input_mode_cursor_movement() {
-        let handler = EventHandler::new();
+        let mut handler = EventHandler::new();
+        let keymap = Keymap::default();
 
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Left)),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Left)),
             Some(Action::CursorLeft)
         );
         assert_eq!(
-            handler.key_to_input_action(make_key_event(KeyCode::Right)),
+            handler.key_to_input_action(&keymap, Scope::Input, make_key_event(KeyCode::Right)),
             Some(Action::CursorRight)
         );
     }
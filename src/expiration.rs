@@ -0,0 +1,187 @@
+//! Secret expiration and delayed version destruction.
+//!
+//! Secret Manager deletes a secret once its expiration time is reached, and
+//! destroys a version scheduled for destruction once its destroy TTL has
+//! passed. This module formats the countdowns shown for both and parses the
+//! inputs that change them.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Expirations closer than this (in seconds) are shown as a warning.
+const SOON: i64 = 7 * 86_400;
+
+/// Expirations closer than this (in seconds) are shown as an error.
+const IMMINENT: i64 = 86_400;
+
+/// How close an expiration is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    /// More than a week away
+    Distant,
+    /// Within a week
+    Soon,
+    /// Within a day, or already passed
+    Imminent,
+}
+
+/// Returns how close `expire_time` is to `now` (both Unix seconds).
+pub fn urgency(expire_time: i64, now: i64) -> Urgency {
+    let left = expire_time - now;
+    if left <= IMMINENT {
+        Urgency::Imminent
+    } else if left <= SOON {
+        Urgency::Soon
+    } else {
+        Urgency::Distant
+    }
+}
+
+/// Formats the time left until `expire_time`, e.g. `in 3d 4h`.
+pub fn countdown(expire_time: i64, now: i64) -> String {
+    let left = expire_time - now;
+    if left <= 0 {
+        return "expired".to_string();
+    }
+    let (days, hours, minutes) = (left / 86_400, left % 86_400 / 3600, left % 3600 / 60);
+    if days >= 7 {
+        format!("in {days}d")
+    } else if days > 0 {
+        format!("in {days}d {hours}h")
+    } else if hours > 0 {
+        format!("in {hours}h {minutes}m")
+    } else {
+        format!("in {}m", minutes.max(1))
+    }
+}
+
+/// Formats an expiration time as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_time(expire_time: i64) -> String {
+    DateTime::<Utc>::from_timestamp(expire_time, 0).map_or_else(
+        || "Unknown".to_string(),
+        |dt| dt.format("%Y-%m-%d %H:%M UTC").to_string(),
+    )
+}
+
+/// Parses a duration such as `90m`, `12h`, `30d` or `2w` into seconds.
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let unit = text.chars().last()?;
+    let seconds = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86_400,
+        'w' => 7 * 86_400,
+        _ => return None,
+    };
+    let amount: i64 = text[..text.len() - 1].parse().ok()?;
+    (amount > 0).then(|| amount.checked_mul(seconds)).flatten()
+}
+
+/// Error for expirations too far in the future to represent.
+const TOO_FAR: &str = "The expiration is too far in the future";
+
+/// Parses the expiration input into the new expiration time (`None` removes
+/// it).
+///
+/// Accepts a duration from now (`30d`), an extension of the `current`
+/// expiration (`+7d`), a date (`2025-12-31`, midnight UTC), an RFC 3339
+/// time, or `never`.
+pub fn parse_expiration(
+    input: &str,
+    current: Option<i64>,
+    now: i64,
+) -> Result<Option<i64>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("never") {
+        return Ok(None);
+    }
+
+    let expire_time = if let Some(extension) = input.strip_prefix('+') {
+        let extension = parse_duration(extension)
+            .ok_or_else(|| format!("'{extension}' is not a duration such as 7d"))?;
+        let current = current.ok_or("The secret does not expire - enter a duration or date")?;
+        current.max(now).checked_add(extension).ok_or(TOO_FAR)?
+    } else if let Some(duration) = parse_duration(input) {
+        now.checked_add(duration).ok_or(TOO_FAR)?
+    } else if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        date.and_hms_opt(0, 0, 0)
+            .map_or(0, |time| time.and_utc().timestamp())
+    } else if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        time.timestamp()
+    } else {
+        return Err(format!(
+            "'{input}' is not a duration (30d), extension (+7d), date (2025-12-31) or 'never'"
+        ));
+    };
+
+    if expire_time <= now {
+        return Err("The expiration must be in the future".to_string());
+    }
+    Ok(Some(expire_time))
+}
+
+/// Parses the destroy TTL input into seconds (`None` destroys versions
+/// immediately again).
+///
+/// Accepts a duration such as `7d`, or `off`. The API enforces the
+/// allowed range.
+pub fn parse_destroy_ttl(input: &str) -> Result<Option<i64>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    parse_duration(input)
+        .map(Some)
+        .ok_or_else(|| format!("'{input}' is not a duration (7d, 24h) or 'off'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_750_000_000;
+
+    #[test]
+    fn test_countdown_and_urgency() {
+        assert_eq!(countdown(NOW + 30 * 86_400, NOW), "in 30d");
+        assert_eq!(countdown(NOW + 3 * 86_400 + 4 * 3600, NOW), "in 3d 4h");
+        assert_eq!(countdown(NOW + 5 * 3600 + 120, NOW), "in 5h 2m");
+        assert_eq!(countdown(NOW - 1, NOW), "expired");
+
+        assert_eq!(urgency(NOW + 30 * 86_400, NOW), Urgency::Distant);
+        assert_eq!(urgency(NOW + 3 * 86_400, NOW), Urgency::Soon);
+        assert_eq!(urgency(NOW + 3600, NOW), Urgency::Imminent);
+    }
+
+    #[test]
+    fn test_parse_expiration() {
+        assert_eq!(
+            parse_expiration("30d", None, NOW),
+            Ok(Some(NOW + 30 * 86_400))
+        );
+        assert_eq!(
+            parse_expiration("+1w", Some(NOW + 100), NOW),
+            Ok(Some(NOW + 100 + 7 * 86_400))
+        );
+        assert_eq!(parse_expiration("never", Some(NOW + 100), NOW), Ok(None));
+        assert_eq!(
+            parse_expiration("2030-01-01", None, NOW),
+            Ok(Some(1_893_456_000))
+        );
+
+        assert!(parse_expiration("+7d", None, NOW).is_err());
+        assert!(parse_expiration("2020-01-01", None, NOW).is_err());
+        assert!(parse_expiration("soon", None, NOW).is_err());
+        assert!(parse_expiration("0d", None, NOW).is_err());
+        assert!(parse_expiration("9223372036854775807s", None, NOW).is_err());
+        assert!(parse_expiration("+9223372036854775807s", Some(NOW), NOW).is_err());
+    }
+
+    #[test]
+    fn test_parse_destroy_ttl() {
+        assert_eq!(parse_destroy_ttl("7d"), Ok(Some(7 * 86_400)));
+        assert_eq!(parse_destroy_ttl(" OFF "), Ok(None));
+        assert!(parse_destroy_ttl("7").is_err());
+        assert!(parse_destroy_ttl("-1d").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Recently used filter expressions.
+//!
+//! Filters sent to the API when listing secrets or versions are kept one
+//! per line, most recent first, in `$XDG_STATE_HOME/gsmtui/filters`, so
+//! they can be recalled in the filter prompt.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// How many filters are remembered.
+pub const MAX_RECENT: usize = 20;
+
+/// Recently used filters, most recent first.
+///
+/// A history without a path is not saved; this is what tests use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterHistory {
+    path: Option<PathBuf>,
+    recent: Vec<String>,
+}
+
+impl FilterHistory {
+    /// Returns the default history file location.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(crate::audit::state_dir()?.join("filters"))
+    }
+
+    /// Loads the history saved at `path`; a missing file is an empty history.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let recent = path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|text| {
+                text.lines()
+                    .filter(|l| !l.trim().is_empty())
+                    .take(MAX_RECENT)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { path, recent }
+    }
+
+    /// Returns the remembered filters, most recent first.
+    pub fn recent(&self) -> &[String] {
+        &self.recent
+    }
+
+    /// Remembers `filter` as the most recent one and saves the history.
+    pub fn add(&mut self, filter: &str) -> Result<()> {
+        self.recent.retain(|f| f != filter);
+        self.recent.insert(0, filter.to_string());
+        self.recent.truncate(MAX_RECENT);
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut text = self.recent.join("\n");
+        text.push('\n');
+        fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_filters_round_trip_most_recent_first() {
+        let path = std::env::temp_dir()
+            .join(format!("gsmtui-filters-{}", std::process::id()))
+            .join("filters");
+        let _ = fs::remove_file(&path);
+
+        let mut history = FilterHistory::load(Some(path.clone()));
+        assert!(history.recent().is_empty());
+        history.add("labels.team=payments").unwrap();
+        history.add("name:api").unwrap();
+        history.add("labels.team=payments").unwrap();
+
+        let loaded = FilterHistory::load(Some(path));
+        assert_eq!(loaded.recent(), ["labels.team=payments", "name:api"]);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = FilterHistory::default();
+        for i in 0..=MAX_RECENT {
+            history.add(&format!("name:s{i}")).unwrap();
+        }
+        assert_eq!(history.recent().len(), MAX_RECENT);
+        assert_eq!(history.recent()[0], format!("name:s{MAX_RECENT}"));
+    }
+}
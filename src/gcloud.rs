@@ -0,0 +1,135 @@
+//! Reading gcloud CLI configuration.
+//!
+//! gcloud keeps named configurations as INI files under
+//! `$CLOUDSDK_CONFIG/configurations/config_<name>` (default config directory
+//! `~/.config/gcloud`). The active one is named by
+//! `CLOUDSDK_ACTIVE_CONFIG_NAME`, or else by the `active_config` file.
+//! These files are only read; switching configurations inside gsmtui does
+//! not change gcloud's own active configuration.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// A named gcloud configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcloudConfig {
+    /// Configuration name
+    pub name: String,
+    /// `core/project`, if set
+    pub project: Option<String>,
+    /// `core/account`, if set
+    pub account: Option<String>,
+}
+
+/// Returns the gcloud config directory.
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("CLOUDSDK_CONFIG")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/gcloud")))
+}
+
+/// Returns the name of the active configuration.
+pub fn active_config_name() -> String {
+    if let Some(name) = env::var("CLOUDSDK_ACTIVE_CONFIG_NAME")
+        .ok()
+        .filter(|n| !n.is_empty())
+    {
+        return name;
+    }
+    config_dir()
+        .and_then(|dir| fs::read_to_string(dir.join("active_config")).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Reads one named configuration.
+pub fn load(name: &str) -> Option<GcloudConfig> {
+    let path = config_dir()?
+        .join("configurations")
+        .join(format!("config_{name}"));
+    let text = fs::read_to_string(path).ok()?;
+    Some(parse(name, &text))
+}
+
+/// Reads the active configuration.
+pub fn active() -> Option<GcloudConfig> {
+    load(&active_config_name())
+}
+
+/// Lists all named configurations, sorted by name.
+pub fn list() -> Vec<GcloudConfig> {
+    let Some(dir) = config_dir().map(|d| d.join("configurations")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut configs: Vec<GcloudConfig> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let name = file_name.strip_prefix("config_")?;
+            let text = fs::read_to_string(entry.path()).ok()?;
+            Some(parse(name, &text))
+        })
+        .collect();
+    configs.sort_by(|a, b| a.name.cmp(&b.name));
+    configs
+}
+
+/// Parses the `[core]` section of a configuration file.
+fn parse(name: &str, text: &str) -> GcloudConfig {
+    let mut config = GcloudConfig {
+        name: name.to_string(),
+        project: None,
+        account: None,
+    };
+    let mut in_core = false;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_core = line == "[core]";
+            continue;
+        }
+        if !in_core || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim() {
+            "project" => config.project = Some(value.to_string()),
+            "account" => config.account = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core_section() {
+        let config = parse(
+            "work",
+            "[compute]\nproject = not-this\n\n[core]\naccount = me@example.com\nproject = my-project\n# project = commented\n",
+        );
+        assert_eq!(config.name, "work");
+        assert_eq!(config.project.as_deref(), Some("my-project"));
+        assert_eq!(config.account.as_deref(), Some("me@example.com"));
+    }
+
+    #[test]
+    fn test_parse_without_project() {
+        let config = parse("empty", "[core]\nproject =\n");
+        assert_eq!(config.project, None);
+    }
+}
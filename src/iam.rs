@@ -0,0 +1,293 @@
+//! IAM policies of secrets.
+//!
+//! A simplified view of a secret's IAM policy, with the edits the IAM view
+//! makes. Policies are written back with the etag they were read with, so a
+//! concurrent change makes the write fail instead of being overwritten.
+//!
+//! Also maps commands to the permissions they need, which are tested when
+//! a secret is opened.
+
+use crate::event::Action;
+use crate::keymap::Scope;
+
+/// Role granted when adding a member without naming one.
+pub const DEFAULT_ROLE: &str = "roles/secretmanager.secretAccessor";
+
+/// Members that make a secret readable by anyone.
+pub const PUBLIC_MEMBERS: [&str; 2] = ["allUsers", "allAuthenticatedUsers"];
+
+/// Policy version that supports conditional role bindings.
+pub const CONDITIONAL_POLICY_VERSION: i32 = 3;
+
+/// A condition attached to a role binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IamCondition {
+    /// Short title
+    pub title: String,
+    /// CEL expression
+    pub expression: String,
+    /// Longer description
+    pub description: String,
+    /// Where the expression came from (for error messages)
+    pub location: String,
+}
+
+/// Members granted a role, optionally under a condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IamBinding {
+    /// Role, e.g. `roles/secretmanager.secretAccessor`
+    pub role: String,
+    /// Principals, e.g. `user:ana@example.com`
+    pub members: Vec<String>,
+    /// Condition limiting when the binding applies
+    pub condition: Option<IamCondition>,
+}
+
+/// The IAM policy of a secret.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IamPolicy {
+    /// Policy format version
+    pub version: i32,
+    /// Role bindings, sorted by role
+    pub bindings: Vec<IamBinding>,
+    /// Etag of the policy as read
+    pub etag: Vec<u8>,
+}
+
+impl IamPolicy {
+    /// Sorts bindings by role so that bindings of a role are listed together.
+    pub fn sort(&mut self) {
+        self.bindings.sort_by(|a, b| {
+            (&a.role, a.condition.as_ref().map(|c| &c.title))
+                .cmp(&(&b.role, b.condition.as_ref().map(|c| &c.title)))
+        });
+    }
+
+    /// Grants `role` to `member` without a condition.
+    ///
+    /// Returns false if the member already has it.
+    pub fn add_member(&mut self, role: &str, member: &str) -> bool {
+        if let Some(binding) = self
+            .bindings
+            .iter_mut()
+            .find(|b| b.role == role && b.condition.is_none())
+        {
+            if binding.members.iter().any(|m| m == member) {
+                return false;
+            }
+            binding.members.push(member.to_string());
+        } else {
+            self.bindings.push(IamBinding {
+                role: role.to_string(),
+                members: vec![member.to_string()],
+                condition: None,
+            });
+            self.sort();
+        }
+        true
+    }
+
+    /// Removes `member` from the binding at `binding`.
+    ///
+    /// A binding left without members is dropped, as the API requires.
+    /// Returns false if the member was not in the binding.
+    pub fn remove_member(&mut self, binding: usize, member: &str) -> bool {
+        let Some(b) = self.bindings.get_mut(binding) else {
+            return false;
+        };
+        let Some(pos) = b.members.iter().position(|m| m == member) else {
+            return false;
+        };
+        b.members.remove(pos);
+        if b.members.is_empty() {
+            self.bindings.remove(binding);
+        }
+        true
+    }
+
+    /// Returns the (binding index, member) pairs in display order.
+    pub fn rows(&self) -> Vec<(usize, &str)> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, b)| b.members.iter().map(move |m| (idx, m.as_str())))
+            .collect()
+    }
+
+    /// Returns true if any binding grants access to everyone.
+    pub fn is_public(&self) -> bool {
+        self.bindings
+            .iter()
+            .any(|b| b.members.iter().any(|m| is_public_member(m)))
+    }
+
+    /// Returns true if any binding has a condition.
+    pub fn has_conditions(&self) -> bool {
+        self.bindings.iter().any(|b| b.condition.is_some())
+    }
+}
+
+/// Permission to read version payloads.
+pub const ACCESS_VERSION: &str = "secretmanager.versions.access";
+
+/// Permission to add versions.
+pub const ADD_VERSION: &str = "secretmanager.versions.add";
+
+/// Permission to enable versions.
+pub const ENABLE_VERSION: &str = "secretmanager.versions.enable";
+
+/// Permission to disable versions.
+pub const DISABLE_VERSION: &str = "secretmanager.versions.disable";
+
+/// Permission to destroy versions.
+pub const DESTROY_VERSION: &str = "secretmanager.versions.destroy";
+
+/// Permission to delete secrets.
+pub const DELETE_SECRET: &str = "secretmanager.secrets.delete";
+
+/// Permission to change a secret's IAM policy.
+pub const SET_IAM_POLICY: &str = "secretmanager.secrets.setIamPolicy";
+
+/// Permission to update a secret's settings (expiration, destroy TTL).
+pub const UPDATE_SECRET: &str = "secretmanager.secrets.update";
+
+/// Permissions tested when entering a secret, so that commands the caller
+/// cannot perform are shown as unavailable.
+pub const SECRET_PERMISSIONS: [&str; 8] = [
+    ACCESS_VERSION,
+    ADD_VERSION,
+    ENABLE_VERSION,
+    DISABLE_VERSION,
+    DESTROY_VERSION,
+    DELETE_SECRET,
+    SET_IAM_POLICY,
+    UPDATE_SECRET,
+];
+
+/// Returns the permission an action needs on the selected secret in a view.
+pub fn required_permission(scope: Scope, action: &Action) -> Option<&'static str> {
+    let permission = match (scope, action) {
+        (Scope::SecretDetail, Action::ToggleSecretValue | Action::Copy) => ACCESS_VERSION,
+        (Scope::SecretDetail, Action::NewVersion) => ADD_VERSION,
+        (Scope::SecretDetail, Action::Enable) => ENABLE_VERSION,
+        (Scope::SecretDetail, Action::Disable) => DISABLE_VERSION,
+        (Scope::SecretDetail, Action::Delete) => DESTROY_VERSION,
+        (Scope::SecretsList, Action::Delete) => DELETE_SECRET,
+        (Scope::SecretIam, Action::AddMember | Action::Delete) => SET_IAM_POLICY,
+        (
+            Scope::SecretsList | Scope::SecretDetail,
+            Action::SetExpiration | Action::SetDestroyTtl,
+        ) => UPDATE_SECRET,
+        _ => return None,
+    };
+    Some(permission)
+}
+
+/// Returns true for `allUsers` and `allAuthenticatedUsers`.
+pub fn is_public_member(member: &str) -> bool {
+    PUBLIC_MEMBERS.contains(&member)
+}
+
+/// Parses the add-member input: a member, optionally followed by a role.
+///
+/// Roles may be given without the `roles/` prefix; without a role,
+/// [`DEFAULT_ROLE`] is granted.
+pub fn parse_grant(input: &str) -> Result<(String, String), String> {
+    let mut parts = input.split_whitespace();
+    let member = parts
+        .next()
+        .ok_or("Enter a member, e.g. user:ana@example.com")?;
+    crate::validation::validate_iam_member(member)?;
+    let role = match parts.next() {
+        Some(role) if role.contains('/') => role.to_string(),
+        Some(role) => format!("roles/{role}"),
+        None => DEFAULT_ROLE.to_string(),
+    };
+    if parts.next().is_some() {
+        return Err("Enter a member and at most one role".to_string());
+    }
+    Ok((member.to_string(), role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> IamPolicy {
+        IamPolicy {
+            version: 1,
+            bindings: vec![IamBinding {
+                role: DEFAULT_ROLE.to_string(),
+                members: vec!["user:ana@example.com".to_string()],
+                condition: None,
+            }],
+            etag: b"BwX1".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_add_member_joins_existing_binding() {
+        let mut policy = policy();
+
+        assert!(policy.add_member(DEFAULT_ROLE, "group:ops@example.com"));
+        assert!(!policy.add_member(DEFAULT_ROLE, "group:ops@example.com"));
+        assert!(policy.add_member("roles/secretmanager.admin", "user:bo@example.com"));
+
+        assert_eq!(policy.bindings.len(), 2);
+        assert_eq!(policy.bindings[0].role, "roles/secretmanager.admin");
+        assert_eq!(policy.bindings[1].members.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_last_member_drops_binding() {
+        let mut policy = policy();
+
+        assert!(!policy.remove_member(0, "user:nobody@example.com"));
+        assert!(policy.remove_member(0, "user:ana@example.com"));
+        assert!(policy.bindings.is_empty());
+        assert_eq!(policy.etag, b"BwX1".to_vec());
+    }
+
+    #[test]
+    fn test_public_members_are_detected() {
+        let mut policy = policy();
+        assert!(!policy.is_public());
+
+        policy.add_member(DEFAULT_ROLE, "allAuthenticatedUsers");
+        assert!(policy.is_public());
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            parse_grant("user:ana@example.com"),
+            Ok(("user:ana@example.com".to_string(), DEFAULT_ROLE.to_string()))
+        );
+        assert_eq!(
+            parse_grant("allUsers secretmanager.viewer"),
+            Ok((
+                "allUsers".to_string(),
+                "roles/secretmanager.viewer".to_string()
+            ))
+        );
+        assert!(parse_grant("ana@example.com").is_err());
+        assert!(parse_grant("user:ana@example.com a b").is_err());
+    }
+
+    #[test]
+    fn test_required_permission_depends_on_view() {
+        assert_eq!(
+            required_permission(Scope::SecretDetail, &Action::Delete),
+            Some("secretmanager.versions.destroy")
+        );
+        assert_eq!(
+            required_permission(Scope::SecretsList, &Action::Delete),
+            Some("secretmanager.secrets.delete")
+        );
+        assert_eq!(
+            required_permission(Scope::SecretDetail, &Action::Copy),
+            Some("secretmanager.versions.access")
+        );
+        assert_eq!(required_permission(Scope::SecretsList, &Action::Copy), None);
+    }
+}
@@ -0,0 +1,1104 @@
+//! Keymap: the single table of key bindings.
+//!
+//! Every binding belongs to one or more scopes (views). Key handling, the
+//! help overlay and the commands bar are all derived from this table, so
+//! what is shown is always what the keys actually do.
+
+use std::fmt;
+
+use crossterm::event::KeyCode;
+
+use crate::app::View;
+use crate::event::{Action, KeyBinding};
+
+/// Where a binding applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Authentication required view
+    AuthRequired,
+    /// Secrets list
+    SecretsList,
+    /// Secret detail / versions list
+    SecretDetail,
+    /// IAM tab of a secret
+    SecretIam,
+    /// Tags tab of a secret
+    SecretTags,
+    /// Project selector dialog
+    ProjectSelector,
+    /// gcloud configuration selector dialog
+    ConfigSelector,
+    /// Location selector dialog
+    LocationSelector,
+    /// Confirmation dialogs
+    Confirm,
+    /// Text input dialogs
+    Input,
+    /// Audit history view
+    History,
+}
+
+impl Scope {
+    /// Scopes by their config file name.
+    pub const NAMED: [(&'static str, Self); 11] = [
+        ("auth", Self::AuthRequired),
+        ("secrets", Self::SecretsList),
+        ("detail", Self::SecretDetail),
+        ("iam", Self::SecretIam),
+        ("tags", Self::SecretTags),
+        ("projects", Self::ProjectSelector),
+        ("configs", Self::ConfigSelector),
+        ("locations", Self::LocationSelector),
+        ("confirm", Self::Confirm),
+        ("input", Self::Input),
+        ("history", Self::History),
+    ];
+
+    /// Looks up a scope by its config file name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMED
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, scope)| *scope)
+    }
+
+    /// Returns the config file name of the scope.
+    pub fn name(self) -> &'static str {
+        Self::NAMED
+            .iter()
+            .find(|(_, scope)| *scope == self)
+            .map_or("", |(name, _)| name)
+    }
+
+    /// Returns true for dialogs where characters are typed, which global
+    /// overrides leave alone so that printable keys can still be typed.
+    pub fn takes_text(self) -> bool {
+        matches!(self, Self::Input | Self::Confirm)
+    }
+}
+
+impl From<&View> for Scope {
+    fn from(view: &View) -> Self {
+        match view {
+            View::AuthRequired => Self::AuthRequired,
+            View::SecretsList => Self::SecretsList,
+            View::SecretDetail => Self::SecretDetail,
+            View::SecretIam => Self::SecretIam,
+            View::SecretTags => Self::SecretTags,
+            View::ProjectSelector => Self::ProjectSelector,
+            View::ConfigSelector => Self::ConfigSelector,
+            View::LocationSelector => Self::LocationSelector,
+            View::Confirm(_) | View::Conflict(_) => Self::Confirm,
+            View::Input(_) => Self::Input,
+            View::History => Self::History,
+        }
+    }
+}
+
+/// Help overlay sections, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// Moving around lists and views
+    Navigation,
+    /// Secret-level actions
+    Secrets,
+    /// Version-level actions
+    Versions,
+    /// Everything else
+    General,
+}
+
+impl Section {
+    /// All sections in display order.
+    pub const ALL: [Self; 4] = [
+        Self::Navigation,
+        Self::Secrets,
+        Self::Versions,
+        Self::General,
+    ];
+
+    /// Section heading.
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Navigation => "NAVIGATION",
+            Self::Secrets => "SECRETS",
+            Self::Versions => "VERSIONS",
+            Self::General => "GENERAL",
+        }
+    }
+}
+
+/// One or more key presses that trigger an action, e.g. `g g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<KeyBinding>);
+
+impl KeySequence {
+    /// Parses space-separated keys, e.g. `g g`, `ctrl+r` or `f5`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let keys = text
+            .split_whitespace()
+            .map(KeyBinding::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err("empty key".to_string());
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crossterm::event::KeyModifiers;
+
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::Delete => write!(f, "Del"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Plain letter sequences read naturally without spaces ("gg")
+        let plain = self
+            .0
+            .iter()
+            .all(|k| k.modifiers.is_empty() && matches!(k.code, KeyCode::Char(c) if c != ' '));
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 && !plain {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A user override of the keys for an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyOverride {
+    /// Limit the override to one scope (`None` applies everywhere)
+    pub scope: Option<Scope>,
+    /// The action being rebound
+    pub action: Action,
+    /// The new keys
+    pub keys: Vec<KeySequence>,
+}
+
+/// A row in the keymap table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// Action triggered
+    pub action: Action,
+    /// Views in which the binding is active
+    pub scopes: Vec<Scope>,
+    /// Keys that trigger it
+    pub keys: Vec<KeySequence>,
+    /// Short label for the commands bar
+    pub hint: Option<&'static str>,
+    /// Help overlay entry
+    pub help: Option<(Section, &'static str)>,
+}
+
+/// An entry in the commands bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// Keys, with bindings that share the label joined as `e/x`
+    pub keys: String,
+    /// Short label
+    pub hint: &'static str,
+    /// Actions behind the keys
+    pub actions: Vec<Action>,
+}
+
+/// Result of looking up pressed keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lookup {
+    /// The keys complete a binding
+    Action(Action),
+    /// The keys are the start of a longer sequence
+    Pending,
+    /// Nothing is bound to the keys
+    Unbound,
+}
+
+/// The key binding table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+/// Views with a navigable list.
+const LISTS: [Scope; 8] = [
+    Scope::SecretsList,
+    Scope::SecretDetail,
+    Scope::SecretIam,
+    Scope::SecretTags,
+    Scope::ProjectSelector,
+    Scope::ConfigSelector,
+    Scope::LocationSelector,
+    Scope::History,
+];
+
+/// Main (non-dialog) views.
+const MAIN: [Scope; 4] = [
+    Scope::SecretsList,
+    Scope::SecretDetail,
+    Scope::SecretIam,
+    Scope::SecretTags,
+];
+
+/// Builds a default binding row.
+fn bind(
+    action: Action,
+    scopes: &[Scope],
+    keys: &[&str],
+    hint: Option<&'static str>,
+    help: Option<(Section, &'static str)>,
+) -> Binding {
+    Binding {
+        action,
+        scopes: scopes.to_vec(),
+        keys: keys
+            .iter()
+            .filter_map(|k| KeySequence::parse(k).ok())
+            .collect(),
+        hint,
+        help,
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Section::{General, Navigation, Secrets, Versions};
+
+        // Order matters: the commands bar lists bindings in table order and
+        // merges neighbours that share a hint ("j/k navigate").
+        let bindings = vec![
+            bind(
+                Action::Down,
+                &LISTS,
+                &["j", "down"],
+                Some("navigate"),
+                Some((Navigation, "Move to next item")),
+            ),
+            bind(
+                Action::Up,
+                &LISTS,
+                &["k", "up"],
+                Some("navigate"),
+                Some((Navigation, "Move to previous item")),
+            ),
+            bind(
+                Action::Top,
+                &LISTS,
+                &["g g", "home"],
+                None,
+                Some((Navigation, "Jump to first item")),
+            ),
+            bind(
+                Action::Bottom,
+                &LISTS,
+                &["G", "end"],
+                None,
+                Some((Navigation, "Jump to last item")),
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::SecretsList],
+                &["enter"],
+                Some("view"),
+                Some((Navigation, "Select / View details")),
+            ),
+            bind(
+                Action::ToggleSelection,
+                &[Scope::LocationSelector],
+                &["space"],
+                Some("toggle"),
+                None,
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::ProjectSelector, Scope::ConfigSelector],
+                &["enter"],
+                Some("select"),
+                None,
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::LocationSelector],
+                &["enter"],
+                Some("apply"),
+                None,
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::AuthRequired],
+                &["enter"],
+                Some("authenticate"),
+                None,
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::Confirm],
+                &["enter"],
+                Some("confirm"),
+                None,
+            ),
+            bind(
+                Action::Enter,
+                &[Scope::Input],
+                &["enter"],
+                Some("submit"),
+                None,
+            ),
+            bind(
+                Action::Back,
+                &[
+                    Scope::SecretDetail,
+                    Scope::SecretIam,
+                    Scope::SecretTags,
+                    Scope::History,
+                ],
+                &["esc", "b", "backspace"],
+                Some("back"),
+                Some((Navigation, "Go back to previous view")),
+            ),
+            bind(
+                Action::SwitchTab,
+                &[Scope::SecretDetail],
+                &["tab"],
+                Some("IAM"),
+                Some((Navigation, "Switch between versions, IAM and tags")),
+            ),
+            bind(
+                Action::SwitchTab,
+                &[Scope::SecretIam],
+                &["tab"],
+                Some("tags"),
+                None,
+            ),
+            bind(
+                Action::SwitchTab,
+                &[Scope::SecretTags],
+                &["tab"],
+                Some("versions"),
+                None,
+            ),
+            bind(
+                Action::Back,
+                &[
+                    Scope::ProjectSelector,
+                    Scope::ConfigSelector,
+                    Scope::LocationSelector,
+                ],
+                &["esc", "b", "backspace"],
+                Some("cancel"),
+                None,
+            ),
+            bind(
+                Action::Back,
+                &[Scope::Input, Scope::Confirm],
+                &["esc"],
+                Some("cancel"),
+                None,
+            ),
+            bind(
+                Action::NewSecret,
+                &[Scope::SecretsList],
+                &["n"],
+                Some("new secret"),
+                Some((Secrets, "Create a new secret")),
+            ),
+            bind(
+                Action::Delete,
+                &[Scope::SecretsList],
+                &["d"],
+                Some("delete"),
+                Some((Secrets, "Delete selected secret")),
+            ),
+            bind(
+                Action::NewVersion,
+                &[Scope::SecretDetail],
+                &["a"],
+                Some("add"),
+                Some((Versions, "Add a new version with value")),
+            ),
+            bind(
+                Action::ToggleSecretValue,
+                &[Scope::SecretDetail],
+                &["s"],
+                Some("show"),
+                Some((Versions, "Show / Hide secret value")),
+            ),
+            bind(
+                Action::Copy,
+                &[Scope::SecretDetail],
+                &["c"],
+                Some("copy"),
+                Some((Versions, "Copy secret value to clipboard")),
+            ),
+            bind(
+                Action::Enable,
+                &[Scope::SecretDetail],
+                &["e"],
+                Some("enable/disable"),
+                Some((Versions, "Enable a disabled version")),
+            ),
+            bind(
+                Action::Disable,
+                &[Scope::SecretDetail],
+                &["x"],
+                Some("enable/disable"),
+                Some((Versions, "Disable an enabled version")),
+            ),
+            bind(
+                Action::Delete,
+                &[Scope::SecretDetail],
+                &["d"],
+                Some("destroy"),
+                Some((Versions, "Destroy selected version")),
+            ),
+            bind(
+                Action::SetExpiration,
+                &[Scope::SecretsList, Scope::SecretDetail],
+                &["E"],
+                Some("expiry"),
+                Some((Secrets, "Set, extend or remove the secret's expiration")),
+            ),
+            bind(
+                Action::SetDestroyTtl,
+                &[Scope::SecretsList, Scope::SecretDetail],
+                &["T"],
+                Some("destroy TTL"),
+                Some((Secrets, "Delay the destruction of versions (destroy TTL)")),
+            ),
+            bind(
+                Action::Filter,
+                &[Scope::SecretsList, Scope::SecretDetail],
+                &["/"],
+                Some("filter"),
+                Some((
+                    Secrets,
+                    "Filter secrets or versions (API filter expression)",
+                )),
+            ),
+            bind(
+                Action::Sort,
+                &[Scope::SecretsList],
+                &["o"],
+                Some("sort"),
+                Some((Secrets, "Sort the secrets by the next column")),
+            ),
+            bind(
+                Action::ReverseSort,
+                &[Scope::SecretsList],
+                &["O"],
+                None,
+                Some((Secrets, "Reverse the sort order")),
+            ),
+            bind(
+                Action::ToggleCmekFilter,
+                &[Scope::SecretsList],
+                &["K"],
+                Some("non-CMEK"),
+                Some((Secrets, "Only list secrets without a customer-managed key")),
+            ),
+            bind(
+                Action::AddMember,
+                &[Scope::SecretIam],
+                &["a"],
+                Some("grant"),
+                Some((Secrets, "Grant a role on the secret (IAM tab)")),
+            ),
+            bind(
+                Action::Delete,
+                &[Scope::SecretIam],
+                &["d"],
+                Some("revoke"),
+                Some((Secrets, "Revoke selected member (IAM tab)")),
+            ),
+            bind(
+                Action::BindTag,
+                &[Scope::SecretTags],
+                &["a"],
+                Some("bind"),
+                Some((Secrets, "Bind a tag value to the secret (tags tab)")),
+            ),
+            bind(
+                Action::Delete,
+                &[Scope::SecretTags],
+                &["d"],
+                Some("unbind"),
+                Some((Secrets, "Unbind selected tag value (tags tab)")),
+            ),
+            bind(
+                Action::OpenProjectSelector,
+                &MAIN,
+                &["p"],
+                Some("switch project"),
+                Some((General, "Switch project")),
+            ),
+            bind(
+                Action::OpenConfigSelector,
+                &MAIN,
+                &["C"],
+                None,
+                Some((General, "Switch gcloud configuration")),
+            ),
+            bind(
+                Action::OpenLocationSelector,
+                &MAIN,
+                &["L"],
+                None,
+                Some((General, "Choose secret locations")),
+            ),
+            bind(
+                Action::History,
+                &MAIN,
+                &["h"],
+                Some("history"),
+                Some((General, "Show audit history")),
+            ),
+            bind(
+                Action::Refresh,
+                &[
+                    Scope::SecretsList,
+                    Scope::SecretDetail,
+                    Scope::SecretIam,
+                    Scope::SecretTags,
+                    Scope::History,
+                ],
+                &["r"],
+                Some("refresh"),
+                Some((General, "Refresh current view")),
+            ),
+            bind(
+                Action::Help,
+                &[
+                    Scope::AuthRequired,
+                    Scope::SecretsList,
+                    Scope::SecretDetail,
+                    Scope::SecretIam,
+                    Scope::SecretTags,
+                    Scope::ProjectSelector,
+                    Scope::ConfigSelector,
+                    Scope::LocationSelector,
+                    Scope::History,
+                ],
+                &["?", "f1"],
+                Some("help"),
+                Some((General, "Show this help")),
+            ),
+            bind(
+                Action::Quit,
+                &[
+                    Scope::AuthRequired,
+                    Scope::SecretsList,
+                    Scope::SecretDetail,
+                    Scope::SecretIam,
+                    Scope::SecretTags,
+                    Scope::ProjectSelector,
+                    Scope::ConfigSelector,
+                    Scope::LocationSelector,
+                    Scope::History,
+                ],
+                &["q", "ctrl+c"],
+                Some("quit"),
+                Some((General, "Quit application")),
+            ),
+        ];
+
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Builds the default keymap with user overrides applied.
+    ///
+    /// Overrides replace the default keys of the action; scoped overrides
+    /// only affect that one view. Global overrides do not affect the input
+    /// and confirmation dialogs, which are only rebound by scoped overrides.
+    ///
+    /// Also returns a message for each key the overrides bind to two actions
+    /// in the same view, where only the first in the table would work.
+    pub fn new(overrides: &[KeyOverride]) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        // Global overrides first so that scoped ones win
+        for o in overrides.iter().filter(|o| o.scope.is_none()) {
+            keymap.apply(o);
+        }
+        for o in overrides.iter().filter(|o| o.scope.is_some()) {
+            keymap.apply(o);
+        }
+        let errors = keymap.conflicts();
+        (keymap, errors)
+    }
+
+    /// Describes keys bound to two different actions in the same view, and
+    /// keys that are the start of a longer sequence bound to another action,
+    /// which can then never be completed.
+    ///
+    /// Views sharing a conflict are named together, so that one global
+    /// override is reported once.
+    fn conflicts(&self) -> Vec<String> {
+        let mut found: Vec<(String, &Action, &Action, Vec<&str>)> = Vec::new();
+        let mut hidden: Vec<(String, &Action, String, &Action, Vec<&str>)> = Vec::new();
+        for (name, scope) in Scope::NAMED {
+            let mut seen: Vec<(&KeySequence, &Action)> = Vec::new();
+            for binding in self.in_scope(scope) {
+                for sequence in &binding.keys {
+                    match seen.iter().find(|(s, _)| *s == sequence) {
+                        Some((_, first)) if **first != binding.action => {
+                            let key = sequence.to_string();
+                            match found.iter_mut().find(|(k, a, b, _)| {
+                                *k == key && *a == *first && **b == binding.action
+                            }) {
+                                Some((.., views)) => views.push(name),
+                                None => found.push((key, first, &binding.action, vec![name])),
+                            }
+                        }
+                        Some(_) => {}
+                        None => seen.push((sequence, &binding.action)),
+                    }
+                }
+            }
+            for (short, short_action) in &seen {
+                for (long, long_action) in &seen {
+                    if short_action == long_action
+                        || long.0.len() <= short.0.len()
+                        || !long.0.starts_with(&short.0)
+                    {
+                        continue;
+                    }
+                    let (short, long) = (short.to_string(), long.to_string());
+                    match hidden
+                        .iter_mut()
+                        .find(|(s, _, l, ..)| *s == short && *l == long)
+                    {
+                        Some((.., views)) => views.push(name),
+                        None => hidden.push((short, short_action, long, long_action, vec![name])),
+                    }
+                }
+            }
+        }
+        let mut errors: Vec<String> = found
+            .into_iter()
+            .map(|(key, first, second, views)| {
+                format!(
+                    "'{key}' is bound to both {} and {} in {}; only {} works",
+                    first.name(),
+                    second.name(),
+                    views.join(", "),
+                    first.name()
+                )
+            })
+            .collect();
+        errors.extend(
+            hidden
+                .into_iter()
+                .map(|(short, short_action, long, long_action, views)| {
+                    format!(
+                        "'{short}' ({}) is the start of '{long}' ({}) in {}; only {} works",
+                        short_action.name(),
+                        long_action.name(),
+                        views.join(", "),
+                        short_action.name()
+                    )
+                }),
+        );
+        errors
+    }
+
+    /// Applies a single override.
+    fn apply(&mut self, o: &KeyOverride) {
+        let Some(scope) = o.scope else {
+            let mut idx = 0;
+            while idx < self.bindings.len() {
+                let binding = &mut self.bindings[idx];
+                idx += 1;
+                if binding.action != o.action || binding.scopes.iter().all(|s| s.takes_text()) {
+                    continue;
+                }
+                if binding.scopes.iter().any(|s| s.takes_text()) {
+                    // Keep the dialogs of a shared row on the default keys
+                    let mut dialogs = binding.clone();
+                    dialogs.scopes.retain(|s| s.takes_text());
+                    binding.scopes.retain(|s| !s.takes_text());
+                    binding.keys.clone_from(&o.keys);
+                    self.bindings.insert(idx, dialogs);
+                    idx += 1;
+                    continue;
+                }
+                binding.keys.clone_from(&o.keys);
+            }
+            return;
+        };
+
+        let found = self
+            .bindings
+            .iter()
+            .position(|b| b.action == o.action && b.scopes.contains(&scope));
+        match found {
+            Some(idx) if self.bindings[idx].scopes.len() == 1 => {
+                self.bindings[idx].keys.clone_from(&o.keys);
+            }
+            Some(idx) => {
+                // Split the view off a shared row, keeping its place in the bar
+                self.bindings[idx].scopes.retain(|s| *s != scope);
+                let mut split = self.bindings[idx].clone();
+                split.scopes = vec![scope];
+                split.keys.clone_from(&o.keys);
+                self.bindings.insert(idx + 1, split);
+            }
+            None => self.bindings.push(Binding {
+                action: o.action.clone(),
+                scopes: vec![scope],
+                keys: o.keys.clone(),
+                hint: None,
+                help: self
+                    .bindings
+                    .iter()
+                    .find(|b| b.action == o.action)
+                    .and_then(|b| b.help),
+            }),
+        }
+    }
+
+    /// Bindings active in a scope, in table order.
+    fn in_scope(&self, scope: Scope) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |b| b.scopes.contains(&scope))
+    }
+
+    /// Looks up the keys pressed so far in a scope.
+    ///
+    /// A complete match wins over a longer sequence sharing the same prefix.
+    pub fn lookup(&self, scope: Scope, keys: &[KeyBinding]) -> Lookup {
+        let mut pending = false;
+        for binding in self.in_scope(scope) {
+            for sequence in &binding.keys {
+                if sequence.0 == keys {
+                    return Lookup::Action(binding.action.clone());
+                }
+                pending |= sequence.0.starts_with(keys);
+            }
+        }
+        if pending {
+            Lookup::Pending
+        } else {
+            Lookup::Unbound
+        }
+    }
+
+    /// Returns the first key bound to an action in a scope, for messages
+    /// that name it.
+    pub fn key(&self, scope: Scope, action: &Action) -> Option<String> {
+        self.in_scope(scope)
+            .filter(|b| b.action == *action)
+            .find_map(|b| b.keys.first())
+            .map(ToString::to_string)
+    }
+
+    /// Commands bar entries for a scope.
+    ///
+    /// Neighbouring bindings with the same hint are merged as `j/k`.
+    /// With `read_only`, actions that modify secrets are left out.
+    pub fn commands(&self, scope: Scope, read_only: bool) -> Vec<Command> {
+        let mut commands: Vec<Command> = Vec::new();
+        for binding in self.in_scope(scope) {
+            if read_only && binding.action.is_mutating() {
+                continue;
+            }
+            let (Some(hint), Some(key)) = (binding.hint, binding.keys.first()) else {
+                continue;
+            };
+            match commands.last_mut() {
+                Some(last) if last.hint == hint => {
+                    last.keys.push('/');
+                    last.keys.push_str(&key.to_string());
+                    last.actions.push(binding.action.clone());
+                }
+                _ => commands.push(Command {
+                    keys: key.to_string(),
+                    hint,
+                    actions: vec![binding.action.clone()],
+                }),
+            }
+        }
+        commands
+    }
+
+    /// Help entries for a section: (keys, description).
+    ///
+    /// Entries come from the bindings as overridden, so they always show the
+    /// keys in use. An entry whose views were rebound differently is listed
+    /// once per binding, naming the views each applies to.
+    pub fn help(&self, section: Section) -> Vec<(Vec<String>, String)> {
+        self.bindings
+            .iter()
+            .filter(|b| !b.keys.is_empty() && !b.scopes.is_empty())
+            .filter_map(|b| {
+                let (s, description) = b.help?;
+                if s != section {
+                    return None;
+                }
+                let shared = self.bindings.iter().filter(|o| o.help == b.help).count() > 1;
+                let description = if shared {
+                    let views: Vec<&str> = b.scopes.iter().map(|s| s.name()).collect();
+                    format!("{description} ({})", views.join(", "))
+                } else {
+                    description.to_string()
+                };
+                Some((
+                    b.keys.iter().map(ToString::to_string).collect(),
+                    description,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(text: &str) -> Vec<KeyBinding> {
+        KeySequence::parse(text).unwrap().0
+    }
+
+    #[test]
+    fn test_sequence_lookup() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("g")),
+            Lookup::Pending
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("g g")),
+            Lookup::Action(Action::Top)
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("z")),
+            Lookup::Unbound
+        );
+    }
+
+    #[test]
+    fn test_bindings_are_scoped() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            keymap.lookup(Scope::SecretDetail, &keys("s")),
+            Lookup::Action(Action::ToggleSecretValue)
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("s")),
+            Lookup::Unbound
+        );
+        // Typing 'b' in an input dialog must not go back
+        assert_eq!(keymap.lookup(Scope::Input, &keys("b")), Lookup::Unbound);
+    }
+
+    #[test]
+    fn test_commands_merge_shared_hints() {
+        let commands = Keymap::default().commands(Scope::SecretDetail, false);
+
+        assert_eq!(
+            (commands[0].keys.as_str(), commands[0].hint),
+            ("j/k", "navigate")
+        );
+        assert!(commands
+            .iter()
+            .any(|c| c.keys == "e/x" && c.actions == [Action::Enable, Action::Disable]));
+        assert!(commands
+            .iter()
+            .any(|c| c.keys == "d" && c.hint == "destroy"));
+    }
+
+    #[test]
+    fn test_read_only_hides_mutating_commands() {
+        let commands = Keymap::default().commands(Scope::SecretDetail, true);
+
+        assert!(commands.iter().any(|c| c.hint == "copy"));
+        assert!(!commands
+            .iter()
+            .any(|c| c.hint == "add" || c.hint == "enable/disable" || c.hint == "destroy"));
+    }
+
+    #[test]
+    fn test_scoped_override_splits_shared_binding() {
+        let (keymap, _) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretDetail),
+            action: Action::Refresh,
+            keys: vec![KeySequence::parse("f5").unwrap()],
+        }]);
+
+        assert_eq!(
+            keymap.lookup(Scope::SecretDetail, &keys("f5")),
+            Lookup::Action(Action::Refresh)
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretDetail, &keys("r")),
+            Lookup::Unbound
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("r")),
+            Lookup::Action(Action::Refresh)
+        );
+        // Help and commands bar follow the table
+        assert!(keymap
+            .commands(Scope::SecretDetail, false)
+            .iter()
+            .any(|c| c.keys == "F5" && c.hint == "refresh"));
+    }
+
+    #[test]
+    fn test_global_override_leaves_dialogs_alone() {
+        let (keymap, _) = Keymap::new(&[KeyOverride {
+            scope: None,
+            action: Action::Back,
+            keys: vec![KeySequence::parse("q").unwrap()],
+        }]);
+
+        assert_eq!(
+            keymap.lookup(Scope::SecretDetail, &keys("q")),
+            Lookup::Action(Action::Back)
+        );
+        // 'q' can still be typed, and Esc still cancels
+        assert_eq!(keymap.lookup(Scope::Input, &keys("q")), Lookup::Unbound);
+        assert_eq!(
+            keymap.lookup(Scope::Confirm, &keys("esc")),
+            Lookup::Action(Action::Back)
+        );
+    }
+
+    #[test]
+    fn test_key_follows_overrides() {
+        let (keymap, errors) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretDetail),
+            action: Action::Enable,
+            keys: vec![KeySequence::parse("ctrl+e").unwrap()],
+        }]);
+
+        assert_eq!(
+            keymap.key(Scope::SecretDetail, &Action::Enable).as_deref(),
+            Some("Ctrl+e")
+        );
+        assert_eq!(keymap.key(Scope::SecretsList, &Action::Enable), None);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_help_is_generated_from_bindings() {
+        let (keymap, _) = Keymap::new(&[KeyOverride {
+            scope: None,
+            action: Action::Quit,
+            keys: vec![KeySequence::parse("ctrl+q").unwrap()],
+        }]);
+
+        let general = keymap.help(Section::General);
+        assert!(general.contains(&(vec!["Ctrl+q".to_string()], "Quit application".to_string())));
+        let navigation = keymap.help(Section::Navigation);
+        assert!(navigation.contains(&(
+            vec!["gg".to_string(), "Home".to_string()],
+            "Jump to first item".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_help_follows_scoped_overrides() {
+        let (keymap, _) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretDetail),
+            action: Action::Refresh,
+            keys: vec![KeySequence::parse("f5").unwrap()],
+        }]);
+
+        let help = keymap.help(Section::General);
+        let refresh: Vec<_> = help
+            .iter()
+            .filter(|(_, description)| description.starts_with("Refresh"))
+            .collect();
+        assert_eq!(refresh.len(), 2, "{refresh:?}");
+        assert!(refresh
+            .iter()
+            .any(|(keys, description)| keys == &["F5"] && description.ends_with("(detail)")));
+        assert!(refresh
+            .iter()
+            .all(|(keys, description)| keys != &["r"] || !description.contains("detail")));
+    }
+
+    #[test]
+    fn test_conflicting_overrides_are_reported() {
+        assert!(Keymap::default().conflicts().is_empty());
+
+        let (keymap, errors) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretDetail),
+            action: Action::Enable,
+            keys: vec![KeySequence::parse("E").unwrap()],
+        }]);
+        assert_eq!(
+            errors,
+            vec!["'E' is bound to both enable and expiration in detail; only enable works"]
+        );
+        // The table is still built; the first binding wins as before
+        assert_eq!(
+            keymap.lookup(Scope::SecretDetail, &keys("E")),
+            Lookup::Action(Action::Enable)
+        );
+
+        // A single key shadows the longer sequence it starts
+        let (keymap, errors) = Keymap::new(&[KeyOverride {
+            scope: Some(Scope::SecretsList),
+            action: Action::Bottom,
+            keys: vec![KeySequence::parse("g").unwrap()],
+        }]);
+        assert_eq!(
+            errors,
+            vec!["'g' (bottom) is the start of 'gg' (top) in secrets; only bottom works"]
+        );
+        assert_eq!(
+            keymap.lookup(Scope::SecretsList, &keys("g")),
+            Lookup::Action(Action::Bottom)
+        );
+    }
+
+    #[test]
+    fn test_default_help_names_no_views() {
+        let keymap = Keymap::default();
+        for section in Section::ALL {
+            for (_, description) in keymap.help(section) {
+                assert!(
+                    keymap
+                        .bindings
+                        .iter()
+                        .any(|b| b.help == Some((section, description.as_str()))),
+                    "{description}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Optional file logging.
+//!
+//! When `--log-file` is given, status messages and errors are appended to
+//! that file with a timestamp. Logging is a no-op otherwise. Secret values
+//! are never passed to the logger.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+
+/// The open log file, if logging was enabled.
+static LOG_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Opens `path` for appending and enables logging.
+pub fn init(path: &Path) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+    let _ = LOG_FILE.set(Mutex::new(file));
+    info("gsmtui started");
+    Ok(())
+}
+
+/// Logs an informational message.
+pub fn info(message: &str) {
+    write("INFO", message);
+}
+
+/// Logs an error message.
+pub fn error(message: &str) {
+    write("ERROR", message);
+}
+
+/// Appends a single timestamped line to the log file.
+fn write(level: &str, message: &str) {
+    let Some(file) = LOG_FILE.get() else {
+        return;
+    };
+    if let Ok(mut file) = file.lock() {
+        let timestamp = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%z");
+        let _ = writeln!(file, "{timestamp} {level:<5} {message}");
+    }
+}
@@ -1,7 +1,8 @@
 //! Google Cloud Secret Manager TUI
 //!
 //! A terminal user interface for managing Google Cloud secrets.
-//! Run with: gsmtui [-p|--project <`PROJECT_ID`>]
+//! Run with: gsmtui [-p|--project <`PROJECT_ID`>] (see `gsmtui --help`)
+//! Backups:  gsmtui backup|restore -p <`PROJECT_ID`> <FILE>
 
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(
@@ -11,90 +12,161 @@
     clippy::unused_self
 )]
 
+mod api_error;
 mod app;
+mod audit;
+mod backup;
+mod cli;
+mod config;
+mod connection;
 mod constants;
 mod event;
+mod expiration;
+mod filters;
+mod gcloud;
+mod iam;
+mod keymap;
+mod logging;
 mod project_client;
+mod retry;
 mod secret_client;
+mod tag_client;
 mod ui;
 mod validation;
 
-use std::env;
+use std::future::Future;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use ratatui::buffer::Buffer;
 
 use crate::app::{App, AppAction, View};
-use crate::event::EventHandler;
+use crate::audit::AuditLog;
+use crate::cli::{Cli, Command};
+use crate::config::Config;
+use crate::connection::{location_label, normalize_location, ConnectionOptions};
+use crate::constants::POLL_TIMEOUT;
+use crate::event::{Action, EventHandler};
+use crate::filters::FilterHistory;
+use crate::keymap::{Keymap, Scope};
+use crate::secret_client::SecretClient;
+use crate::ui::theme::Theme;
+use crate::ui::Busy;
 
-/// Checks if GCP credentials are available.
+/// Writes an encrypted backup of every secret in the given locations.
+async fn run_backup(
+    project_id: String,
+    connection: &ConnectionOptions,
+    locations: &[Option<String>],
+    file: &Path,
+) -> Result<()> {
+    let passphrase = backup::read_passphrase(true)?;
+    let clients = secret_clients(&project_id, connection, locations).await?;
+
+    let labels: Vec<&str> = locations
+        .iter()
+        .map(|l| location_label(l.as_deref()))
+        .collect();
+    println!(
+        "Reading secrets from {project_id} ({})...",
+        labels.join(", ")
+    );
+    let data = backup::collect(&clients, &project_id).await?;
+    backup::write_file(file, &data, &passphrase)?;
+
+    let versions: usize = data.secrets.iter().map(|s| s.versions.len()).sum();
+    let skipped: usize = data.secrets.iter().map(|s| s.skipped_versions.len()).sum();
+    println!(
+        "Backed up {} secrets ({versions} versions) to {}",
+        data.secrets.len(),
+        file.display()
+    );
+    if skipped > 0 {
+        println!("Skipped {skipped} disabled or destroyed versions (payload not accessible)");
+    }
+    Ok(())
+}
+
+/// Restores missing secrets and versions from a backup into a project.
 ///
-/// Looks for:
-/// 1. `GOOGLE_APPLICATION_CREDENTIALS` environment variable pointing to a file
-/// 2. Default ADC location: ~/.`config/gcloud/application_default_credentials.json`
-fn has_gcp_credentials() -> bool {
-    // Check $GOOGLE_APPLICATION_CREDENTIALS first
-    if let Ok(path) = env::var("GOOGLE_APPLICATION_CREDENTIALS") {
-        return Path::new(&path).exists();
+/// Secrets go back to the location they were backed up from, or all into
+/// the `--location` one when `single_location` is set.
+async fn run_restore(
+    project_id: String,
+    connection: &ConnectionOptions,
+    single_location: bool,
+    file: &Path,
+    dry_run: bool,
+) -> Result<()> {
+    let passphrase = backup::read_passphrase(false)?;
+    let mut data = backup::read_file(file, &passphrase)?;
+    if single_location {
+        data.retarget(connection.location.as_deref())?;
     }
+    let clients = secret_clients(&project_id, connection, &data.locations()).await?;
 
-    // Check default ADC location
-    if let Ok(home) = env::var("HOME") {
-        let adc_path = format!("{home}/.config/gcloud/application_default_credentials.json");
-        return Path::new(&adc_path).exists();
+    println!(
+        "Backup of {} taken {} ({} secrets)",
+        data.project_id,
+        data.created_at,
+        data.secrets.len()
+    );
+
+    let steps = backup::plan_restore(&clients, &data).await?;
+    if steps.is_empty() {
+        println!("{project_id} already contains everything in the backup");
+        return Ok(());
     }
 
-    false
-}
+    if dry_run {
+        println!(
+            "Dry run - {} changes would be made to {project_id}:",
+            steps.len()
+        );
+        for step in &steps {
+            println!("  {step}");
+        }
+        return Ok(());
+    }
 
-/// Parses command line arguments.
-///
-/// Supports:
-/// - `-p <PROJECT_ID>` or `--project <PROJECT_ID>` to specify a project
-/// - `-h` or `--help` to show usage
-///
-/// Returns `Some(project_id)` if a project was specified, None otherwise.
-fn parse_args() -> Option<String> {
-    let args: Vec<String> = env::args().collect();
-
-    // Simple argument parsing using iterator
-    let mut args_iter = args.iter().skip(1); // Skip program name
-
-    #[allow(clippy::never_loop)]
-    while let Some(arg) = args_iter.next() {
-        match arg.as_str() {
-            "-p" | "--project" => {
-                // Get the next argument as the project ID
-                if let Some(project_id) = args_iter.next() {
-                    return Some(project_id.clone());
-                }
-                eprintln!("Error: --project requires a PROJECT_ID argument");
-                std::process::exit(1);
-            }
-            "-h" | "--help" => {
-                println!("gsmtui - Google Cloud Secret Manager TUI");
-                println!();
-                println!("Usage: gsmtui [OPTIONS]");
-                println!();
-                println!("Options:");
-                println!("  -p, --project <PROJECT_ID>  Start with the specified GCP project");
-                println!("  -h, --help                  Show this help message");
-                println!();
-                println!("If no project is specified, the project selector will open.");
-                println!();
-                println!("Make sure you have authenticated with:");
-                println!("  gcloud auth application-default login");
-                std::process::exit(0);
-            }
-            other => {
-                eprintln!("Error: Unknown argument '{other}'");
-                eprintln!("Use --help for usage information");
-                std::process::exit(1);
+    println!("Restoring {} changes into {project_id}:", steps.len());
+    let audit = AuditLog::new(AuditLog::default_path(), connection.principal());
+    backup::apply_restore(&clients, &data, &steps, &audit).await?;
+
+    let incomplete: Vec<String> = steps
+        .iter()
+        .filter_map(|step| match step {
+            backup::RestoreStep::CreateSecret {
+                secret,
+                not_restored,
+                ..
+            } if !not_restored.is_empty() => {
+                Some(format!("  {secret}: {}", not_restored.join(", ")))
             }
+            _ => None,
+        })
+        .collect();
+    if !incomplete.is_empty() {
+        println!("Settings not restored, set them again if needed:");
+        for line in incomplete {
+            println!("{line}");
         }
     }
+    Ok(())
+}
 
-    None
+/// Creates a Secret Manager client for each location.
+async fn secret_clients(
+    project_id: &str,
+    connection: &ConnectionOptions,
+    locations: &[Option<String>],
+) -> Result<Vec<SecretClient>> {
+    let mut clients = Vec::new();
+    for location in locations {
+        let connection = connection.at_location(location.clone());
+        clients.push(SecretClient::new(project_id.to_string(), &connection).await?);
+    }
+    Ok(clients)
 }
 
 /// Entry point for the application.
@@ -106,16 +178,88 @@ fn parse_args() -> Option<String> {
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse command line arguments
-    let project_id = parse_args();
+    let cli = Cli::parse_args();
+
+    if let Some(path) = &cli.log_file {
+        logging::init(path)?;
+    }
+    let (config, mut config_errors) = Config::load(cli.config.as_deref());
+    let gcloud_config = gcloud::active();
+    let project = cli
+        .project
+        .clone()
+        .or_else(|| cli.gcloud_project.clone())
+        .or_else(|| config.default_project.clone())
+        .or_else(|| gcloud_config.as_ref().and_then(|c| c.project.clone()));
+
+    let single_location = cli.location.is_some();
+    let connection = ConnectionOptions {
+        location: normalize_location(cli.location),
+        impersonate_service_account: cli.impersonate_service_account.clone().or_else(|| {
+            project
+                .as_deref()
+                .and_then(|p| config.impersonation_for(p))
+                .map(str::to_string)
+        }),
+        retry: config.retry.clone(),
+        quota_project: config.quota_project.clone(),
+    };
+    // --location picks a single location; otherwise the configured ones
+    let locations = if single_location || config.locations.is_empty() {
+        vec![connection.location.clone()]
+    } else {
+        config.locations.clone()
+    };
+
+    match cli.command {
+        None => {}
+        Some(Command::Completions { shell }) => {
+            cli::print_completions(shell);
+            return Ok(());
+        }
+        Some(Command::Man) => return Ok(cli::print_man_page()?),
+        Some(Command::Backup { file }) => {
+            let project_id = require_project(project, "backup")?;
+            return run_backup(project_id, &connection, &locations, &file).await;
+        }
+        Some(Command::Restore { file, dry_run }) => {
+            // Never restore into a project picked up from the config or gcloud
+            let project_id = cli
+                .project
+                .context("restore requires --project <PROJECT_ID> naming the target project")?;
+            let read_only = cli.read_only || config.is_read_only_project(&project_id);
+            if read_only && !dry_run {
+                bail!("restore modifies secrets and cannot run with --read-only (use --dry-run)");
+            }
+            return run_restore(project_id, &connection, single_location, &file, dry_run).await;
+        }
+    }
 
     // Initialize the terminal
     let terminal = ratatui::init();
 
     // Create the application (with optional project ID)
-    let app = App::new(project_id);
+    let mut app = App::new(project);
+    app.read_only = cli.read_only;
+    app.connection = connection;
+    let (keymap, keymap_errors) = Keymap::new(&config.keybindings);
+    app.keymap = keymap;
+    config_errors.extend(keymap_errors);
+    app.theme = if Theme::no_color_requested() {
+        Theme::monochrome()
+    } else {
+        config.theme
+    };
+    app.locations = locations;
+    app.config = config;
+    app.audit = AuditLog::new(AuditLog::default_path(), None);
+    app.filter_history = FilterHistory::load(FilterHistory::default_path());
+    app.impersonation_override = cli.impersonate_service_account;
+    app.gcloud_config = gcloud_config.map_or_else(gcloud::active_config_name, |c| c.name);
+    app.apply_project_settings();
 
     // Run the application
-    let result = run_app(terminal, app).await;
+    let result = run_app(terminal, app, &config_errors).await;
 
     // Restore the terminal to its original state
     ratatui::restore();
@@ -124,6 +268,13 @@ async fn main() -> Result<()> {
     result
 }
 
+/// Returns the project ID or an error naming the subcommand that needs it.
+fn require_project(project_id: Option<String>, command: &str) -> Result<String> {
+    project_id.with_context(|| {
+        format!("{command} requires --project <PROJECT_ID>, default_project or a gcloud project")
+    })
+}
+
 /// Main application loop.
 ///
 /// This function runs the TUI event loop:
@@ -131,52 +282,100 @@ async fn main() -> Result<()> {
 /// 2. Handle user input events
 /// 3. Update application state
 /// 4. Repeat until the user quits
-async fn run_app(mut terminal: ratatui::DefaultTerminal, mut app: App) -> Result<()> {
-    // Check credentials before loading anything
-    if has_gcp_credentials() {
-        // Load initial data based on starting view
-        match app.current_view {
-            View::SecretsList => {
-                // Project was provided, load secrets
-                app.load_secrets().await?;
-            }
-            View::ProjectSelector => {
-                // No project provided, load projects for selection
-                app.load_projects().await?;
-            }
-            _ => {}
+async fn run_app(
+    mut terminal: ratatui::DefaultTerminal,
+    mut app: App,
+    config_errors: &[String],
+) -> Result<()> {
+    // Create the event handler
+    let mut event_handler = EventHandler::new();
+
+    // Load initial data based on starting view. Credentials are checked
+    // first and any problem switches to the auth screen.
+    if matches!(app.current_view, View::SecretsList | View::ProjectSelector) {
+        let snapshot = terminal
+            .draw(|frame| ui::draw(frame, &app))
+            .context("Failed to draw UI")?
+            .buffer
+            .clone();
+        let scope = Scope::from(&app.current_view);
+        let keymap = app.keymap.clone();
+        let busy = Busy {
+            theme: app.theme,
+            cancel_key: keymap.key(scope, &Action::Back),
+            progress: None,
+            ticks: 0,
+        };
+        let mut screen = BusyScreen {
+            terminal: &mut terminal,
+            events: &mut event_handler,
+            keymap: &keymap,
+            scope,
+            snapshot: &snapshot,
+        };
+        let loaded = if app.current_view == View::SecretsList {
+            screen.run(busy, app.load_secrets()).await?
+        } else {
+            screen.run(busy, app.load_projects()).await?
+        };
+        if loaded.is_none() {
+            app.on_cancelled();
         }
-    } else {
-        app.current_view = View::AuthRequired;
     }
 
-    // Create the event handler
-    let event_handler = EventHandler::new();
+    // Surface config problems once the initial load has settled
+    app.report_config_errors(config_errors);
 
     // Main loop
     loop {
-        // Draw the UI
-        terminal
+        // Draw the UI, keeping the frame to show while a call runs
+        let snapshot = terminal
             .draw(|frame| ui::draw(frame, &app))
-            .context("Failed to draw UI")?;
+            .context("Failed to draw UI")?
+            .buffer
+            .clone();
 
         // Use different event handling for input mode vs normal mode
-        let event = if matches!(app.current_view, View::Input(_)) {
-            event_handler.next_input()?
+        let scope = Scope::from(&app.current_view);
+        let event = if matches!(app.current_view, View::Input(_) | View::Confirm(_)) {
+            event_handler.next_input(&app.keymap, scope)?
         } else {
-            event_handler.next()?
+            event_handler.next(&app.keymap, scope)?
         };
 
+        // Expire revealed values and copied secrets
+        app.on_tick();
+
         // Handle events (keyboard input, etc.)
         if let Some(action) = event {
+            let keymap = app.keymap.clone();
+            let busy = Busy {
+                theme: app.theme,
+                cancel_key: if app.can_cancel(&action) {
+                    keymap.key(scope, &Action::Back)
+                } else {
+                    None
+                },
+                progress: None,
+                ticks: 0,
+            };
+            let mut screen = BusyScreen {
+                terminal: &mut terminal,
+                events: &mut event_handler,
+                keymap: &keymap,
+                scope,
+                snapshot: &snapshot,
+            };
+
             // Process the event and check what action is needed
-            match app.handle_event(action).await? {
-                Some(AppAction::Quit) => break,
-                Some(AppAction::RunGcloudAuth) => {
+            match screen.run(busy, app.handle_event(action)).await? {
+                Some(Some(AppAction::Quit)) => break,
+                Some(Some(AppAction::RunGcloudAuth)) => {
                     drop(terminal);
                     terminal = run_gcloud_auth(&mut app).await?;
                 }
-                None => {}
+                Some(None) => {}
+                None => app.on_cancelled(),
             }
         }
     }
@@ -184,6 +383,59 @@ async fn run_app(mut terminal: ratatui::DefaultTerminal, mut app: App) -> Result
     Ok(())
 }
 
+/// The screen while an API call runs.
+///
+/// Calls borrow the app, so instead of drawing the views the screen shows
+/// `snapshot`, the last frame drawn before the call, with a spinner and the
+/// latest retry in the status bar.
+struct BusyScreen<'a> {
+    terminal: &'a mut ratatui::DefaultTerminal,
+    events: &'a mut EventHandler,
+    keymap: &'a Keymap,
+    scope: Scope,
+    snapshot: &'a Buffer,
+}
+
+impl BusyScreen<'_> {
+    /// Runs `call` while redrawing the screen, returning its result.
+    ///
+    /// Calls that finish within one poll interval never show the busy
+    /// status. When `busy` has a cancel key, pressing it drops the call and
+    /// returns `None`.
+    async fn run<T>(
+        &mut self,
+        mut busy: Busy,
+        call: impl Future<Output = Result<T>>,
+    ) -> Result<Option<T>> {
+        tokio::pin!(call);
+        let mut ticker =
+            tokio::time::interval_at(tokio::time::Instant::now() + POLL_TIMEOUT, POLL_TIMEOUT);
+
+        loop {
+            tokio::select! {
+                biased;
+                result = &mut call => {
+                    retry::take_progress();
+                    return result.map(Some);
+                }
+                _ = ticker.tick() => {}
+            }
+
+            if self.events.cancel_requested(self.keymap, self.scope)? && busy.cancel_key.is_some() {
+                retry::take_progress();
+                return Ok(None);
+            }
+            if let Some(progress) = retry::take_progress() {
+                busy.progress = Some(progress);
+            }
+            busy.ticks += 1;
+            self.terminal
+                .draw(|frame| ui::draw_busy(frame, self.snapshot, &busy))
+                .context("Failed to draw UI")?;
+        }
+    }
+}
+
 /// Runs gcloud auth with proper terminal management.
 ///
 /// This function:
@@ -3,9 +3,15 @@
 //! This module provides a simple interface to list GCP projects
 //! accessible to the authenticated user.
 
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use anyhow::{Context, Result};
+use google_cloud_gax::retry_policy::NeverRetry;
 use google_cloud_resourcemanager_v3::client::Projects;
 
+use crate::connection::ConnectionOptions;
+use crate::retry::{Idempotency, RetryPolicy};
+
 /// Information about a GCP project (simplified view).
 #[derive(Debug, Clone)]
 pub struct ProjectInfo {
@@ -15,38 +21,73 @@ pub struct ProjectInfo {
     pub display_name: String,
 }
 
-/// Fetches the list of projects accessible to the current user.
-///
-/// This uses Application Default Credentials (ADC) for authentication.
-/// Make sure you have run: gcloud auth application-default login
-pub async fn list_projects() -> Result<Vec<ProjectInfo>> {
-    // Create the Resource Manager client
-    let client = Projects::builder()
-        .build()
-        .await
-        .context("Failed to create Resource Manager client")?;
-
-    // Search for all projects the user has access to
-    // An empty query returns all accessible projects
-    let response = client
-        .search_projects()
-        .send()
-        .await
-        .context("Failed to list projects")?;
-
-    // Convert to our simplified format
-    let projects: Vec<ProjectInfo> = response
-        .projects
-        .into_iter()
-        .map(|p| ProjectInfo {
-            project_id: p.project_id.clone(),
-            display_name: if p.display_name.is_empty() {
-                p.project_id
-            } else {
-                p.display_name
-            },
+/// Wrapper around the Resource Manager projects client.
+pub struct ProjectClient {
+    /// The underlying Google Cloud client
+    client: Projects,
+    /// Timeout and retry settings
+    retry: RetryPolicy,
+    /// Retries made by calls
+    retries: AtomicU32,
+}
+
+impl ProjectClient {
+    /// Creates a client using Application Default Credentials (ADC),
+    /// impersonating a service account if `connection` asks for it.
+    /// Make sure you have run: gcloud auth application-default login
+    pub async fn new(connection: &ConnectionOptions) -> Result<Self> {
+        let mut builder = Projects::builder().with_retry_policy(NeverRetry);
+        if let Some(credentials) = connection.credentials()? {
+            builder = builder.with_credentials(credentials);
+        }
+        let client = builder
+            .build()
+            .await
+            .context("Failed to create Resource Manager client")?;
+
+        Ok(Self {
+            client,
+            retry: connection.retry.clone(),
+            retries: AtomicU32::new(0),
         })
-        .collect();
+    }
+
+    /// Returns how many retries were made since the last call, resetting the count.
+    pub fn take_retries(&self) -> u32 {
+        self.retries.swap(0, Ordering::Relaxed)
+    }
+
+    /// Fetches the list of projects accessible to the current user.
+    pub async fn list_projects(&self) -> Result<Vec<ProjectInfo>> {
+        let mut projects = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            // An empty query returns all accessible projects
+            let page = self
+                .retry
+                .run(Idempotency::Idempotent, &self.retries, || async {
+                    self.client
+                        .search_projects()
+                        .set_page_token(&page_token)
+                        .send()
+                        .await
+                        .context("Failed to list projects")
+                })
+                .await?;
 
-    Ok(projects)
+            // Convert to our simplified format
+            projects.extend(page.projects.into_iter().map(|p| ProjectInfo {
+                project_id: p.project_id.clone(),
+                display_name: if p.display_name.is_empty() {
+                    p.project_id
+                } else {
+                    p.display_name
+                },
+            }));
+            if page.next_page_token.is_empty() {
+                return Ok(projects);
+            }
+            page_token = page.next_page_token;
+        }
+    }
 }
@@ -0,0 +1,224 @@
+//! Request timeouts and retries for transient API failures.
+//!
+//! Every request gets a per-attempt timeout; listings run one request per
+//! page, so a long listing is not cut off by the timeout of a single page.
+//! Idempotent calls (list, get, access) are retried with exponential backoff
+//! and full jitter when the service is unavailable, times out or is rate
+//! limiting us; calls with side effects such as adding a version are never
+//! retried.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::api_error::{ApiError, ErrorKind};
+use crate::logging;
+
+/// The latest retry of the running call, shown while the call is in flight.
+static PROGRESS: Mutex<Option<String>> = Mutex::new(None);
+
+/// Returns the message about the latest retry, if any, and clears it.
+pub fn take_progress() -> Option<String> {
+    PROGRESS
+        .lock()
+        .ok()
+        .and_then(|mut progress| progress.take())
+}
+
+/// Whether a call may safely be repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// Reads; retried on transient failures
+    Idempotent,
+    /// Writes; attempted once
+    NonIdempotent,
+}
+
+/// Timeout and backoff settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Time allowed for a single attempt
+    pub timeout: Duration,
+    /// Total attempts for idempotent calls, including the first
+    pub max_attempts: u32,
+    /// Backoff ceiling before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the backoff ceiling
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the backoff ceiling before retry number `retry` (starting at 1).
+    pub fn backoff_ceiling(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Runs `call`, retrying transient failures of idempotent calls.
+    ///
+    /// Each retry increments `retries` so the caller can report it.
+    pub async fn run<T, F, Fut>(
+        &self,
+        idempotency: Idempotency,
+        retries: &AtomicU32,
+        mut call: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_attempts = match idempotency {
+            Idempotency::Idempotent => self.max_attempts.max(1),
+            Idempotency::NonIdempotent => 1,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = match tokio::time::timeout(self.timeout, call()).await {
+                Ok(result) => result,
+                Err(elapsed) => Err(anyhow!(elapsed).context(format!(
+                    "Request timed out after {}s",
+                    self.timeout.as_secs()
+                ))),
+            };
+            let err = match result {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            if attempt >= max_attempts || !is_transient(&err) {
+                return Err(err);
+            }
+
+            let delay = jitter(self.backoff_ceiling(attempt));
+            let message = format!(
+                "Retrying (attempt {}/{max_attempts}) in {}ms: {}",
+                attempt + 1,
+                delay.as_millis(),
+                ApiError::classify(&err)
+            );
+            logging::info(&message);
+            if let Ok(mut progress) = PROGRESS.lock() {
+                *progress = Some(message);
+            }
+            retries.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Returns true for failures worth retrying.
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    matches!(
+        ApiError::classify(err).kind,
+        ErrorKind::Network | ErrorKind::Quota
+    )
+}
+
+/// Picks a random duration in `[0, ceiling]` ("full jitter").
+fn jitter(ceiling: Duration) -> Duration {
+    let mut bytes = [0u8; 4];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        return ceiling;
+    }
+    let fraction = f64::from(u32::from_le_bytes(bytes)) / f64::from(u32::MAX);
+    ceiling.mul_f64(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use google_cloud_gax::error::rpc::{Code, Status};
+    use google_cloud_gax::error::Error as GaxError;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            timeout: Duration::from_millis(50),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+        }
+    }
+
+    fn service_error(code: Code) -> anyhow::Error {
+        anyhow!(GaxError::service(Status::default().set_code(code)))
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff_ceiling(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff_ceiling(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff_ceiling(5), Duration::from_secs(8));
+        assert_eq!(policy.backoff_ceiling(40), Duration::from_secs(8));
+        assert!(jitter(Duration::from_secs(1)) <= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_idempotent_calls() {
+        let retries = AtomicU32::new(0);
+        let calls = AtomicU32::new(0);
+        let result = fast_policy()
+            .run(Idempotency::Idempotent, &retries, || async {
+                if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+                    Err(service_error(Code::Unavailable))
+                } else {
+                    Ok(7)
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(retries.load(Ordering::Relaxed), 2);
+        assert!(take_progress().is_some_and(|p| p.starts_with("Retrying (attempt")));
+    }
+
+    #[tokio::test]
+    async fn test_never_retries_writes_or_permanent_errors() {
+        let retries = AtomicU32::new(0);
+        let result: Result<()> = fast_policy()
+            .run(Idempotency::NonIdempotent, &retries, || async {
+                Err(service_error(Code::Unavailable))
+            })
+            .await;
+        assert!(result.is_err());
+
+        let result: Result<()> = fast_policy()
+            .run(Idempotency::Idempotent, &retries, || async {
+                Err(service_error(Code::PermissionDenied))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(retries.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_and_gives_up() {
+        let retries = AtomicU32::new(0);
+        let result: Result<()> = fast_policy()
+            .run(Idempotency::Idempotent, &retries, || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .await;
+        let err = result.unwrap_err();
+        assert_eq!(ApiError::classify(&err).kind, ErrorKind::Network);
+        assert_eq!(retries.load(Ordering::Relaxed), 2);
+    }
+}
@@ -3,15 +3,26 @@
 //! This module provides a simplified interface to the Secret Manager API.
 //! It wraps the official Google Cloud Rust SDK.
 
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use google_cloud_gax::retry_policy::NeverRetry;
+use google_cloud_iam_v1::model::{Binding, GetPolicyOptions, Policy};
 use google_cloud_secretmanager_v1::client::SecretManagerService;
 use google_cloud_secretmanager_v1::model::{
-    replication, secret_version, Replication, Secret, SecretPayload, SecretVersion,
+    replication, replication_status, secret_version, CustomerManagedEncryption, Replication,
+    Secret, SecretPayload, SecretVersion,
 };
+use google_cloud_wkt::{Duration, FieldMask, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use crate::connection::ConnectionOptions;
+use crate::iam::{IamBinding, IamCondition, IamPolicy, CONDITIONAL_POLICY_VERSION};
+use crate::retry::{Idempotency, RetryPolicy};
 
 /// Replication policy for a secret.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReplicationPolicy {
     /// Google manages replication automatically
     Automatic,
@@ -19,8 +30,17 @@ pub enum ReplicationPolicy {
     UserManaged(Vec<String>),
 }
 
+/// How one replica of a secret is encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicaEncryption {
+    /// Replica location, the region of a regional secret, or `automatic`
+    pub location: String,
+    /// Cloud KMS key protecting the replica (`None` when Google-managed)
+    pub kms_key: Option<String>,
+}
+
 /// Rotation configuration for a secret.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RotationConfig {
     /// Rotation period (e.g., "86400s" for 1 day)
     pub rotation_period: Option<String>,
@@ -29,7 +49,7 @@ pub struct RotationConfig {
 }
 
 /// Information about a secret (simplified view).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecretInfo {
     /// Short name (just the secret name without the full path)
     pub short_name: String,
@@ -49,6 +69,29 @@ pub struct SecretInfo {
     pub rotation: Option<RotationConfig>,
     /// Version destroy TTL (delayed destruction)
     pub version_destroy_ttl: Option<String>,
+    /// Region of a regional secret (`None` for global secrets)
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Etag of the secret as read, sent back with writes to it
+    #[serde(default, skip_serializing)]
+    pub etag: String,
+    /// When the secret is deleted automatically (Unix seconds)
+    #[serde(default)]
+    pub expire_time: Option<i64>,
+    /// Encryption of each replica
+    #[serde(default)]
+    pub encryption: Vec<ReplicaEncryption>,
+    /// Summary of the versions, loaded only for the list columns showing it
+    #[serde(skip)]
+    pub versions: Option<VersionSummary>,
+}
+
+impl SecretInfo {
+    /// Returns true if every replica is protected by a customer-managed
+    /// encryption key (CMEK).
+    pub fn is_cmek(&self) -> bool {
+        !self.encryption.is_empty() && self.encryption.iter().all(|r| r.kms_key.is_some())
+    }
 }
 
 /// The state of a secret version.
@@ -75,6 +118,28 @@ impl std::fmt::Display for VersionState {
     }
 }
 
+/// Version numbers and states of a secret, shown in the secrets list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VersionSummary {
+    /// Highest version number, in any state
+    pub latest: Option<i64>,
+    /// Number of enabled versions
+    pub enabled: usize,
+}
+
+impl VersionSummary {
+    /// Summarizes the versions of a secret.
+    pub fn from_versions(versions: &[VersionInfo]) -> Self {
+        Self {
+            latest: versions.iter().filter_map(|v| v.version.parse().ok()).max(),
+            enabled: versions
+                .iter()
+                .filter(|v| v.state == VersionState::Enabled)
+                .count(),
+        }
+    }
+}
+
 /// Information about a secret version.
 #[derive(Debug, Clone)]
 pub struct VersionInfo {
@@ -86,10 +151,14 @@ pub struct VersionInfo {
     pub create_time: String,
     /// Time when the version was destroyed (if applicable)
     pub destroy_time: Option<String>,
-    /// Scheduled destruction time (for delayed destroy)
-    pub scheduled_destroy_time: Option<String>,
+    /// When a version scheduled for destruction is destroyed (Unix seconds)
+    pub scheduled_destroy_time: Option<i64>,
     /// Whether a client-specified checksum was provided
     pub has_checksum: bool,
+    /// Cloud KMS key versions the payload is encrypted with (CMEK only)
+    pub kms_key_versions: Vec<String>,
+    /// Etag of the version as read, sent back with writes to it
+    pub etag: String,
 }
 
 /// Wrapper around the Google Cloud Secret Manager client.
@@ -98,67 +167,184 @@ pub struct SecretClient {
     client: SecretManagerService,
     /// The Google Cloud project ID
     project_id: String,
+    /// Location for regional secrets (`None` for global)
+    location: Option<String>,
+    /// Timeouts and retries applied to every call
+    retry: RetryPolicy,
+    /// Retries made since last taken with `take_retries`
+    retries: AtomicU32,
 }
 
 impl SecretClient {
     /// Creates a new Secret Manager client for the given project.
     ///
-    /// This will use Application Default Credentials (ADC) for authentication.
+    /// This will use Application Default Credentials (ADC) for authentication,
+    /// optionally impersonating a service account and targeting a regional
+    /// endpoint as described by `connection`.
     /// Make sure you have run: gcloud auth application-default login
-    pub async fn new(project_id: String) -> Result<Self> {
-        let client = SecretManagerService::builder()
+    pub async fn new(project_id: String, connection: &ConnectionOptions) -> Result<Self> {
+        // Retries are handled by `RetryPolicy` so they can be reported
+        let mut builder = SecretManagerService::builder().with_retry_policy(NeverRetry);
+        if let Some(endpoint) = connection.secret_manager_endpoint() {
+            builder = builder.with_endpoint(endpoint);
+        }
+        if let Some(credentials) = connection.credentials()? {
+            builder = builder.with_credentials(credentials);
+        }
+
+        let client = builder
             .build()
             .await
             .context("Failed to create Secret Manager client. Make sure you have authenticated with: gcloud auth application-default login")?;
 
-        Ok(Self { client, project_id })
+        Ok(Self {
+            client,
+            project_id,
+            location: connection.location.clone(),
+            retry: connection.retry.clone(),
+            retries: AtomicU32::new(0),
+        })
     }
 
     /// Returns the parent path for API calls.
     fn parent(&self) -> String {
-        format!("projects/{}", self.project_id)
+        match &self.location {
+            Some(location) => format!("projects/{}/locations/{location}", self.project_id),
+            None => format!("projects/{}", self.project_id),
+        }
     }
 
-    /// Lists all secrets in the project.
-    pub async fn list_secrets(&self) -> Result<Vec<SecretInfo>> {
-        // Use the paginated list_secrets API
-        let response = self
-            .client
-            .list_secrets()
-            .set_parent(self.parent())
-            .send()
-            .await
-            .context("Failed to list secrets")?;
-
-        // Convert secrets to our simplified format
-        let secrets = response
-            .secrets
-            .into_iter()
-            .map(|s| self.secret_to_info(&s))
-            .collect();
+    /// Lists all secrets in the project, or those matching `filter`.
+    ///
+    /// Follows page tokens so projects with more secrets than a single
+    /// page holds are listed completely. Each page is its own request, so the
+    /// timeout and retries apply per page. The filter is evaluated by the API,
+    /// e.g. `labels.team=payments AND create_time>2024-01-01`.
+    pub async fn list_secrets(&self, filter: Option<&str>) -> Result<Vec<SecretInfo>> {
+        let parent = self.parent();
+
+        let mut secrets = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let page = self
+                .call(Idempotency::Idempotent, || async {
+                    self.client
+                        .list_secrets()
+                        .set_parent(&parent)
+                        .set_filter(filter.unwrap_or_default())
+                        .set_page_token(&page_token)
+                        .send()
+                        .await
+                        .context("Failed to list secrets")
+                })
+                .await?;
+
+            // Convert secrets to our simplified format
+            secrets.extend(
+                page.secrets
+                    .iter()
+                    .map(|secret| self.secret_to_info(secret)),
+            );
+            if page.next_page_token.is_empty() {
+                return Ok(secrets);
+            }
+            page_token = page.next_page_token;
+        }
+    }
 
-        Ok(secrets)
+    /// Lists the regions offering regional secrets for the project.
+    pub async fn list_locations(&self) -> Result<Vec<String>> {
+        let name = format!("projects/{}", self.project_id);
+
+        let mut locations = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let page = self
+                .call(Idempotency::Idempotent, || async {
+                    self.client
+                        .list_locations()
+                        .set_name(&name)
+                        .set_page_token(&page_token)
+                        .send()
+                        .await
+                        .context("Failed to list locations")
+                })
+                .await?;
+
+            locations.extend(
+                page.locations
+                    .into_iter()
+                    .map(|location| location.location_id),
+            );
+            if page.next_page_token.is_empty() {
+                return Ok(locations);
+            }
+            page_token = page.next_page_token;
+        }
     }
 
-    /// Lists all versions of a secret.
-    pub async fn list_versions(&self, secret_name: &str) -> Result<Vec<VersionInfo>> {
+    /// Lists all versions of a secret, or those matching `filter` (e.g.
+    /// `state:ENABLED`).
+    pub async fn list_versions(
+        &self,
+        secret_name: &str,
+        filter: Option<&str>,
+    ) -> Result<Vec<VersionInfo>> {
         let parent = self.secret_path(secret_name);
 
+        let mut versions = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let page = self
+                .call(Idempotency::Idempotent, || async {
+                    self.client
+                        .list_secret_versions()
+                        .set_parent(&parent)
+                        .set_filter(filter.unwrap_or_default())
+                        .set_page_token(&page_token)
+                        .send()
+                        .await
+                        .context("Failed to list versions")
+                })
+                .await?;
+
+            versions.extend(
+                page.versions
+                    .iter()
+                    .map(|version| self.version_to_info(version)),
+            );
+            if page.next_page_token.is_empty() {
+                return Ok(versions);
+            }
+            page_token = page.next_page_token;
+        }
+    }
+
+    /// Lists the versions of a secret and summarizes them.
+    pub async fn version_summary(&self, secret_name: &str) -> Result<VersionSummary> {
+        let versions = self.list_versions(secret_name, None).await?;
+        Ok(VersionSummary::from_versions(&versions))
+    }
+
+    /// Gets the raw payload bytes of a secret version.
+    pub async fn access_version_bytes(&self, secret_name: &str, version: &str) -> Result<Vec<u8>> {
+        let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
+
         let response = self
-            .client
-            .list_secret_versions()
-            .set_parent(&parent)
-            .send()
-            .await
-            .context("Failed to list versions")?;
+            .call(Idempotency::Idempotent, || async {
+                self.client
+                    .access_secret_version()
+                    .set_name(&name)
+                    .send()
+                    .await
+                    .context("Failed to access secret version")
+            })
+            .await?;
 
-        let versions = response
-            .versions
-            .into_iter()
-            .map(|v| self.version_to_info(&v))
-            .collect();
+        // Extract the payload data
+        let payload = response.payload.context("Secret version has no payload")?;
 
-        Ok(versions)
+        Ok(payload.data.into())
     }
 
     /// Gets the actual value of a secret version.
@@ -166,21 +352,9 @@ impl SecretClient {
     /// Returns the secret data as a string. If the data is not valid UTF-8,
     /// it returns a base64-encoded representation with a prefix indicator.
     pub async fn access_version(&self, secret_name: &str, version: &str) -> Result<String> {
-        let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
-
-        let response = self
-            .client
-            .access_secret_version()
-            .set_name(&name)
-            .send()
-            .await
-            .context("Failed to access secret version")?;
-
-        // Extract the payload data
-        let payload = response.payload.context("Secret version has no payload")?;
+        let data = self.access_version_bytes(secret_name, version).await?;
 
         // Try UTF-8 first, fall back to base64 for binary data
-        let data: Vec<u8> = payload.data.into();
         if let Ok(value) = String::from_utf8(data.clone()) {
             Ok(value)
         } else {
@@ -191,109 +365,409 @@ impl SecretClient {
         }
     }
 
+    /// Gets a secret as currently stored.
+    pub async fn get_secret(&self, secret_name: &str) -> Result<SecretInfo> {
+        let name = self.secret_path(secret_name);
+
+        let secret = self
+            .call(Idempotency::Idempotent, || async {
+                self.client
+                    .get_secret()
+                    .set_name(&name)
+                    .send()
+                    .await
+                    .context("Failed to get secret")
+            })
+            .await?;
+
+        Ok(self.secret_to_info(&secret))
+    }
+
+    /// Gets a secret version as currently stored.
+    pub async fn get_version(&self, secret_name: &str, version: &str) -> Result<VersionInfo> {
+        let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
+
+        let result = self
+            .call(Idempotency::Idempotent, || async {
+                self.client
+                    .get_secret_version()
+                    .set_name(&name)
+                    .send()
+                    .await
+                    .context("Failed to get secret version")
+            })
+            .await?;
+
+        Ok(self.version_to_info(&result))
+    }
+
     /// Creates a new secret (without any version/value).
     pub async fn create_secret(&self, secret_name: &str) -> Result<SecretInfo> {
         // Set up automatic replication (Google manages the replication)
-        let replication = Replication::default()
-            .set_automatic(google_cloud_secretmanager_v1::model::replication::Automatic::default());
+        let replication = Replication::default().set_automatic(replication::Automatic::default());
 
-        let secret = Secret::default().set_replication(replication);
+        let secret = new_secret(self.location.as_deref(), replication);
 
         let created = self
-            .client
-            .create_secret()
-            .set_parent(self.parent())
-            .set_secret_id(secret_name)
-            .set_secret(secret)
-            .send()
-            .await
-            .context("Failed to create secret")?;
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .create_secret()
+                    .set_parent(self.parent())
+                    .set_secret_id(secret_name)
+                    .set_secret(secret.clone())
+                    .send()
+                    .await
+                    .context("Failed to create secret")
+            })
+            .await?;
+
+        Ok(self.secret_to_info(&created))
+    }
+
+    /// Creates a new secret carrying over the metadata of an existing one.
+    ///
+    /// Labels, annotations and the replication policy are copied. Topics and
+    /// rotation are not, since they reference resources that may not exist
+    /// in this project. Secrets restored to a region take no replication
+    /// policy.
+    pub async fn create_secret_from_info(&self, info: &SecretInfo) -> Result<SecretInfo> {
+        let replication = match &info.replication {
+            ReplicationPolicy::UserManaged(locations) if !locations.is_empty() => {
+                let replicas = locations.iter().map(|location| {
+                    replication::user_managed::Replica::default().set_location(location)
+                });
+                Replication::default()
+                    .set_user_managed(replication::UserManaged::default().set_replicas(replicas))
+            }
+            _ => Replication::default().set_automatic(replication::Automatic::default()),
+        };
+
+        let secret = new_secret(self.location.as_deref(), replication)
+            .set_labels(info.labels.iter().cloned())
+            .set_annotations(info.annotations.iter().cloned());
+
+        let created = self
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .create_secret()
+                    .set_parent(self.parent())
+                    .set_secret_id(&info.short_name)
+                    .set_secret(secret.clone())
+                    .send()
+                    .await
+                    .context("Failed to create secret")
+            })
+            .await?;
 
         Ok(self.secret_to_info(&created))
     }
 
     /// Adds a new version to an existing secret.
     pub async fn add_version(&self, secret_name: &str, value: &str) -> Result<VersionInfo> {
+        self.add_version_bytes(secret_name, value.as_bytes()).await
+    }
+
+    /// Adds a new version with a raw byte payload to an existing secret.
+    pub async fn add_version_bytes(&self, secret_name: &str, data: &[u8]) -> Result<VersionInfo> {
         let parent = self.secret_path(secret_name);
 
-        let payload = SecretPayload::default().set_data(value.as_bytes().to_vec());
+        let payload = SecretPayload::default().set_data(data.to_vec());
 
         let version = self
-            .client
-            .add_secret_version()
-            .set_parent(&parent)
-            .set_payload(payload)
-            .send()
-            .await
-            .context("Failed to add secret version")?;
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .add_secret_version()
+                    .set_parent(&parent)
+                    .set_payload(payload.clone())
+                    .send()
+                    .await
+                    .context("Failed to add secret version")
+            })
+            .await?;
 
         Ok(self.version_to_info(&version))
     }
 
     /// Enables a disabled secret version.
-    pub async fn enable_version(&self, secret_name: &str, version: &str) -> Result<VersionInfo> {
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the version changed since `etag` was read.
+    pub async fn enable_version(
+        &self,
+        secret_name: &str,
+        version: &str,
+        etag: &str,
+    ) -> Result<VersionInfo> {
         let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
 
         let result = self
-            .client
-            .enable_secret_version()
-            .set_name(&name)
-            .send()
-            .await
-            .context("Failed to enable secret version")?;
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .enable_secret_version()
+                    .set_name(&name)
+                    .set_etag(etag)
+                    .send()
+                    .await
+                    .context("Failed to enable secret version")
+            })
+            .await?;
 
         Ok(self.version_to_info(&result))
     }
 
     /// Disables an enabled secret version.
-    pub async fn disable_version(&self, secret_name: &str, version: &str) -> Result<VersionInfo> {
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the version changed since `etag` was read.
+    pub async fn disable_version(
+        &self,
+        secret_name: &str,
+        version: &str,
+        etag: &str,
+    ) -> Result<VersionInfo> {
         let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
 
         let result = self
-            .client
-            .disable_secret_version()
-            .set_name(&name)
-            .send()
-            .await
-            .context("Failed to disable secret version")?;
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .disable_secret_version()
+                    .set_name(&name)
+                    .set_etag(etag)
+                    .send()
+                    .await
+                    .context("Failed to disable secret version")
+            })
+            .await?;
 
         Ok(self.version_to_info(&result))
     }
 
     /// Destroys a secret version (irreversible!).
-    pub async fn destroy_version(&self, secret_name: &str, version: &str) -> Result<VersionInfo> {
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the version changed since `etag` was read.
+    pub async fn destroy_version(
+        &self,
+        secret_name: &str,
+        version: &str,
+        etag: &str,
+    ) -> Result<VersionInfo> {
         let name = format!("{}/versions/{}", self.secret_path(secret_name), version);
 
         let result = self
-            .client
-            .destroy_secret_version()
-            .set_name(&name)
-            .send()
-            .await
-            .context("Failed to destroy secret version")?;
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .destroy_secret_version()
+                    .set_name(&name)
+                    .set_etag(etag)
+                    .send()
+                    .await
+                    .context("Failed to destroy secret version")
+            })
+            .await?;
 
         Ok(self.version_to_info(&result))
     }
 
+    /// Sets the time a secret expires (Unix seconds), or removes it.
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the secret changed since `etag` was read.
+    /// Returns the secret as stored.
+    pub async fn update_expiration(
+        &self,
+        secret_name: &str,
+        expire_time: Option<i64>,
+        etag: &str,
+    ) -> Result<SecretInfo> {
+        let mut secret = Secret::default();
+        if let Some(expire_time) = expire_time {
+            secret = secret.set_expire_time(Timestamp::clamp(expire_time, 0));
+        }
+        self.update_secret(
+            secret_name,
+            secret,
+            "expire_time",
+            etag,
+            "Failed to update secret expiration",
+        )
+        .await
+    }
+
+    /// Sets how long destroyed versions stay disabled before they are
+    /// destroyed (seconds), or removes the delay.
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the secret changed since `etag` was read.
+    /// Returns the secret as stored.
+    pub async fn update_version_destroy_ttl(
+        &self,
+        secret_name: &str,
+        ttl: Option<i64>,
+        etag: &str,
+    ) -> Result<SecretInfo> {
+        let mut secret = Secret::default();
+        if let Some(ttl) = ttl {
+            secret = secret.set_version_destroy_ttl(Duration::clamp(ttl, 0));
+        }
+        self.update_secret(
+            secret_name,
+            secret,
+            "version_destroy_ttl",
+            etag,
+            "Failed to update version destroy TTL",
+        )
+        .await
+    }
+
     /// Deletes a secret entirely (irreversible!).
-    pub async fn delete_secret(&self, secret_name: &str) -> Result<()> {
+    ///
+    /// Fails with a conflict (`ABORTED`, or `FAILED_PRECONDITION` for an etag
+    /// mismatch) if the secret changed since `etag` was read.
+    pub async fn delete_secret(&self, secret_name: &str, etag: &str) -> Result<()> {
         let name = self.secret_path(secret_name);
 
-        self.client
-            .delete_secret()
-            .set_name(&name)
-            .send()
-            .await
-            .context("Failed to delete secret")?;
+        self.call(Idempotency::NonIdempotent, || async {
+            self.client
+                .delete_secret()
+                .set_name(&name)
+                .set_etag(etag)
+                .send()
+                .await
+                .context("Failed to delete secret")
+        })
+        .await?;
 
         Ok(())
     }
 
+    /// Gets the IAM policy of a secret, including conditional bindings.
+    pub async fn get_iam_policy(&self, secret_name: &str) -> Result<IamPolicy> {
+        let resource = self.secret_path(secret_name);
+        let options =
+            GetPolicyOptions::default().set_requested_policy_version(CONDITIONAL_POLICY_VERSION);
+
+        let policy = self
+            .call(Idempotency::Idempotent, || async {
+                self.client
+                    .get_iam_policy()
+                    .set_resource(&resource)
+                    .set_options(options.clone())
+                    .send()
+                    .await
+                    .context("Failed to get IAM policy")
+            })
+            .await?;
+
+        Ok(policy_from_proto(&policy))
+    }
+
+    /// Replaces the role bindings of a secret's IAM policy.
+    ///
+    /// The policy's etag makes the call fail if someone else changed the
+    /// policy since it was read. Returns the policy as stored.
+    pub async fn set_iam_policy(&self, secret_name: &str, policy: &IamPolicy) -> Result<IamPolicy> {
+        let resource = self.secret_path(secret_name);
+        let proto = policy_to_proto(policy);
+
+        let stored = self
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .set_iam_policy()
+                    .set_resource(&resource)
+                    .set_policy(proto.clone())
+                    .send()
+                    .await
+                    .context("Failed to set IAM policy")
+            })
+            .await?;
+
+        Ok(policy_from_proto(&stored))
+    }
+
+    /// Returns which of `permissions` the caller holds on a secret.
+    pub async fn test_iam_permissions(
+        &self,
+        secret_name: &str,
+        permissions: &[&str],
+    ) -> Result<Vec<String>> {
+        let resource = self.secret_path(secret_name);
+
+        let response = self
+            .call(Idempotency::Idempotent, || async {
+                self.client
+                    .test_iam_permissions()
+                    .set_resource(&resource)
+                    .set_permissions(permissions.iter().copied())
+                    .send()
+                    .await
+                    .context("Failed to test IAM permissions")
+            })
+            .await?;
+
+        Ok(response.permissions)
+    }
+
     // --- Helper methods ---
 
+    /// Writes one field of a secret.
+    ///
+    /// Naming the field in the update mask without setting it clears it.
+    async fn update_secret(
+        &self,
+        secret_name: &str,
+        secret: Secret,
+        field: &str,
+        etag: &str,
+        context: &'static str,
+    ) -> Result<SecretInfo> {
+        let secret = secret
+            .set_name(self.secret_path(secret_name))
+            .set_etag(etag);
+        let mask = FieldMask::default().set_paths([field]);
+
+        let updated = self
+            .call(Idempotency::NonIdempotent, || async {
+                self.client
+                    .update_secret()
+                    .set_secret(secret.clone())
+                    .set_update_mask(mask.clone())
+                    .send()
+                    .await
+                    .context(context)
+            })
+            .await?;
+
+        Ok(self.secret_to_info(&updated))
+    }
+
+    /// Runs an API call under the retry policy.
+    async fn call<T, F, Fut>(&self, idempotency: Idempotency, call: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.retry.run(idempotency, &self.retries, call).await
+    }
+
+    /// Returns how many retries were made since the last call, resetting the count.
+    pub fn take_retries(&self) -> u32 {
+        self.retries.swap(0, Ordering::Relaxed)
+    }
+
+    /// Returns the project this client operates on.
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Returns the location this client operates on (`None` for global).
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
     /// Returns the full path for a secret.
-    fn secret_path(&self, secret_name: &str) -> String {
-        format!("projects/{}/secrets/{}", self.project_id, secret_name)
+    pub(crate) fn secret_path(&self, secret_name: &str) -> String {
+        format!("{}/secrets/{}", self.parent(), secret_name)
     }
 
     /// Formats a protobuf timestamp as a date string (YYYY-MM-DD).
@@ -318,11 +792,13 @@ impl SecretClient {
             |t| Self::format_timestamp(t.seconds()),
         );
 
-        let labels: Vec<(String, String)> = secret
+        // Sorted, so that labels are listed and compared in a stable order
+        let mut labels: Vec<(String, String)> = secret
             .labels
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
+        labels.sort();
 
         let annotations: Vec<(String, String)> = secret
             .annotations
@@ -330,20 +806,50 @@ impl SecretClient {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
-        // Parse replication policy
-        let replication = match &secret.replication {
-            Some(r) => match &r.replication {
-                Some(replication::Replication::UserManaged(um)) => {
-                    let locations: Vec<String> = um
-                        .replicas
-                        .iter()
-                        .map(|replica| replica.location.clone())
-                        .collect();
-                    ReplicationPolicy::UserManaged(locations)
-                }
-                _ => ReplicationPolicy::Automatic,
-            },
-            None => ReplicationPolicy::Automatic,
+        // Parse replication policy and the keys protecting each replica
+        let kms_key = |cme: Option<&CustomerManagedEncryption>| {
+            cme.map(|c| c.kms_key_name.clone())
+                .filter(|name| !name.is_empty())
+        };
+        let (replication, encryption) = match secret
+            .replication
+            .as_ref()
+            .and_then(|r| r.replication.as_ref())
+        {
+            Some(replication::Replication::UserManaged(um)) => {
+                let locations: Vec<String> = um
+                    .replicas
+                    .iter()
+                    .map(|replica| replica.location.clone())
+                    .collect();
+                let encryption = um
+                    .replicas
+                    .iter()
+                    .map(|replica| ReplicaEncryption {
+                        location: replica.location.clone(),
+                        kms_key: kms_key(replica.customer_managed_encryption.as_ref()),
+                    })
+                    .collect();
+                (ReplicationPolicy::UserManaged(locations), encryption)
+            }
+            Some(replication::Replication::Automatic(automatic)) => (
+                ReplicationPolicy::Automatic,
+                vec![ReplicaEncryption {
+                    location: "automatic".to_string(),
+                    kms_key: kms_key(automatic.customer_managed_encryption.as_ref()),
+                }],
+            ),
+            // Regional secrets have no replication, only a key of their own
+            _ => (
+                ReplicationPolicy::Automatic,
+                location_from_name(&secret.name)
+                    .map(|location| ReplicaEncryption {
+                        location,
+                        kms_key: kms_key(secret.customer_managed_encryption.as_ref()),
+                    })
+                    .into_iter()
+                    .collect(),
+            ),
         };
 
         // Extract Pub/Sub topics
@@ -369,16 +875,10 @@ impl SecretClient {
         });
 
         // Parse version destroy TTL
-        let version_destroy_ttl = secret.version_destroy_ttl.as_ref().map(|d| {
-            let secs = d.seconds();
-            if secs >= 86400 {
-                format!("{}d", secs / 86400)
-            } else if secs >= 3600 {
-                format!("{}h", secs / 3600)
-            } else {
-                format!("{secs}s")
-            }
-        });
+        let version_destroy_ttl = secret
+            .version_destroy_ttl
+            .as_ref()
+            .map(|d| format_ttl(d.seconds()));
 
         SecretInfo {
             short_name,
@@ -390,6 +890,11 @@ impl SecretClient {
             version_aliases,
             rotation,
             version_destroy_ttl,
+            location: location_from_name(&secret.name),
+            etag: secret.etag.clone(),
+            expire_time: secret.expire_time().map(|t| t.seconds()),
+            encryption,
+            versions: None,
         }
     }
 
@@ -419,7 +924,38 @@ impl SecretClient {
         let scheduled_destroy_time = version
             .scheduled_destroy_time
             .as_ref()
-            .map(|t| Self::format_timestamp(t.seconds()));
+            .map(Timestamp::seconds);
+
+        // Key versions used by a regional secret, or by each replica
+        let mut kms_key_versions: Vec<String> = version
+            .customer_managed_encryption
+            .iter()
+            .map(|c| c.kms_key_version_name.clone())
+            .collect();
+        match version
+            .replication_status
+            .as_ref()
+            .and_then(|r| r.replication_status.as_ref())
+        {
+            Some(replication_status::ReplicationStatus::Automatic(automatic)) => kms_key_versions
+                .extend(
+                    automatic
+                        .customer_managed_encryption
+                        .iter()
+                        .map(|c| c.kms_key_version_name.clone()),
+                ),
+            Some(replication_status::ReplicationStatus::UserManaged(um)) => {
+                kms_key_versions.extend(
+                    um.replicas
+                        .iter()
+                        .filter_map(|r| r.customer_managed_encryption.as_ref())
+                        .map(|c| c.kms_key_version_name.clone()),
+                );
+            }
+            _ => {}
+        }
+        kms_key_versions.retain(|name| !name.is_empty());
+        kms_key_versions.dedup();
 
         VersionInfo {
             version: version_num,
@@ -428,14 +964,216 @@ impl SecretClient {
             destroy_time,
             scheduled_destroy_time,
             has_checksum: version.client_specified_payload_checksum,
+            kms_key_versions,
+            etag: version.etag.clone(),
+        }
+    }
+}
+
+/// Shortens a Cloud KMS key or key version name to its key ring, key and
+/// version, e.g. `ring/key/3`.
+pub(crate) fn short_kms_name(name: &str) -> String {
+    let parts: Vec<&str> = name.split('/').collect();
+    let value = |collection: &str| {
+        parts
+            .iter()
+            .position(|p| *p == collection)
+            .and_then(|i| parts.get(i + 1))
+    };
+    let short: Vec<&str> = ["keyRings", "cryptoKeys", "cryptoKeyVersions"]
+        .iter()
+        .filter_map(|c| value(c).copied())
+        .collect();
+    if short.is_empty() {
+        name.to_string()
+    } else {
+        short.join("/")
+    }
+}
+
+/// Formats a destroy TTL in seconds as `7d`, `12h` or `90s`.
+pub(crate) fn format_ttl(seconds: i64) -> String {
+    if seconds >= 86400 {
+        format!("{}d", seconds / 86400)
+    } else if seconds >= 3600 {
+        format!("{}h", seconds / 3600)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Converts an IAM `Policy` proto to our `IamPolicy`, sorted by role.
+fn policy_from_proto(policy: &Policy) -> IamPolicy {
+    let mut result = IamPolicy {
+        version: policy.version,
+        bindings: policy
+            .bindings
+            .iter()
+            .map(|b| IamBinding {
+                role: b.role.clone(),
+                members: b.members.clone(),
+                condition: b.condition.as_ref().map(|c| IamCondition {
+                    title: c.title.clone(),
+                    expression: c.expression.clone(),
+                    description: c.description.clone(),
+                    location: c.location.clone(),
+                }),
+            })
+            .collect(),
+        etag: policy.etag.to_vec(),
+    };
+    result.sort();
+    result
+}
+
+/// Converts our `IamPolicy` back to a proto for `SetIamPolicy`.
+///
+/// Policies with conditions must be written as version 3, or the
+/// conditions would be dropped.
+fn policy_to_proto(policy: &IamPolicy) -> Policy {
+    let version = if policy.has_conditions() {
+        CONDITIONAL_POLICY_VERSION
+    } else {
+        policy.version
+    };
+    let bindings = policy.bindings.iter().map(|b| {
+        Binding::default()
+            .set_role(&b.role)
+            .set_members(b.members.iter().cloned())
+            .set_or_clear_condition(b.condition.as_ref().map(|c| {
+                google_cloud_type::model::Expr::default()
+                    .set_title(&c.title)
+                    .set_expression(&c.expression)
+                    .set_description(&c.description)
+                    .set_location(&c.location)
+            }))
+    });
+    Policy::default()
+        .set_version(version)
+        .set_bindings(bindings)
+        .set_etag(policy.etag.clone())
+}
+
+/// Extracts the region from a secret resource name.
+///
+/// `projects/p/locations/r/secrets/s` yields `r`; global secrets
+/// (`projects/p/secrets/s`) yield `None`.
+fn location_from_name(name: &str) -> Option<String> {
+    let mut parts = name.split('/');
+    while let Some(part) = parts.next() {
+        if part == "locations" {
+            return parts.next().map(str::to_string);
         }
     }
+    None
+}
+
+/// Returns a secret to create in `location` with `replication`.
+///
+/// Only global secrets take a replication policy; a regional secret is
+/// stored in its location and the API rejects one that sets replication.
+fn new_secret(location: Option<&str>, replication: Replication) -> Secret {
+    let secret = Secret::default();
+    if location.is_some() {
+        secret
+    } else {
+        secret.set_replication(replication)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_policy_round_trip_keeps_conditions_and_etag() {
+        let policy = IamPolicy {
+            version: 1,
+            bindings: vec![IamBinding {
+                role: "roles/secretmanager.secretAccessor".to_string(),
+                members: vec!["user:ana@example.com".to_string()],
+                condition: Some(IamCondition {
+                    title: "weekdays".to_string(),
+                    expression: "request.time.getDayOfWeek() < 5".to_string(),
+                    description: String::new(),
+                    location: String::new(),
+                }),
+            }],
+            etag: b"BwX1".to_vec(),
+        };
+
+        let proto = policy_to_proto(&policy);
+        assert_eq!(proto.version, CONDITIONAL_POLICY_VERSION);
+
+        let back = policy_from_proto(&proto);
+        assert_eq!(back.bindings, policy.bindings);
+        assert_eq!(back.etag, policy.etag);
+    }
+
+    #[test]
+    fn test_version_summary_counts_enabled_and_finds_latest() {
+        let version = |number: &str, state| VersionInfo {
+            version: number.to_string(),
+            state,
+            create_time: "2024-01-01".to_string(),
+            destroy_time: None,
+            scheduled_destroy_time: None,
+            has_checksum: false,
+            kms_key_versions: vec![],
+            etag: String::new(),
+        };
+        let versions = [
+            version("2", VersionState::Enabled),
+            version("10", VersionState::Destroyed),
+            version("9", VersionState::Enabled),
+            version("3", VersionState::Disabled),
+        ];
+        assert_eq!(
+            VersionSummary::from_versions(&versions),
+            VersionSummary {
+                latest: Some(10),
+                enabled: 2,
+            }
+        );
+        assert_eq!(
+            VersionSummary::from_versions(&[]),
+            VersionSummary::default()
+        );
+    }
+
+    #[test]
+    fn test_regional_secrets_take_no_replication() {
+        let automatic = || Replication::default().set_automatic(replication::Automatic::default());
+        assert!(new_secret(Some("europe-west1"), automatic())
+            .replication
+            .is_none());
+        assert_eq!(new_secret(None, automatic()).replication, Some(automatic()));
+    }
+
+    #[test]
+    fn test_short_kms_name() {
+        assert_eq!(
+            short_kms_name(
+                "projects/p/locations/europe-west1/keyRings/ring/cryptoKeys/key/cryptoKeyVersions/3"
+            ),
+            "ring/key/3"
+        );
+        assert_eq!(
+            short_kms_name("projects/p/locations/global/keyRings/ring/cryptoKeys/key"),
+            "ring/key"
+        );
+        assert_eq!(short_kms_name("unexpected"), "unexpected");
+    }
+
+    #[test]
+    fn test_location_from_name() {
+        assert_eq!(location_from_name("projects/p/secrets/api-key"), None);
+        assert_eq!(
+            location_from_name("projects/p/locations/europe-west1/secrets/api-key"),
+            Some("europe-west1".to_string())
+        );
+    }
+
     #[test]
     fn test_format_timestamp_valid() {
         // 2024-01-15 00:00:00 UTC = 1705276800 seconds since epoch
@@ -0,0 +1,191 @@
+//! Resource Manager tag bindings of secrets.
+//!
+//! Tags are bound to a secret through the Resource Manager `TagBindings`
+//! service, which addresses the secret by its full resource name. Bindings
+//! of regional secrets are managed through the regional Resource Manager
+//! endpoint.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use anyhow::{Context, Result};
+use google_cloud_gax::retry_policy::NeverRetry;
+use google_cloud_lro::Poller;
+use google_cloud_resourcemanager_v3::client::TagBindings;
+use google_cloud_resourcemanager_v3::model;
+
+use crate::connection::ConnectionOptions;
+use crate::retry::{Idempotency, RetryPolicy};
+
+/// A tag value bound to a secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagBinding {
+    /// Binding name, used to unbind it
+    pub name: String,
+    /// Tag value ID, e.g. `tagValues/281484`
+    pub tag_value: String,
+    /// Namespaced tag value, e.g. `123456789/env/prod` (may be empty)
+    pub namespaced_name: String,
+}
+
+impl TagBinding {
+    /// Returns the namespaced value if known, otherwise the value ID.
+    pub fn label(&self) -> &str {
+        if self.namespaced_name.is_empty() {
+            &self.tag_value
+        } else {
+            &self.namespaced_name
+        }
+    }
+}
+
+/// Wrapper around the Resource Manager tag bindings client.
+pub struct TagClient {
+    /// The underlying Google Cloud client
+    client: TagBindings,
+    /// Timeout and retry settings
+    retry: RetryPolicy,
+    /// Retries made by calls
+    retries: AtomicU32,
+}
+
+impl TagClient {
+    /// Creates a client for tag bindings of secrets in the location of
+    /// `connection`.
+    pub async fn new(connection: &ConnectionOptions) -> Result<Self> {
+        let mut builder = TagBindings::builder().with_retry_policy(NeverRetry);
+        if let Some(endpoint) = connection.resource_manager_endpoint() {
+            builder = builder.with_endpoint(endpoint);
+        }
+        if let Some(credentials) = connection.credentials()? {
+            builder = builder.with_credentials(credentials);
+        }
+        let client = builder
+            .build()
+            .await
+            .context("Failed to create Resource Manager client")?;
+
+        Ok(Self {
+            client,
+            retry: connection.retry.clone(),
+            retries: AtomicU32::new(0),
+        })
+    }
+
+    /// Returns how many retries were made since the last call, resetting the count.
+    pub fn take_retries(&self) -> u32 {
+        self.retries.swap(0, Ordering::Relaxed)
+    }
+
+    /// Lists the tag values bound directly to a secret.
+    ///
+    /// `resource` is the secret's resource name (`projects/.../secrets/...`);
+    /// tags inherited from the project or organization are not included.
+    pub async fn list_tag_bindings(&self, resource: &str) -> Result<Vec<TagBinding>> {
+        let parent = full_resource_name(resource);
+
+        let mut bindings = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let page = self
+                .retry
+                .run(Idempotency::Idempotent, &self.retries, || async {
+                    self.client
+                        .list_tag_bindings()
+                        .set_parent(&parent)
+                        .set_page_token(&page_token)
+                        .send()
+                        .await
+                        .context("Failed to list tags")
+                })
+                .await?;
+
+            bindings.extend(page.tag_bindings.into_iter().map(binding_to_info));
+            if page.next_page_token.is_empty() {
+                return Ok(bindings);
+            }
+            page_token = page.next_page_token;
+        }
+    }
+
+    /// Binds a tag value to a secret and waits until the binding exists.
+    ///
+    /// `value` is a namespaced value (`123456789/env/prod`) or a value ID
+    /// (`tagValues/281484`).
+    pub async fn bind_tag(&self, resource: &str, value: &str) -> Result<TagBinding> {
+        let mut binding = model::TagBinding::new().set_parent(full_resource_name(resource));
+        binding = if value.starts_with("tagValues/") {
+            binding.set_tag_value(value)
+        } else {
+            binding.set_tag_value_namespaced_name(value)
+        };
+
+        self.retry
+            .run(Idempotency::NonIdempotent, &self.retries, || async {
+                self.client
+                    .create_tag_binding()
+                    .set_tag_binding(binding.clone())
+                    .poller()
+                    .until_done()
+                    .await
+                    .map(binding_to_info)
+                    .with_context(|| format!("Failed to bind tag {value}"))
+            })
+            .await
+    }
+
+    /// Removes a tag binding and waits until it is gone.
+    pub async fn unbind_tag(&self, binding: &TagBinding) -> Result<()> {
+        self.retry
+            .run(Idempotency::NonIdempotent, &self.retries, || async {
+                self.client
+                    .delete_tag_binding()
+                    .set_name(&binding.name)
+                    .poller()
+                    .until_done()
+                    .await
+                    .with_context(|| format!("Failed to unbind tag {}", binding.label()))
+            })
+            .await
+    }
+}
+
+/// Returns the full resource name tags address a secret by, e.g.
+/// `//secretmanager.googleapis.com/projects/p/secrets/api-key`.
+pub fn full_resource_name(resource: &str) -> String {
+    format!("//secretmanager.googleapis.com/{resource}")
+}
+
+/// Converts a `TagBinding` proto to our `TagBinding` struct.
+fn binding_to_info(binding: model::TagBinding) -> TagBinding {
+    TagBinding {
+        name: binding.name,
+        tag_value: binding.tag_value,
+        namespaced_name: binding.tag_value_namespaced_name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_resource_name() {
+        assert_eq!(
+            full_resource_name("projects/p/locations/europe-west1/secrets/api-key"),
+            "//secretmanager.googleapis.com/projects/p/locations/europe-west1/secrets/api-key"
+        );
+    }
+
+    #[test]
+    fn test_label_falls_back_to_value_id() {
+        let mut binding = TagBinding {
+            name: "tagBindings/x".to_string(),
+            tag_value: "tagValues/281484".to_string(),
+            namespaced_name: String::new(),
+        };
+        assert_eq!(binding.label(), "tagValues/281484");
+
+        binding.namespaced_name = "123456789/env/prod".to_string();
+        assert_eq!(binding.label(), "123456789/env/prod");
+    }
+}
@@ -2,72 +2,109 @@
 
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     symbols,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-use super::colors;
+use crate::app::App;
+use crate::connection::CredentialSource;
+use crate::event::Action;
+use crate::keymap::Scope;
 
 /// Draws the authentication required screen.
-pub fn draw_auth_required(frame: &mut Frame, area: Rect) {
+///
+/// Explains why credentials are needed and lists the sources that were checked.
+pub fn draw_auth_required(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::WARNING))
+        .border_style(Style::default().fg(theme.warning))
         .border_set(symbols::border::ROUNDED)
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled("", Style::default().fg(colors::WARNING)),
+            Span::styled("", Style::default().fg(theme.warning)),
             Span::styled(
                 " Authentication Required ",
-                Style::default().fg(colors::WARNING).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
         ]));
 
-    let content = vec![
+    let mut content = vec![
         Line::from(""),
-        Line::from(""),
-        Line::from(Span::styled("", Style::default().fg(colors::WARNING))),
+        Line::from(Span::styled("", Style::default().fg(theme.warning))),
         Line::from(""),
         Line::from(Span::styled(
-            "GCP credentials not found",
-            Style::default().fg(colors::PRIMARY).bold(),
+            app.auth_error
+                .as_deref()
+                .unwrap_or("GCP credentials not found"),
+            Style::default().fg(theme.primary).bold(),
         )),
         Line::from(""),
         Line::from(Span::styled(
             "To use this app, you need to authenticate with Google Cloud.",
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         )),
         Line::from(""),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Press ", Style::default().fg(colors::MUTED)),
-            Span::styled("Enter", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" to run: ", Style::default().fg(colors::MUTED)),
+        Line::from(Span::styled(
+            "Checked, in order:",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    for check in CredentialSource::checks() {
+        let (icon, color) = match check.found {
+            Some(true) => ("✓", theme.success),
+            Some(false) => ("✗", theme.error),
+            None => ("·", theme.muted),
+        };
+        content.push(Line::from(vec![
+            Span::styled(format!("{icon} "), Style::default().fg(color)),
+            Span::styled(check.name, Style::default().fg(theme.text).bold()),
             Span::styled(
-                "gcloud auth application-default login",
-                Style::default().fg(colors::SECONDARY),
+                format!("  {}", check.detail),
+                Style::default().fg(theme.muted),
             ),
-        ]),
+        ]));
+    }
+    // Without a login key, the command is still worth running by hand
+    let mut login = match app.keymap.key(Scope::AuthRequired, &Action::Enter) {
+        Some(key) => vec![
+            Span::styled("Press ", Style::default().fg(theme.muted)),
+            Span::styled(key, Style::default().fg(theme.key).bold()),
+            Span::styled(" to run: ", Style::default().fg(theme.muted)),
+        ],
+        None => vec![Span::styled("Run: ", Style::default().fg(theme.muted))],
+    };
+    login.push(Span::styled(
+        "gcloud auth application-default login",
+        Style::default().fg(theme.secondary),
+    ));
+    content.extend([
+        Line::from(""),
+        Line::from(login),
         Line::from(""),
         Line::from(Span::styled(
             "This will open your browser to authenticate.",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         )),
         Line::from(Span::styled(
             "Make sure to check all permission boxes in the consent screen.",
-            Style::default().fg(colors::WARNING),
+            Style::default().fg(theme.warning),
         )),
-        Line::from(""),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Press ", Style::default().fg(colors::MUTED)),
-            Span::styled("q", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" to quit", Style::default().fg(colors::MUTED)),
-        ]),
-    ];
+    ]);
+    if let Some(key) = app.keymap.key(Scope::AuthRequired, &Action::Quit) {
+        content.extend([
+            Line::from(""),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Press ", Style::default().fg(theme.muted)),
+                Span::styled(key, Style::default().fg(theme.key).bold()),
+                Span::styled(" to quit", Style::default().fg(theme.muted)),
+            ]),
+        ]);
+    }
 
     let paragraph = Paragraph::new(content)
         .block(block)
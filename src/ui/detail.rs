@@ -1,22 +1,30 @@
 //! Secret detail view rendering.
 
+use chrono::Utc;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     symbols,
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, View};
+use crate::connection::location_label;
+use crate::event::Action;
+use crate::expiration::{countdown, format_time, urgency};
+use crate::keymap::Scope;
 use crate::secret_client::ReplicationPolicy;
 
-use super::colors;
+use super::iam::draw_iam_policy;
 use super::lists::draw_versions_list;
+use super::tags::draw_tag_bindings;
+use super::theme::Theme;
 
-/// Draws the secret detail view with versions.
+/// Draws the secret detail view with the versions or IAM tab.
 pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let Some(secret) = &app.current_secret else {
         return;
     };
@@ -41,6 +49,10 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if secret.version_destroy_ttl.is_some() {
         extra_rows += 1;
     }
+    if secret.expire_time.is_some() {
+        extra_rows += 1;
+    }
+    extra_rows += u16::try_from(secret.encryption.len().max(1)).unwrap_or(1);
     let info_card_height = 5 + extra_rows; // Base: name, created, replication + borders
 
     // Split the area into sections
@@ -57,22 +69,22 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     // Draw back hint
     let back_hint = Paragraph::new(Line::from(vec![
         Span::styled("  ", Style::default()),
-        Span::styled("", Style::default().fg(colors::PRIMARY)),
+        Span::styled("", Style::default().fg(theme.primary)),
         Span::styled(" ", Style::default()),
-        Span::styled("Esc", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" to go back", Style::default().fg(colors::MUTED)),
+        Span::styled("Esc", Style::default().fg(theme.key).bold()),
+        Span::styled(" to go back", Style::default().fg(theme.muted)),
     ]));
     frame.render_widget(back_hint, chunks[0]);
 
     // Draw secret info card
     let info_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::PRIMARY))
+        .border_style(Style::default().fg(theme.primary))
         .border_set(symbols::border::ROUNDED)
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled("", Style::default().fg(colors::PRIMARY)),
-            Span::styled(" Secret Details ", Style::default().fg(Color::White).bold()),
+            Span::styled("", Style::default().fg(theme.primary)),
+            Span::styled(" Secret Details ", Style::default().fg(theme.text).bold()),
         ]));
 
     // Replication display
@@ -89,14 +101,19 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
 
     let mut info_content = vec![
         Line::from(vec![
-            Span::styled("  Name        ", Style::default().fg(colors::MUTED)),
-            Span::styled(&secret.short_name, Style::default().fg(Color::White).bold()),
+            Span::styled("  Name        ", Style::default().fg(theme.muted)),
+            Span::styled(&secret.short_name, Style::default().fg(theme.text).bold()),
+            Span::styled("    Location  ", Style::default().fg(theme.muted)),
+            Span::styled(
+                location_label(secret.location.as_deref()),
+                Style::default().fg(theme.secondary),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("  Created     ", Style::default().fg(colors::MUTED)),
-            Span::styled(&secret.create_time, Style::default().fg(Color::White)),
-            Span::styled("    Replication  ", Style::default().fg(colors::MUTED)),
-            Span::styled(&replication_str, Style::default().fg(colors::SECONDARY)),
+            Span::styled("  Created     ", Style::default().fg(theme.muted)),
+            Span::styled(&secret.create_time, Style::default().fg(theme.text)),
+            Span::styled("    Replication  ", Style::default().fg(theme.muted)),
+            Span::styled(&replication_str, Style::default().fg(theme.secondary)),
         ]),
     ];
 
@@ -104,15 +121,15 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if !secret.labels.is_empty() {
         let mut label_spans = vec![Span::styled(
             "  Labels      ",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         )];
         for (i, (key, value)) in secret.labels.iter().enumerate() {
             if i > 0 {
                 label_spans.push(Span::styled("  ", Style::default()));
             }
-            label_spans.push(Span::styled(key, Style::default().fg(colors::ACCENT)));
-            label_spans.push(Span::styled("=", Style::default().fg(colors::MUTED)));
-            label_spans.push(Span::styled(value, Style::default().fg(Color::White)));
+            label_spans.push(Span::styled(key, Style::default().fg(theme.accent)));
+            label_spans.push(Span::styled("=", Style::default().fg(theme.muted)));
+            label_spans.push(Span::styled(value, Style::default().fg(theme.text)));
         }
         info_content.push(Line::from(label_spans));
     }
@@ -121,15 +138,15 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if !secret.annotations.is_empty() {
         let mut spans = vec![Span::styled(
             "  Annotations ",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         )];
         for (i, (key, value)) in secret.annotations.iter().enumerate() {
             if i > 0 {
                 spans.push(Span::styled("  ", Style::default()));
             }
-            spans.push(Span::styled(key, Style::default().fg(colors::WARNING)));
-            spans.push(Span::styled("=", Style::default().fg(colors::MUTED)));
-            spans.push(Span::styled(value, Style::default().fg(Color::White)));
+            spans.push(Span::styled(key, Style::default().fg(theme.warning)));
+            spans.push(Span::styled("=", Style::default().fg(theme.muted)));
+            spans.push(Span::styled(value, Style::default().fg(theme.text)));
         }
         info_content.push(Line::from(spans));
     }
@@ -138,8 +155,8 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if !secret.topics.is_empty() {
         let topics_str = secret.topics.join(", ");
         info_content.push(Line::from(vec![
-            Span::styled("  Pub/Sub     ", Style::default().fg(colors::MUTED)),
-            Span::styled(topics_str, Style::default().fg(Color::White)),
+            Span::styled("  Pub/Sub     ", Style::default().fg(theme.muted)),
+            Span::styled(topics_str, Style::default().fg(theme.text)),
         ]));
     }
 
@@ -147,17 +164,17 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if !secret.version_aliases.is_empty() {
         let mut spans = vec![Span::styled(
             "  Aliases     ",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         )];
         for (i, (alias, version)) in secret.version_aliases.iter().enumerate() {
             if i > 0 {
                 spans.push(Span::styled("  ", Style::default()));
             }
-            spans.push(Span::styled(alias, Style::default().fg(colors::KEY)));
-            spans.push(Span::styled("→v", Style::default().fg(colors::MUTED)));
+            spans.push(Span::styled(alias, Style::default().fg(theme.key)));
+            spans.push(Span::styled("→v", Style::default().fg(theme.muted)));
             spans.push(Span::styled(
                 version.to_string(),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme.text),
             ));
         }
         info_content.push(Line::from(spans));
@@ -167,15 +184,15 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     if let Some(rotation) = &secret.rotation {
         let mut spans = vec![Span::styled(
             "  Rotation    ",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         )];
         if let Some(period) = &rotation.rotation_period {
-            spans.push(Span::styled("every ", Style::default().fg(Color::White)));
-            spans.push(Span::styled(period, Style::default().fg(colors::SECONDARY)));
+            spans.push(Span::styled("every ", Style::default().fg(theme.text)));
+            spans.push(Span::styled(period, Style::default().fg(theme.secondary)));
         }
         if let Some(next) = &rotation.next_rotation_time {
-            spans.push(Span::styled("  next: ", Style::default().fg(colors::MUTED)));
-            spans.push(Span::styled(next, Style::default().fg(Color::White)));
+            spans.push(Span::styled("  next: ", Style::default().fg(theme.muted)));
+            spans.push(Span::styled(next, Style::default().fg(theme.text)));
         }
         info_content.push(Line::from(spans));
     }
@@ -183,30 +200,130 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
     // Add version destroy TTL if set
     if let Some(ttl) = &secret.version_destroy_ttl {
         info_content.push(Line::from(vec![
-            Span::styled("  Destroy TTL ", Style::default().fg(colors::MUTED)),
-            Span::styled(ttl, Style::default().fg(colors::WARNING)),
-            Span::styled(" (delayed destruction)", Style::default().fg(colors::MUTED)),
+            Span::styled("  Destroy TTL ", Style::default().fg(theme.muted)),
+            Span::styled(ttl, Style::default().fg(theme.warning)),
+            Span::styled(" (delayed destruction)", Style::default().fg(theme.muted)),
+        ]));
+    }
+
+    // Add expiration with a countdown if set
+    if let Some(expire_time) = secret.expire_time {
+        let now = Utc::now().timestamp();
+        let color = theme.expiration(urgency(expire_time, now));
+        info_content.push(Line::from(vec![
+            Span::styled("  Expires     ", Style::default().fg(theme.muted)),
+            Span::styled(format_time(expire_time), Style::default().fg(theme.text)),
+            Span::styled(
+                format!("  {}", countdown(expire_time, now)),
+                Style::default().fg(color).bold(),
+            ),
+            Span::styled(
+                " (the secret is then deleted)",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
+
+    // Add the key protecting each replica, or Google-managed encryption
+    if secret.encryption.is_empty() {
+        info_content.push(Line::from(vec![
+            Span::styled("  Encryption  ", Style::default().fg(theme.muted)),
+            Span::styled("Google-managed", Style::default().fg(theme.text)),
+        ]));
+    }
+    // Replicas left without a key when others have one are flagged
+    let partial = secret.encryption.iter().any(|r| r.kms_key.is_some());
+    for (i, replica) in secret.encryption.iter().enumerate() {
+        let label = if i == 0 {
+            "  Encryption  "
+        } else {
+            "              "
+        };
+        let key = match &replica.kms_key {
+            Some(key) => Span::styled(key, Style::default().fg(theme.success)),
+            None => Span::styled(
+                "Google-managed",
+                Style::default().fg(if partial { theme.warning } else { theme.text }),
+            ),
+        };
+        info_content.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.muted)),
+            Span::styled(
+                format!("{:<14}", replica.location),
+                Style::default().fg(theme.secondary),
+            ),
+            key,
         ]));
     }
 
     let info = Paragraph::new(info_content).block(info_block);
     frame.render_widget(info, chunks[1]);
 
-    // Draw versions header with action hints
-    let versions_hint = Paragraph::new(Line::from(vec![
+    // Dialogs opened from a tab keep showing it underneath
+    let tab = if matches!(app.current_view, View::SecretIam | View::SecretTags) {
+        &app.current_view
+    } else {
+        app.previous_view.as_ref().unwrap_or(&View::SecretDetail)
+    };
+    let (iam_tab, tags_tab) = (tab == &View::SecretIam, tab == &View::SecretTags);
+
+    // Draw the tabs with action hints
+    let tab_style = |active: bool| {
+        if active {
+            Style::default().fg(theme.primary).bold()
+        } else {
+            Style::default().fg(theme.muted)
+        }
+    };
+    let mut tabs = vec![
         Span::styled("  ", Style::default()),
-        Span::styled("", Style::default().fg(colors::ACCENT)),
+        Span::styled("", Style::default().fg(theme.accent)),
         Span::styled(" ", Style::default()),
-        Span::styled("Versions", Style::default().fg(colors::PRIMARY).bold()),
-        Span::styled(" - ", Style::default().fg(colors::MUTED)),
-        Span::styled("s", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" show  ", Style::default().fg(colors::MUTED)),
-        Span::styled("c", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" copy  ", Style::default().fg(colors::MUTED)),
-        Span::styled("a", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" add new", Style::default().fg(colors::MUTED)),
-    ]));
-    frame.render_widget(versions_hint, chunks[2]);
+        Span::styled("Versions", tab_style(!iam_tab && !tags_tab)),
+        Span::styled(" │ ", Style::default().fg(theme.border)),
+        Span::styled("IAM", tab_style(iam_tab)),
+        Span::styled(" │ ", Style::default().fg(theme.border)),
+        Span::styled("Tags", tab_style(tags_tab)),
+        Span::styled(" - ", Style::default().fg(theme.muted)),
+    ];
+    if iam_tab {
+        tabs.extend([
+            Span::styled("a", Style::default().fg(theme.key).bold()),
+            Span::styled(" grant  ", Style::default().fg(theme.muted)),
+            Span::styled("d", Style::default().fg(theme.key).bold()),
+            Span::styled(" revoke  ", Style::default().fg(theme.muted)),
+        ]);
+    } else if tags_tab {
+        tabs.extend([
+            Span::styled("a", Style::default().fg(theme.key).bold()),
+            Span::styled(" bind  ", Style::default().fg(theme.muted)),
+            Span::styled("d", Style::default().fg(theme.key).bold()),
+            Span::styled(" unbind  ", Style::default().fg(theme.muted)),
+        ]);
+    } else {
+        tabs.extend([
+            Span::styled("s", Style::default().fg(theme.key).bold()),
+            Span::styled(" show  ", Style::default().fg(theme.muted)),
+            Span::styled("c", Style::default().fg(theme.key).bold()),
+            Span::styled(" copy  ", Style::default().fg(theme.muted)),
+            Span::styled("a", Style::default().fg(theme.key).bold()),
+            Span::styled(" add new  ", Style::default().fg(theme.muted)),
+        ]);
+    }
+    tabs.extend([
+        Span::styled("Tab", Style::default().fg(theme.key).bold()),
+        Span::styled(" switch", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(Paragraph::new(Line::from(tabs)), chunks[2]);
+
+    if iam_tab {
+        draw_iam_policy(frame, chunks[3], app);
+        return;
+    }
+    if tags_tab {
+        draw_tag_bindings(frame, chunks[3], app);
+        return;
+    }
 
     // Determine if we're showing the secret value
     let (versions_area, value_area) = if app.revealed_value.is_some() {
@@ -224,31 +341,42 @@ pub fn draw_secret_detail(frame: &mut Frame, area: Rect, app: &App) {
 
     // Draw the revealed value if present
     if let (Some(area), Some(value)) = (value_area, &app.revealed_value) {
-        draw_secret_value(frame, area, value);
+        let hide_key = app
+            .keymap
+            .key(Scope::SecretDetail, &Action::ToggleSecretValue);
+        draw_secret_value(frame, area, value, hide_key.as_deref(), theme);
     }
 }
 
-/// Draws the revealed secret value panel.
-pub fn draw_secret_value(frame: &mut Frame, area: Rect, value: &str) {
+/// Draws the revealed secret value panel, naming `hide_key` in its title.
+pub fn draw_secret_value(
+    frame: &mut Frame,
+    area: Rect,
+    value: &str,
+    hide_key: Option<&str>,
+    theme: &Theme,
+) {
+    let mut title = vec![
+        Span::styled(" ", Style::default()),
+        Span::styled("", Style::default().fg(theme.warning)),
+        Span::styled(" Secret Value ", Style::default().fg(theme.warning).bold()),
+    ];
+    if let Some(key) = hide_key {
+        title.extend([
+            Span::styled("- press ", Style::default().fg(theme.muted)),
+            Span::styled(key.to_string(), Style::default().fg(theme.key).bold()),
+            Span::styled(" to hide ", Style::default().fg(theme.muted)),
+        ]);
+    }
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::WARNING))
+        .border_style(Style::default().fg(theme.warning))
         .border_set(symbols::border::ROUNDED)
-        .title(Line::from(vec![
-            Span::styled(" ", Style::default()),
-            Span::styled("", Style::default().fg(colors::WARNING)),
-            Span::styled(
-                " Secret Value ",
-                Style::default().fg(colors::WARNING).bold(),
-            ),
-            Span::styled("- press ", Style::default().fg(colors::MUTED)),
-            Span::styled("s", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" to hide ", Style::default().fg(colors::MUTED)),
-        ]))
+        .title(Line::from(title))
         .padding(Padding::horizontal(1));
 
     let content = Paragraph::new(value)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .wrap(Wrap { trim: false })
         .block(block);
 
@@ -1,18 +1,21 @@
-//! Dialog rendering (input, confirm, project selector).
+//! Dialog rendering (input, confirm, conflict, project, configuration and location
+//! selectors).
 
 use ratatui::{
     layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
     symbols,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::{App, ConfirmAction, InputMode};
+use crate::app::{App, ConfirmAction, Conflict, InputMode};
+use crate::connection::location_label;
 use crate::constants::dialog;
+use crate::event::Action;
+use crate::keymap::Scope;
 
-use super::colors;
 use super::utils::centered_rect;
 
 /// Block cursor character for input fields.
@@ -23,9 +26,47 @@ pub(crate) const INPUT_INDICATOR: &str = "› ";
 
 /// Draws the text input dialog.
 pub fn draw_input_dialog(frame: &mut Frame, mode: &InputMode, app: &App) {
+    let theme = &app.theme;
     let (title, prompt, icon) = match mode {
-        InputMode::NewSecretName => ("Create New Secret", "Enter a name for your secret:", ""),
-        InputMode::NewVersionValue => ("Add New Version", "Enter the secret value:", ""),
+        InputMode::NewSecretName { location } => (
+            "Create New Secret",
+            format!(
+                "Enter a name for your secret ({}):",
+                location_label(location.as_deref())
+            ),
+            "",
+        ),
+        InputMode::NewVersionValue => ("Add New Version", "Enter the secret value:".into(), ""),
+        InputMode::GrantMember => (
+            "Grant Access",
+            "Member and optional role (default secretmanager.secretAccessor):".into(),
+            "",
+        ),
+        InputMode::Expiration => (
+            "Set Expiration",
+            "Expire in (30d), extend by (+7d), on (2030-12-31) or never:".into(),
+            "",
+        ),
+        InputMode::DestroyTtl => (
+            "Set Destroy TTL",
+            "Keep destroyed versions disabled for (e.g. 7d), or off:".into(),
+            "",
+        ),
+        InputMode::SecretsFilter => (
+            "Filter Secrets",
+            "API filter, e.g. labels.team=payments (↑↓ recent, empty clears):".into(),
+            "",
+        ),
+        InputMode::VersionsFilter => (
+            "Filter Versions",
+            "API filter, e.g. state:ENABLED (↑↓ recent, empty clears):".into(),
+            "",
+        ),
+        InputMode::BindTag => (
+            "Bind Tag",
+            "Tag value as parent/key/value (123456789/env/prod) or tagValues/ID:".into(),
+            "",
+        ),
     };
 
     let area = centered_rect(dialog::INPUT_WIDTH, dialog::INPUT_HEIGHT, frame.area());
@@ -35,13 +76,13 @@ pub fn draw_input_dialog(frame: &mut Frame, mode: &InputMode, app: &App) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::PRIMARY))
+        .border_style(Style::default().fg(theme.primary))
         .border_set(symbols::border::DOUBLE)
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled(icon, Style::default().fg(colors::PRIMARY)),
+            Span::styled(icon, Style::default().fg(theme.primary)),
             Span::styled(" ", Style::default()),
-            Span::styled(title, Style::default().fg(Color::White).bold()),
+            Span::styled(title, Style::default().fg(theme.text).bold()),
             Span::styled(" ", Style::default()),
         ]))
         .padding(Padding::uniform(1));
@@ -57,30 +98,30 @@ pub fn draw_input_dialog(frame: &mut Frame, mode: &InputMode, app: &App) {
     // Build the content
     let content = vec![
         Line::from(""),
-        Line::from(Span::styled(prompt, Style::default().fg(Color::White))),
+        Line::from(Span::styled(prompt, Style::default().fg(theme.text))),
         Line::from(""),
         Line::from(vec![
             Span::styled(
                 format!("  {INPUT_INDICATOR}"),
-                Style::default().fg(colors::MUTED),
+                Style::default().fg(theme.muted),
             ),
-            Span::styled(before_cursor, Style::default().fg(Color::White)),
+            Span::styled(before_cursor, Style::default().fg(theme.text)),
             Span::styled(
                 BLOCK_CURSOR,
                 Style::default()
-                    .fg(colors::PRIMARY)
+                    .fg(theme.primary)
                     .add_modifier(Modifier::SLOW_BLINK),
             ),
-            Span::styled(after_cursor, Style::default().fg(Color::White)),
+            Span::styled(after_cursor, Style::default().fg(theme.text)),
         ]),
         Line::from(""),
         Line::from(""),
         Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled("Enter", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" submit  ", Style::default().fg(colors::MUTED)),
-            Span::styled("Esc", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" cancel", Style::default().fg(colors::MUTED)),
+            Span::styled("Enter", Style::default().fg(theme.key).bold()),
+            Span::styled(" submit  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.key).bold()),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ]),
     ];
 
@@ -89,24 +130,93 @@ pub fn draw_input_dialog(frame: &mut Frame, mode: &InputMode, app: &App) {
     frame.render_widget(input_widget, area);
 }
 
+/// Names the location of a regional secret in a dialog (` in europe-west1`);
+/// global secrets are not qualified.
+fn in_location(location: Option<&str>) -> String {
+    location.map_or_else(String::new, |location| format!(" in {location}"))
+}
+
 /// Draws the confirmation dialog.
-pub fn draw_confirm_dialog(frame: &mut Frame, action: &ConfirmAction) {
+///
+/// Destructive actions are confirmed by typing a phrase (usually the secret
+/// name); Enter only takes effect once the input matches.
+pub fn draw_confirm_dialog(frame: &mut Frame, action: &ConfirmAction, app: &App) {
+    let theme = &app.theme;
     let (title, message, icon) = match action {
-        ConfirmAction::DeleteSecret(name) => (
+        ConfirmAction::DeleteSecret {
+            secret: name,
+            location,
+            ..
+        } => (
             "Delete Secret",
             format!(
-                "Are you sure you want to delete '{name}'?\n\nThis will permanently delete the secret and ALL its versions.\nThis action cannot be undone!"
+                "Are you sure you want to delete '{name}'{}?\n\nThis will permanently delete the secret and ALL its versions.\nThis action cannot be undone!",
+                in_location(location.as_deref())
             ),
             "",
         ),
-        ConfirmAction::DestroyVersion(secret, version) => (
+        ConfirmAction::DestroyVersion {
+            secret,
+            version,
+            location,
+            ..
+        } => (
             "Destroy Version",
+            match app
+                .current_secret
+                .as_ref()
+                .and_then(|s| s.version_destroy_ttl.as_deref())
+            {
+                Some(ttl) => format!(
+                    "Are you sure you want to destroy version {version} of '{secret}'{}?\n\nThe version is disabled now and destroyed in {ttl}.{}",
+                    in_location(location.as_deref()),
+                    app.keymap
+                        .key(Scope::SecretDetail, &Action::Enable)
+                        .map_or_else(String::new, |key| format!(
+                            "\nUntil then, press {key} on it to cancel."
+                        ))
+                ),
+                None => format!(
+                    "Are you sure you want to destroy version {version} of '{secret}'{}?\n\nThe secret data will be permanently destroyed.\nThis action cannot be undone!",
+                    in_location(location.as_deref())
+                ),
+            },
+            "",
+        ),
+        ConfirmAction::RemoveIamMember {
+            secret,
+            role,
+            member,
+            ..
+        } => (
+            "Revoke Access",
+            format!(
+                "Remove {member} from {role} on '{secret}'?\n\nAccess granted through the project is not affected."
+            ),
+            "",
+        ),
+        ConfirmAction::UnbindTag { secret, binding } => (
+            "Unbind Tag",
+            format!(
+                "Unbind {} from '{secret}'?\n\nPolicies conditioned on this tag no longer apply to the secret.",
+                binding.label()
+            ),
+            "",
+        ),
+        ConfirmAction::GrantPublicAccess {
+            secret,
+            role,
+            member,
+        } => (
+            "Make Secret Public",
             format!(
-                "Are you sure you want to destroy version {version} of '{secret}'?\n\nThe secret data will be permanently destroyed.\nThis action cannot be undone!"
+                "Grant {role} to {member} on '{secret}'?\n\n{member} includes everyone, not just your organization.\nAnyone could then use this secret!"
             ),
             "",
         ),
     };
+    let phrase = app.confirm_phrase(action);
+    let matches = app.input_buffer == phrase;
 
     let area = centered_rect(dialog::CONFIRM_WIDTH, dialog::CONFIRM_HEIGHT, frame.area());
 
@@ -115,30 +225,69 @@ pub fn draw_confirm_dialog(frame: &mut Frame, action: &ConfirmAction) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::ERROR))
+        .border_style(Style::default().fg(theme.error))
         .border_set(symbols::border::DOUBLE)
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled(icon, Style::default().fg(colors::ERROR)),
+            Span::styled(icon, Style::default().fg(theme.error)),
             Span::styled(" ", Style::default()),
-            Span::styled(title, Style::default().fg(colors::ERROR).bold()),
+            Span::styled(title, Style::default().fg(theme.error).bold()),
             Span::styled(" ", Style::default()),
         ]))
         .padding(Padding::uniform(1));
 
-    let content = vec![
-        Line::from(""),
-        Line::from(Span::styled(&message, Style::default().fg(colors::WARNING))),
+    // Split the input buffer at cursor position for rendering
+    let (before_cursor, after_cursor): (String, String) = {
+        let chars: Vec<char> = app.input_buffer.chars().collect();
+        let before: String = chars.iter().take(app.cursor_position).collect();
+        let after: String = chars.iter().skip(app.cursor_position).collect();
+        (before, after)
+    };
+
+    let mut content: Vec<Line> = vec![Line::from("")];
+    content.extend(message.lines().map(|line| {
+        Line::from(Span::styled(
+            line.to_string(),
+            Style::default().fg(theme.warning),
+        ))
+    }));
+    content.extend([
         Line::from(""),
+        Line::from(vec![
+            Span::styled("Type ", Style::default().fg(theme.text)),
+            Span::styled(phrase, Style::default().fg(theme.error).bold()),
+            Span::styled(" to confirm:", Style::default().fg(theme.text)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("  {INPUT_INDICATOR}"),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(before_cursor, Style::default().fg(theme.text)),
+            Span::styled(
+                BLOCK_CURSOR,
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ),
+            Span::styled(after_cursor, Style::default().fg(theme.text)),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled("Enter", Style::default().fg(colors::ERROR).bold()),
-            Span::styled(" confirm deletion  ", Style::default().fg(colors::MUTED)),
-            Span::styled("Esc", Style::default().fg(colors::KEY).bold()),
-            Span::styled(" cancel", Style::default().fg(colors::MUTED)),
+            Span::styled(
+                "Enter",
+                if matches {
+                    Style::default().fg(theme.error).bold()
+                } else {
+                    Style::default().fg(theme.muted)
+                },
+            ),
+            Span::styled(" confirm deletion  ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.key).bold()),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ]),
-    ];
+    ]);
 
     let confirm_widget = Paragraph::new(content)
         .wrap(Wrap { trim: false })
@@ -147,8 +296,86 @@ pub fn draw_confirm_dialog(frame: &mut Frame, action: &ConfirmAction) {
     frame.render_widget(confirm_widget, area);
 }
 
+/// Draws the dialog for a write rejected because its target changed.
+///
+/// Shows the target as now stored; Enter retries against that state, or
+/// asks again for the typed confirmation of a delete or destroy.
+pub fn draw_conflict_dialog(frame: &mut Frame, conflict: &Conflict, app: &App) {
+    let theme = &app.theme;
+    let write = &conflict.write;
+    let target = match &write.version {
+        Some(version) => format!("Version {version} of '{}'", write.secret),
+        None => format!("'{}'", write.secret),
+    };
+
+    let area = centered_rect(dialog::CONFIRM_WIDTH, dialog::CONFIRM_HEIGHT, frame.area());
+
+    // Clear the background
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .border_set(symbols::border::DOUBLE)
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("", Style::default().fg(theme.warning)),
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                "Changed Since Loaded",
+                Style::default().fg(theme.warning).bold(),
+            ),
+            Span::styled(" ", Style::default()),
+        ]))
+        .padding(Padding::uniform(1));
+
+    let mut content: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{target} was changed by someone else. It is now:"),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+    ];
+    content.extend(conflict.current.iter().map(|(field, value)| {
+        Line::from(vec![
+            Span::styled(format!("  {field:<12}"), Style::default().fg(theme.muted)),
+            Span::styled(value.clone(), Style::default().fg(theme.secondary)),
+        ])
+    }));
+    content.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Retry and {} anyway?", write.kind.verb()),
+            Style::default().fg(theme.warning),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled("Enter", Style::default().fg(theme.key).bold()),
+            Span::styled(
+                if conflict.reconfirm().is_some() {
+                    " confirm again  "
+                } else {
+                    " retry  "
+                },
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled("Esc", Style::default().fg(theme.key).bold()),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ]),
+    ]);
+
+    let conflict_widget = Paragraph::new(content)
+        .wrap(Wrap { trim: false })
+        .block(block);
+
+    frame.render_widget(conflict_widget, area);
+}
+
 /// Draws the project selector dialog.
 pub fn draw_project_selector(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let area = centered_rect(
         dialog::PROJECT_SELECTOR_WIDTH,
         dialog::PROJECT_SELECTOR_HEIGHT,
@@ -172,23 +399,20 @@ pub fn draw_project_selector(frame: &mut Frame, app: &App) {
     // Outer block
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::PRIMARY))
+        .border_style(Style::default().fg(theme.primary))
         .border_set(symbols::border::DOUBLE)
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled("", Style::default().fg(colors::ACCENT)),
-            Span::styled(" Select Project ", Style::default().fg(Color::White).bold()),
+            Span::styled("", Style::default().fg(theme.accent)),
+            Span::styled(" Select Project ", Style::default().fg(theme.text).bold()),
         ]));
 
     frame.render_widget(block, area);
 
     // Title/hint
     let hint = Paragraph::new(Line::from(vec![
-        Span::styled("Current: ", Style::default().fg(colors::MUTED)),
-        Span::styled(
-            &app.project_id,
-            Style::default().fg(colors::SECONDARY).bold(),
-        ),
+        Span::styled("Current: ", Style::default().fg(theme.muted)),
+        Span::styled(&app.project_id, Style::default().fg(theme.secondary).bold()),
     ]));
     frame.render_widget(hint, chunks[0]);
 
@@ -202,9 +426,7 @@ pub fn draw_project_selector(frame: &mut Frame, app: &App) {
             let is_current = project.project_id == app.project_id;
 
             let style = if is_selected {
-                Style::default()
-                    .bg(colors::SELECTION)
-                    .fg(colors::SELECTION_TEXT)
+                theme.selected()
             } else {
                 Style::default()
             };
@@ -218,25 +440,25 @@ pub fn draw_project_selector(frame: &mut Frame, app: &App) {
             };
 
             let current_marker = if is_current {
-                Span::styled(" (current)", Style::default().fg(colors::SUCCESS))
+                Span::styled(" (current)", Style::default().fg(theme.success))
             } else {
                 Span::raw("")
             };
 
             let content = Line::from(vec![
-                Span::styled(number, Style::default().fg(colors::ACCENT)),
+                Span::styled(number, Style::default().fg(theme.accent)),
                 Span::styled("  ", style),
                 Span::styled(
                     if is_selected { "▸" } else { " " },
                     Style::default().fg(if is_current {
-                        colors::SUCCESS
+                        theme.success
                     } else {
-                        colors::PRIMARY
+                        theme.primary
                     }),
                 ),
                 Span::styled(" ", style),
                 Span::styled(project_id, style.add_modifier(Modifier::BOLD)),
-                Span::styled(display_name, style.fg(colors::MUTED)),
+                Span::styled(display_name, style.fg(theme.muted)),
                 current_marker,
             ]);
 
@@ -252,12 +474,221 @@ pub fn draw_project_selector(frame: &mut Frame, app: &App) {
 
     // Footer with commands
     let footer = Paragraph::new(Line::from(vec![
-        Span::styled("j/k", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" navigate  ", Style::default().fg(colors::MUTED)),
-        Span::styled("Enter", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" select  ", Style::default().fg(colors::MUTED)),
-        Span::styled("Esc", Style::default().fg(colors::KEY).bold()),
-        Span::styled(" cancel", Style::default().fg(colors::MUTED)),
+        Span::styled("j/k", Style::default().fg(theme.key).bold()),
+        Span::styled(" navigate  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.key).bold()),
+        Span::styled(" select  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.key).bold()),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ]));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// Draws the gcloud configuration selector dialog.
+pub fn draw_config_selector(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let area = centered_rect(
+        dialog::PROJECT_SELECTOR_WIDTH,
+        dialog::PROJECT_SELECTOR_HEIGHT,
+        frame.area(),
+    );
+
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer with commands
+        ])
+        .margin(1)
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary))
+        .border_set(symbols::border::DOUBLE)
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("", Style::default().fg(theme.accent)),
+            Span::styled(
+                " gcloud Configurations ",
+                Style::default().fg(theme.text).bold(),
+            ),
+        ]));
+    frame.render_widget(block, area);
+
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Current: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            &app.gcloud_config,
+            Style::default().fg(theme.secondary).bold(),
+        ),
+    ]));
+    frame.render_widget(hint, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .gcloud_configs
+        .iter()
+        .enumerate()
+        .map(|(idx, config)| {
+            let is_selected = app.configs_state.selected() == Some(idx);
+            let is_current = config.name == app.gcloud_config;
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+
+            let details = [config.project.as_deref(), config.account.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "▸" } else { " " },
+                    Style::default().fg(if is_current {
+                        theme.success
+                    } else {
+                        theme.primary
+                    }),
+                ),
+                Span::styled(" ", style),
+                Span::styled(config.name.clone(), style.add_modifier(Modifier::BOLD)),
+            ];
+            if !details.is_empty() {
+                spans.push(Span::styled(format!("  {details}"), style.fg(theme.muted)));
+            }
+            if is_current {
+                spans.push(Span::styled(
+                    " (current)",
+                    Style::default().fg(theme.success),
+                ));
+            }
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default())
+        .highlight_symbol("");
+    frame.render_stateful_widget(list, chunks[1], &mut app.configs_state.clone());
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.key).bold()),
+        Span::styled(" navigate  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.key).bold()),
+        Span::styled(" select  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.key).bold()),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ]));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// Draws the location selector; several locations can be marked at once.
+pub fn draw_location_selector(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let area = centered_rect(
+        dialog::PROJECT_SELECTOR_WIDTH,
+        dialog::PROJECT_SELECTOR_HEIGHT,
+        frame.area(),
+    );
+
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer with commands
+        ])
+        .margin(1)
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary))
+        .border_set(symbols::border::DOUBLE)
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("", Style::default().fg(theme.accent)),
+            Span::styled(" Secret Locations ", Style::default().fg(theme.text).bold()),
+        ]));
+    frame.render_widget(block, area);
+
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Marked: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            app.location_selection.len().to_string(),
+            Style::default().fg(theme.secondary).bold(),
+        ),
+        Span::styled(
+            " - new secrets go to the first marked location",
+            Style::default().fg(theme.muted),
+        ),
+    ]));
+    frame.render_widget(hint, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .available_locations
+        .iter()
+        .enumerate()
+        .map(|(idx, location)| {
+            let is_selected = app.locations_state.selected() == Some(idx);
+            let is_marked = app.location_selection.contains(location);
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+
+            let mut spans = vec![
+                Span::styled(
+                    if is_selected { "▸" } else { " " },
+                    Style::default().fg(theme.primary),
+                ),
+                Span::styled(" ", style),
+                Span::styled(
+                    if is_marked { "[x] " } else { "[ ] " },
+                    style.fg(if is_marked {
+                        theme.success
+                    } else {
+                        theme.muted
+                    }),
+                ),
+                Span::styled(
+                    location_label(location.as_deref()).to_string(),
+                    style.add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if app.locations.contains(location) {
+                spans.push(Span::styled(
+                    " (current)",
+                    Style::default().fg(theme.success),
+                ));
+            }
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default())
+        .highlight_symbol("");
+    frame.render_stateful_widget(list, chunks[1], &mut app.locations_state.clone());
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.key).bold()),
+        Span::styled(" navigate  ", Style::default().fg(theme.muted)),
+        Span::styled("Space", Style::default().fg(theme.key).bold()),
+        Span::styled(" mark  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.key).bold()),
+        Span::styled(" apply  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.key).bold()),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
     ]));
     frame.render_widget(footer, chunks[2]);
 }
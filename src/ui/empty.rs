@@ -9,7 +9,7 @@ use ratatui::{
     Frame,
 };
 
-use super::colors;
+use super::theme::Theme;
 
 /// Draws an empty state with icon, title, and description.
 pub fn draw_empty_state(
@@ -18,28 +18,26 @@ pub fn draw_empty_state(
     title: &str,
     action: &str,
     description: &str,
+    theme: &Theme,
 ) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(colors::BORDER))
+        .border_style(Style::default().fg(theme.border))
         .border_set(symbols::border::ROUNDED);
 
     let content = vec![
         Line::from(""),
         Line::from(""),
-        Line::from(Span::styled("", Style::default().fg(colors::ACCENT))),
+        Line::from(Span::styled("", Style::default().fg(theme.accent))),
         Line::from(""),
         Line::from(Span::styled(
             title,
-            Style::default().fg(colors::PRIMARY).bold(),
+            Style::default().fg(theme.primary).bold(),
         )),
         Line::from(""),
-        Line::from(Span::styled(action, Style::default().fg(colors::SUCCESS))),
+        Line::from(Span::styled(action, Style::default().fg(theme.success))),
         Line::from(""),
-        Line::from(Span::styled(
-            description,
-            Style::default().fg(colors::MUTED),
-        )),
+        Line::from(Span::styled(description, Style::default().fg(theme.muted))),
     ];
 
     let paragraph = Paragraph::new(content)
@@ -9,30 +9,23 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::connection::location_label;
 
-use super::colors;
+use super::theme::Theme;
 
-/// Returns a randomly selected logo color (selected once at startup).
-fn logo_color() -> Color {
-    use std::sync::OnceLock;
-    static COLOR: OnceLock<Color> = OnceLock::new();
-    *COLOR.get_or_init(|| {
-        const COLORS: [Color; 4] = [
-            Color::Rgb(56, 189, 248),  // Cyan
-            Color::Rgb(244, 114, 182), // Pink
-            Color::Rgb(192, 132, 252), // Purple
-            Color::Rgb(52, 211, 153),  // Emerald
-        ];
-        COLORS[std::process::id() as usize % COLORS.len()]
-    })
+/// Returns a randomly selected logo color (stable for the process lifetime).
+fn logo_color(theme: &Theme) -> Color {
+    let colors = theme.logo_colors();
+    colors[std::process::id() as usize % colors.len()]
 }
 
 /// Draws the header with ASCII art logo and subtitle.
 pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
-    let border_style = Style::default().fg(colors::BORDER);
-    let dim_style = Style::default().fg(Color::Rgb(55, 65, 81));
-    let muted_style = Style::default().fg(Color::Rgb(75, 85, 99));
-    let logo_style = Style::default().fg(logo_color()).bold();
+    let theme = &app.theme;
+    let border_style = Style::default().fg(theme.border);
+    let dim_style = Style::default().fg(theme.dim);
+    let muted_style = Style::default().fg(theme.subtle);
+    let logo_style = Style::default().fg(logo_color(theme)).bold();
 
     // Status indicator
     let status = if app.is_loading {
@@ -41,26 +34,26 @@ pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 " ◈ ",
                 Style::default()
-                    .fg(colors::WARNING)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::SLOW_BLINK),
             ),
-            Span::styled("SYNC", Style::default().fg(colors::WARNING).bold()),
+            Span::styled("SYNC", Style::default().fg(theme.warning).bold()),
             Span::styled(" ┃", border_style),
         ]
     } else {
         vec![
             Span::styled("┃", border_style),
-            Span::styled(" ◈ ", Style::default().fg(colors::SUCCESS)),
-            Span::styled("Google Cloud", Style::default().fg(colors::SUCCESS).bold()),
+            Span::styled(" ◈ ", Style::default().fg(theme.success)),
+            Span::styled("Google Cloud", Style::default().fg(theme.success).bold()),
             Span::styled(" ┃", border_style),
         ]
     };
 
     // Top border with status indicator
     let line0 = Line::from(vec![
-        Span::styled("┏", Style::default().fg(colors::ACCENT)),
+        Span::styled("┏", Style::default().fg(theme.accent)),
         Span::styled("━━━━━━━━━━━━━━━━━━━━━━━", border_style),
-        Span::styled("┓", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┓", Style::default().fg(theme.primary)),
         Span::styled("░▒▓", dim_style),
         status[0].clone(),
         status[1].clone(),
@@ -72,66 +65,104 @@ pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
 
     // Logo line 1 + info panel top
     let line1 = Line::from(vec![
-        Span::styled("┃", Style::default().fg(colors::ACCENT)),
+        Span::styled("┃", Style::default().fg(theme.accent)),
         Span::styled(" ▄████ ▄█▀▀▀ ███▄███▄  ", logo_style),
-        Span::styled("┃", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┃", Style::default().fg(theme.primary)),
         Span::styled("  ╭───────────────────────────────╮", border_style),
     ]);
 
     // Logo line 2 + SECRET::MANAGER title
     let line2 = Line::from(vec![
-        Span::styled("┃", Style::default().fg(colors::ACCENT)),
+        Span::styled("┃", Style::default().fg(theme.accent)),
         Span::styled(" ██ ██ ▀███▄ ██ ██ ██  ", logo_style),
-        Span::styled("┃", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┃", Style::default().fg(theme.primary)),
         Span::styled("  │ ", border_style),
-        Span::styled("◆", Style::default().fg(colors::ACCENT)),
-        Span::styled(" SECRET", Style::default().fg(colors::PRIMARY).bold()),
+        Span::styled("◆", Style::default().fg(theme.accent)),
+        Span::styled(" SECRET", Style::default().fg(theme.primary).bold()),
         Span::styled("::", muted_style),
-        Span::styled("MANAGER", Style::default().fg(colors::KEY).bold()),
+        Span::styled("MANAGER", Style::default().fg(theme.key).bold()),
         Span::styled(" ▸▸ ", muted_style),
-        Span::styled("TUI", Style::default().fg(colors::ACCENT).bold()),
+        Span::styled("TUI", Style::default().fg(theme.accent).bold()),
         Span::styled(" ◆    │", border_style),
     ]);
 
     // Logo line 3 + info tags
     let line3 = Line::from(vec![
-        Span::styled("┃", Style::default().fg(colors::ACCENT)),
+        Span::styled("┃", Style::default().fg(theme.accent)),
         Span::styled(" ▀████ ▄▄▄█▀ ██ ██ ██  ", logo_style),
-        Span::styled("┃", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┃", Style::default().fg(theme.primary)),
         Span::styled("  │ ", border_style),
-        Span::styled("▪", Style::default().fg(colors::SECONDARY)),
-        Span::styled(" GCP  ", Style::default().fg(colors::MUTED)),
+        Span::styled("▪", Style::default().fg(theme.secondary)),
+        Span::styled(" GCP  ", Style::default().fg(theme.muted)),
         Span::styled("│", dim_style),
-        Span::styled(" ▪", Style::default().fg(colors::SUCCESS)),
-        Span::styled(" SECRETS ", Style::default().fg(colors::MUTED)),
+        Span::styled(" ▪", Style::default().fg(theme.success)),
+        Span::styled(" SECRETS ", Style::default().fg(theme.muted)),
         Span::styled("│", dim_style),
-        Span::styled(" ▪", Style::default().fg(colors::WARNING)),
+        Span::styled(" ▪", Style::default().fg(theme.warning)),
         Span::styled(format!(" v{} │", env!("CARGO_PKG_VERSION")), border_style),
     ]);
 
     // Logo line 4 (G tail) + info panel bottom
     let line4 = Line::from(vec![
-        Span::styled("┃", Style::default().fg(colors::ACCENT)),
+        Span::styled("┃", Style::default().fg(theme.accent)),
         Span::styled("    ██                 ", logo_style),
-        Span::styled("┃", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┃", Style::default().fg(theme.primary)),
         Span::styled("  ╰───────────────────────────────╯", border_style),
     ]);
 
     // Logo line 5 (G base) + project info
     let line5 = Line::from(vec![
-        Span::styled("┗", Style::default().fg(colors::ACCENT)),
+        Span::styled("┗", Style::default().fg(theme.accent)),
         Span::styled("━━▀▀▀", logo_style),
         Span::styled("━━━━━━━━━━━━━━━━━━", border_style),
-        Span::styled("┛", Style::default().fg(colors::PRIMARY)),
+        Span::styled("┛", Style::default().fg(theme.primary)),
         Span::styled("  ╾╢", border_style),
-        Span::styled(" ⬢  ", Style::default().fg(colors::SECONDARY)),
-        Span::styled(
-            &app.project_id,
-            Style::default().fg(colors::SECONDARY).bold(),
-        ),
+        Span::styled(" ⬢  ", Style::default().fg(theme.secondary)),
+        Span::styled(&app.project_id, Style::default().fg(theme.secondary).bold()),
+        Span::styled(locations_suffix(app), Style::default().fg(theme.muted)),
         Span::styled(" ╟╼", border_style),
     ]);
+    // Who we act as, and where the credentials come from
+    let source = if app.connection.impersonate_service_account.is_some() {
+        format!(" (impersonated via {})", app.credential_source)
+    } else {
+        format!(" ({})", app.credential_source)
+    };
+    let mut spans = line5.spans;
+    spans.push(Span::styled(" as ", muted_style));
+    spans.push(Span::styled(
+        app.principal.as_deref().unwrap_or("unknown account"),
+        Style::default().fg(theme.accent),
+    ));
+    spans.push(Span::styled(source, muted_style));
+    let line5 = Line::from(spans);
+    let line5 = if app.is_read_only() {
+        let mut spans = line5.spans;
+        spans.push(Span::styled(" ", Style::default()));
+        spans.push(Span::styled(
+            " READ-ONLY ",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+        ));
+        Line::from(spans)
+    } else {
+        line5
+    };
 
     let header = Paragraph::new(vec![line0, line1, line2, line3, line4, line5]);
     frame.render_widget(header, area);
 }
+
+/// Lists the browsed locations after the project, unless only global is browsed.
+fn locations_suffix(app: &App) -> String {
+    if app.locations == [None] {
+        return String::new();
+    }
+    let names: Vec<&str> = app
+        .locations
+        .iter()
+        .map(|l| location_label(l.as_deref()))
+        .collect();
+    format!(" @ {}", names.join(", "))
+}
@@ -1,7 +1,7 @@
 //! Help overlay rendering.
 
 use ratatui::{
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     symbols,
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
@@ -9,31 +9,32 @@ use ratatui::{
 };
 
 use crate::constants::dialog;
+use crate::keymap::{Keymap, Section};
 
-use super::colors;
+use super::theme::Theme;
 use super::utils::centered_rect;
 
 /// Draws a help overlay popup.
-pub fn draw_help_overlay(frame: &mut Frame) {
+pub fn draw_help_overlay(frame: &mut Frame, keymap: &Keymap, theme: &Theme) {
     let area = centered_rect(dialog::HELP_WIDTH, dialog::HELP_HEIGHT, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
 
-    let help_text = get_help_text();
+    let help_text = get_help_text(keymap, theme);
 
     let help = Paragraph::new(help_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(colors::PRIMARY))
+                .border_style(Style::default().fg(theme.primary))
                 .border_set(symbols::border::DOUBLE)
                 .title(Line::from(vec![
                     Span::styled(" ", Style::default()),
-                    Span::styled(" Help ", Style::default().fg(Color::White).bold()),
+                    Span::styled(" Help ", Style::default().fg(theme.text).bold()),
                     Span::styled(
                         "- Press any key to close ",
-                        Style::default().fg(colors::MUTED),
+                        Style::default().fg(theme.muted),
                     ),
                 ]))
                 .style(Style::default()),
@@ -43,138 +44,49 @@ pub fn draw_help_overlay(frame: &mut Frame) {
     frame.render_widget(help, area);
 }
 
-/// Returns the help text content.
-fn get_help_text() -> Text<'static> {
-    let key_style = Style::default().fg(colors::KEY).bold();
-    let desc_style = Style::default().fg(Color::White);
-    let section_style = Style::default().fg(colors::PRIMARY).bold();
+/// Width of the key column in the help overlay.
+const KEY_COLUMN_WIDTH: usize = 16;
 
-    Text::from(vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  ", Style::default()),
-            Span::styled("", section_style),
-            Span::styled(" NAVIGATION", section_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("j  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("Down  ", key_style),
-            Span::styled("Move to next item", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("k  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("Up    ", key_style),
-            Span::styled("Move to previous item", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("g  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("Home  ", key_style),
-            Span::styled("Jump to first item", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("G  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("End   ", key_style),
-            Span::styled("Jump to last item", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("Enter     ", key_style),
-            Span::styled("Select / View details", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("Esc       ", key_style),
-            Span::styled("Go back to previous view", desc_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  ", Style::default()),
-            Span::styled("", section_style),
-            Span::styled(" SECRETS", section_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("n         ", key_style),
-            Span::styled("Create a new secret", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("d         ", key_style),
-            Span::styled("Delete selected secret", desc_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  ", Style::default()),
-            Span::styled("", section_style),
-            Span::styled(" VERSIONS", section_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("a         ", key_style),
-            Span::styled("Add a new version with value", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("s         ", key_style),
-            Span::styled("Show / Hide secret value", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("c         ", key_style),
-            Span::styled("Copy secret value to clipboard", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("e         ", key_style),
-            Span::styled("Enable a disabled version", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("x         ", key_style),
-            Span::styled("Disable an enabled version", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("d         ", key_style),
-            Span::styled("Destroy selected version", desc_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
+/// Builds the help text from the keymap.
+fn get_help_text(keymap: &Keymap, theme: &Theme) -> Text<'static> {
+    let key_style = Style::default().fg(theme.key).bold();
+    let desc_style = Style::default().fg(theme.text);
+    let section_style = Style::default().fg(theme.primary).bold();
+    let or_style = Style::default().fg(theme.muted);
+
+    let mut lines = vec![Line::from("")];
+    for section in Section::ALL {
+        let entries = keymap.help(section);
+        if entries.is_empty() {
+            continue;
+        }
+
+        lines.push(Line::from(vec![
             Span::styled("  ", Style::default()),
             Span::styled("", section_style),
-            Span::styled(" GENERAL", section_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("r         ", key_style),
-            Span::styled("Refresh current view", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("?  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("F1    ", key_style),
-            Span::styled("Show this help", desc_style),
-        ]),
-        Line::from(vec![
-            Span::styled("    ", Style::default()),
-            Span::styled("q  ", key_style),
-            Span::styled("or ", Style::default().fg(colors::MUTED)),
-            Span::styled("Ctrl+C", key_style),
-            Span::styled(" Quit application", desc_style),
-        ]),
-        Line::from(""),
-    ])
+            Span::styled(format!(" {}", section.title()), section_style),
+        ]));
+        lines.push(Line::from(""));
+
+        for (keys, description) in entries {
+            let mut spans = vec![Span::styled("    ", Style::default())];
+            let mut width = 0;
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::styled(" or ", or_style));
+                    width += 4;
+                }
+                width += key.chars().count();
+                spans.push(Span::styled(key, key_style));
+            }
+            spans.push(Span::raw(
+                " ".repeat(KEY_COLUMN_WIDTH.saturating_sub(width).max(1)),
+            ));
+            spans.push(Span::styled(description, desc_style));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+    }
+
+    Text::from(lines)
 }
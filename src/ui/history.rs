@@ -0,0 +1,115 @@
+//! Audit history view rendering.
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Padding},
+    Frame,
+};
+
+use crate::app::App;
+use crate::audit::Outcome;
+
+use super::empty::draw_empty_state;
+
+/// Draws the audit history, newest first.
+pub fn draw_history(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    if app.history.is_empty() {
+        draw_empty_state(
+            frame,
+            area,
+            "No history yet",
+            "Changes made with gsmtui are recorded here",
+            &app.audit
+                .path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            theme,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .history
+        .iter()
+        .enumerate()
+        .map(|(idx, record)| {
+            let is_selected = app.history_state.selected() == Some(idx);
+            let base_style = if is_selected {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+            let muted = base_style.fg(if is_selected {
+                theme.selection_text
+            } else {
+                theme.muted
+            });
+
+            let (icon, color) = match record.outcome {
+                Outcome::Success => ("✓", theme.success),
+                Outcome::Failure => ("✗", theme.error),
+            };
+            // Trim RFC 3339 fractional seconds and offset for display
+            let timestamp = record.timestamp.get(..19).unwrap_or(&record.timestamp);
+            let who = record.principal.as_deref().unwrap_or(&record.user);
+
+            let mut spans = vec![
+                Span::styled(timestamp.replace('T', " "), muted),
+                Span::styled("  ", base_style),
+                Span::styled(icon, Style::default().fg(color)),
+                Span::styled(" ", base_style),
+                Span::styled(
+                    format!("{:<16}", record.action.to_string()),
+                    base_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(record.resource.clone(), base_style),
+                Span::styled(
+                    record
+                        .detail
+                        .as_ref()
+                        .map(|d| format!(" ({d})"))
+                        .unwrap_or_default(),
+                    base_style,
+                ),
+                Span::styled(format!("  {who}"), muted),
+            ];
+            if let Some(error) = &record.error {
+                spans.push(Span::styled(
+                    format!("  {error}"),
+                    base_style.fg(if is_selected {
+                        theme.selection_text
+                    } else {
+                        theme.error
+                    }),
+                ));
+            }
+
+            ListItem::new(Line::from(spans)).style(base_style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .border_set(symbols::border::ROUNDED)
+                .title(Line::from(vec![
+                    Span::styled(" ", Style::default()),
+                    Span::styled(
+                        format!("{}", app.history.len()),
+                        Style::default().fg(theme.secondary).bold(),
+                    ),
+                    Span::styled(" changes ", Style::default().fg(theme.text)),
+                ]))
+                .padding(Padding::horizontal(1)),
+        )
+        .highlight_symbol("");
+
+    frame.render_stateful_widget(list, area, &mut app.history_state.clone());
+}
@@ -0,0 +1,167 @@
+//! IAM tab rendering: a secret's role bindings, grouped by role.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::event::Action;
+use crate::iam::is_public_member;
+use crate::keymap::Scope;
+
+use super::empty::draw_empty_state;
+
+/// Draws the IAM policy of the current secret.
+pub fn draw_iam_policy(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(policy) = &app.iam_policy else {
+        draw_empty_state(
+            frame,
+            area,
+            "IAM policy not loaded",
+            &app.keymap
+                .key(Scope::SecretIam, &Action::Refresh)
+                .map_or_else(
+                    || "Refresh to load it".to_string(),
+                    |key| format!("Press '{key}' to load it"),
+                ),
+            "Viewing the policy needs secretmanager.secrets.getIamPolicy",
+            theme,
+        );
+        return;
+    };
+    if policy.bindings.is_empty() {
+        draw_empty_state(
+            frame,
+            area,
+            "No role bindings on this secret",
+            &match app.keymap.key(Scope::SecretIam, &Action::AddMember) {
+                Some(key) if !app.is_read_only() => format!("Press '{key}' to grant a role"),
+                _ => "Access is inherited from the project".to_string(),
+            },
+            "Project-level roles still apply",
+            theme,
+        );
+        return;
+    }
+
+    // Warn above the list when anyone can access the secret
+    let area = if policy.is_public() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let warning = Paragraph::new(Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                " PUBLIC ",
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            ),
+            Span::styled(
+                " allUsers or allAuthenticatedUsers can access this secret",
+                Style::default().fg(theme.error),
+            ),
+        ]));
+        frame.render_widget(warning, chunks[0]);
+        chunks[1]
+    } else {
+        area
+    };
+
+    // One header item per binding followed by its members; the selection
+    // counts members only, so map it onto the list items.
+    let selected = app.iam_state.selected();
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_item = None;
+    let mut row = 0;
+    for binding in &policy.bindings {
+        let mut header = vec![Span::styled(
+            binding.role.clone(),
+            Style::default().fg(theme.accent).bold(),
+        )];
+        if let Some(condition) = &binding.condition {
+            header.push(Span::styled("  if ", Style::default().fg(theme.muted)));
+            header.push(Span::styled(
+                if condition.title.is_empty() {
+                    condition.expression.clone()
+                } else {
+                    condition.title.clone()
+                },
+                Style::default().fg(theme.warning),
+            ));
+            if !condition.title.is_empty() {
+                header.push(Span::styled(
+                    format!("  {}", condition.expression),
+                    Style::default().fg(theme.muted),
+                ));
+            }
+        }
+        items.push(ListItem::new(Line::from(header)));
+
+        for member in &binding.members {
+            let is_selected = selected == Some(row);
+            if is_selected {
+                selected_item = Some(items.len());
+            }
+            let public = is_public_member(member);
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![
+                Span::styled("   ", style),
+                Span::styled(
+                    member.clone(),
+                    style.fg(if is_selected {
+                        theme.selection_text
+                    } else if public {
+                        theme.error
+                    } else {
+                        theme.text
+                    }),
+                ),
+            ];
+            if public {
+                spans.push(Span::styled(
+                    "  (public)",
+                    style.fg(if is_selected {
+                        theme.selection_text
+                    } else {
+                        theme.error
+                    }),
+                ));
+            }
+            items.push(ListItem::new(Line::from(spans)).style(style));
+            row += 1;
+        }
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .border_set(symbols::border::ROUNDED)
+                .title(Line::from(vec![
+                    Span::styled(" ", Style::default()),
+                    Span::styled(
+                        format!("{}", policy.bindings.len()),
+                        Style::default().fg(theme.secondary).bold(),
+                    ),
+                    Span::styled(" role bindings ", Style::default().fg(theme.text)),
+                ]))
+                .padding(Padding::horizontal(1)),
+        )
+        .highlight_symbol("");
+
+    let mut state = ListState::default().with_selected(selected_item);
+    frame.render_stateful_widget(list, area, &mut state);
+}
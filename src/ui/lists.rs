@@ -1,22 +1,35 @@
 //! List rendering for secrets and versions.
 
+use chrono::Utc;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
     symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Padding, Paragraph},
+    widgets::{Block, Borders, Cell, List, ListItem, Padding, Paragraph, Row, Table},
     Frame,
 };
 
 use crate::app::App;
-use crate::secret_client::VersionState;
+use crate::config::Column;
+use crate::connection::location_label;
+use crate::event::Action;
+use crate::expiration::{countdown, format_time, urgency};
+use crate::keymap::Scope;
+use crate::secret_client::{short_kms_name, ReplicationPolicy, SecretInfo, VersionState};
 
-use super::colors;
 use super::empty::draw_empty_state;
+use super::theme::Theme;
+
+/// Blank cells between columns of the secrets table.
+const COLUMN_SPACING: u16 = 2;
+
+/// Widest a column other than the name gets, in cells.
+const MAX_COLUMN_WIDTH: usize = 40;
 
 /// Draws the list of secrets.
 pub fn draw_secrets_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Split into header hint and list
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -29,109 +42,335 @@ pub fn draw_secrets_list(frame: &mut Frame, area: Rect, app: &App) {
     // Draw section hint
     let hint = Paragraph::new(Line::from(vec![
         Span::styled("  ", Style::default()),
-        Span::styled("", Style::default().fg(colors::WARNING)),
+        Span::styled("", Style::default().fg(theme.warning)),
         Span::styled(" ", Style::default()),
-        Span::styled("Secrets", Style::default().fg(colors::PRIMARY).bold()),
+        Span::styled("Secrets", Style::default().fg(theme.primary).bold()),
         Span::styled(
             " - Select a secret to view versions and values",
-            Style::default().fg(colors::MUTED),
+            Style::default().fg(theme.muted),
         ),
     ]));
     frame.render_widget(hint, chunks[0]);
 
     // Handle empty state
+    if app.secrets.is_empty() && app.filter.secrets.is_some() {
+        draw_empty_state(
+            frame,
+            chunks[1],
+            "No secrets match the filter",
+            app.filter.secrets.as_deref().unwrap_or_default(),
+            &clear_filter_hint(app, Scope::SecretsList, "secrets"),
+            &app.theme,
+        );
+        return;
+    }
+    if app.secrets.is_empty() && app.filter.non_cmek {
+        draw_empty_state(
+            frame,
+            chunks[1],
+            "No secrets without CMEK",
+            "Every secret is protected by a customer-managed key",
+            &app.keymap
+                .key(Scope::SecretsList, &Action::ToggleCmekFilter)
+                .map_or_else(
+                    || "Turn off the CMEK filter to list all secrets".to_string(),
+                    |key| format!("Press '{key}' to list all secrets"),
+                ),
+            &app.theme,
+        );
+        return;
+    }
     if app.secrets.is_empty() {
         draw_empty_state(
             frame,
             chunks[1],
             "No secrets found",
-            "Press 'n' to create your first secret",
+            &match app.keymap.key(Scope::SecretsList, &Action::NewSecret) {
+                _ if app.is_read_only() => "Read-only mode - secrets cannot be created".to_string(),
+                Some(key) => format!("Press '{key}' to create your first secret"),
+                None => "Create a secret to get started".to_string(),
+            },
             "",
+            &app.theme,
         );
         return;
     }
 
-    // Create list items from secrets
-    let items: Vec<ListItem> = app
+    // Build the cells first, so that column widths can fit their contents
+    let now = Utc::now().timestamp();
+    let cells: Vec<Vec<Line>> = app
         .secrets
         .iter()
         .enumerate()
         .map(|(idx, secret)| {
             let is_selected = app.secrets_state.selected() == Some(idx);
+            app.config
+                .columns
+                .iter()
+                .map(|column| secret_cell(app, *column, idx, secret, is_selected, now))
+                .collect()
+        })
+        .collect();
 
-            let number = format!("{:>3}", idx + 1);
-            let name = secret.short_name.clone();
-            let date = secret.create_time.clone();
+    // Block borders and padding take two columns on each side
+    let widths = fit_columns(
+        &app.config.columns,
+        &cells,
+        chunks[1].width.saturating_sub(4),
+    );
+    let shown: Vec<usize> = (0..app.config.columns.len())
+        .filter(|i| widths[*i].is_some())
+        .collect();
 
-            let style = if is_selected {
-                Style::default()
-                    .bg(colors::SELECTION)
-                    .fg(colors::SELECTION_TEXT)
+    let header = Row::new(shown.iter().map(|&i| {
+        let column = app.config.columns[i];
+        match app.sort {
+            Some(sort) if sort.column == column => Cell::from(format!(
+                "{} {}",
+                column.title(),
+                if sort.descending { "▼" } else { "▲" }
+            ))
+            .style(Style::default().fg(theme.accent).bold()),
+            _ => Cell::from(column.title()).style(Style::default().fg(theme.muted).bold()),
+        }
+    }));
+
+    let rows: Vec<Row> = cells
+        .into_iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            let style = if app.secrets_state.selected() == Some(idx) {
+                theme.selected()
             } else {
                 Style::default()
             };
-
-            let content = Line::from(vec![
-                Span::styled(number, Style::default().fg(colors::ACCENT)),
-                Span::styled("  ", style),
-                Span::styled(
-                    "",
-                    if is_selected {
-                        Style::default().fg(colors::WARNING)
-                    } else {
-                        Style::default().fg(colors::PRIMARY)
-                    },
-                ),
-                Span::styled(" ", style),
-                Span::styled(name, style.add_modifier(Modifier::BOLD)),
-                Span::styled("  ", style),
-                Span::styled(
-                    date,
-                    style.fg(if is_selected {
-                        colors::SELECTION_TEXT
-                    } else {
-                        colors::MUTED
-                    }),
-                ),
-            ]);
-
-            ListItem::new(content).style(style)
+            let row: Vec<Cell> = row
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| widths[*i].is_some())
+                .map(|(_, line)| Cell::from(line))
+                .collect();
+            Row::new(row).style(style)
         })
         .collect();
 
-    // Create the list widget
-    let list = List::new(items)
+    // The name takes the space other columns leave
+    let constraints = shown.iter().map(|&i| match app.config.columns[i] {
+        Column::Name => Constraint::Fill(1),
+        _ => Constraint::Length(widths[i].unwrap_or_default()),
+    });
+
+    let table = Table::new(rows, constraints)
+        .header(header)
+        .column_spacing(COLUMN_SPACING)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(colors::BORDER))
+                .border_style(Style::default().fg(theme.border))
                 .border_set(symbols::border::ROUNDED)
                 .title(Line::from(vec![
                     Span::styled(" ", Style::default()),
                     Span::styled(
                         format!("{}", app.secrets.len()),
-                        Style::default().fg(colors::SECONDARY).bold(),
+                        Style::default().fg(theme.secondary).bold(),
+                    ),
+                    Span::styled(" secrets ", Style::default().fg(theme.text)),
+                    Span::styled(
+                        if app.filter.non_cmek {
+                            "without CMEK "
+                        } else {
+                            ""
+                        },
+                        Style::default().fg(theme.warning),
                     ),
-                    Span::styled(" secrets ", Style::default().fg(Color::White)),
+                    filter_span(app.filter.secrets.as_deref(), theme),
                 ]))
                 .padding(Padding::horizontal(1)),
         )
-        .highlight_style(Style::default()) // We handle highlighting in items
+        .row_highlight_style(Style::default()) // We handle highlighting in rows
         .highlight_symbol("");
 
-    frame.render_stateful_widget(list, chunks[1], &mut app.secrets_state.clone());
+    frame.render_stateful_widget(table, chunks[1], &mut app.secrets_state.clone());
+}
+
+/// Renders one cell of the secrets table.
+fn secret_cell(
+    app: &App,
+    column: Column,
+    idx: usize,
+    secret: &SecretInfo,
+    is_selected: bool,
+    now: i64,
+) -> Line<'static> {
+    let theme = &app.theme;
+    let style = if is_selected {
+        theme.selected()
+    } else {
+        Style::default()
+    };
+    let colored = |color| {
+        style.fg(if is_selected {
+            theme.selection_text
+        } else {
+            color
+        })
+    };
+    let muted = |text: String| Line::from(Span::styled(text, colored(theme.muted)));
+
+    match column {
+        Column::Index => Line::from(Span::styled(
+            format!("{:>3}", idx + 1),
+            Style::default().fg(theme.accent),
+        )),
+        Column::Name => {
+            let mut spans = vec![
+                Span::styled(
+                    "",
+                    Style::default().fg(if is_selected {
+                        theme.warning
+                    } else {
+                        theme.primary
+                    }),
+                ),
+                Span::styled(" ", style),
+                Span::styled(
+                    secret.short_name.clone(),
+                    style.add_modifier(Modifier::BOLD),
+                ),
+            ];
+            // Without an expiration column, the countdown follows the name
+            if let (Some(expire_time), false) = (
+                secret.expire_time,
+                app.config.columns.contains(&Column::Expiration),
+            ) {
+                spans.push(Span::styled(
+                    format!(" expires {}", countdown(expire_time, now)),
+                    colored(theme.expiration(urgency(expire_time, now))),
+                ));
+            }
+            Line::from(spans)
+        }
+        Column::Created => muted(secret.create_time.clone()),
+        Column::Labels => muted(
+            secret
+                .labels
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Column::Replication => muted(match &secret.replication {
+            ReplicationPolicy::Automatic => "automatic".to_string(),
+            ReplicationPolicy::UserManaged(locations) => locations.join(","),
+        }),
+        Column::Location => {
+            let color = if secret.location.is_some() {
+                theme.secondary
+            } else {
+                theme.muted
+            };
+            Line::from(Span::styled(
+                format!("[{}]", location_label(secret.location.as_deref())),
+                colored(color),
+            ))
+        }
+        Column::LatestVersion => muted(
+            secret
+                .versions
+                .and_then(|v| v.latest)
+                .map_or_else(|| "-".to_string(), |latest| format!("v{latest}")),
+        ),
+        Column::EnabledVersions => muted(
+            secret
+                .versions
+                .map_or_else(|| "-".to_string(), |v| v.enabled.to_string()),
+        ),
+        Column::Rotation => muted(
+            secret
+                .rotation
+                .as_ref()
+                .and_then(|r| r.next_rotation_time.clone())
+                .unwrap_or_default(),
+        ),
+        Column::Expiration => secret
+            .expire_time
+            .map_or_else(Line::default, |expire_time| {
+                Line::from(Span::styled(
+                    countdown(expire_time, now),
+                    colored(theme.expiration(urgency(expire_time, now))),
+                ))
+            }),
+        Column::Topics => muted(secret.topics.len().to_string()),
+    }
+}
+
+/// Returns the width of each column, or `None` for columns left out because
+/// the table would not fit in `available` cells.
+///
+/// Columns are as wide as their header and widest cell, up to
+/// `MAX_COLUMN_WIDTH`; the name column is never left out, and columns are
+/// dropped from the right until the rest fits.
+fn fit_columns(columns: &[Column], cells: &[Vec<Line>], available: u16) -> Vec<Option<u16>> {
+    let mut widths: Vec<Option<u16>> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            // Room for the sort arrow
+            let header = column.title().len() + 2;
+            let widest = cells.iter().map(|row| row[i].width()).max().unwrap_or(0);
+            let width = header.max(widest).min(MAX_COLUMN_WIDTH);
+            Some(u16::try_from(width).unwrap_or(u16::MAX))
+        })
+        .collect();
+
+    let total = |widths: &[Option<u16>]| {
+        let shown: Vec<u16> = widths.iter().flatten().copied().collect();
+        let spacing = u16::try_from(shown.len().saturating_sub(1)).unwrap_or(u16::MAX);
+        shown
+            .iter()
+            .fold(spacing.saturating_mul(COLUMN_SPACING), |sum, w| {
+                sum.saturating_add(*w)
+            })
+    };
+    for i in (0..columns.len()).rev() {
+        if total(&widths) <= available {
+            break;
+        }
+        if columns[i] != Column::Name {
+            widths[i] = None;
+        }
+    }
+    widths
 }
 
 /// Draws the versions list.
 pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Handle empty state
+    if app.versions.is_empty() && app.filter.versions.is_some() {
+        draw_empty_state(
+            frame,
+            area,
+            "No versions match the filter",
+            app.filter.versions.as_deref().unwrap_or_default(),
+            &clear_filter_hint(app, Scope::SecretDetail, "versions"),
+            &app.theme,
+        );
+        return;
+    }
     if app.versions.is_empty() {
         draw_empty_state(
             frame,
             area,
             "No versions yet",
-            "Press 'a' to add the first version",
+            &app.keymap
+                .key(Scope::SecretDetail, &Action::NewVersion)
+                .map_or_else(
+                    || "Add a version to store the first value".to_string(),
+                    |key| format!("Press '{key}' to add the first version"),
+                ),
             "A secret needs at least one version to store a value",
+            &app.theme,
         );
         return;
     }
@@ -143,23 +382,27 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
         .map(|(idx, v)| {
             let is_selected = app.versions_state.selected() == Some(idx);
 
+            let now = Utc::now().timestamp();
             let (state_icon, state_color) = match v.state {
-                VersionState::Enabled => ("", colors::SUCCESS),
-                VersionState::Disabled => ("", colors::WARNING),
-                VersionState::Destroyed => ("", colors::ERROR),
-                VersionState::Unknown => ("?", colors::MUTED),
+                _ if v.scheduled_destroy_time.is_some() => ("", theme.error),
+                VersionState::Enabled => ("", theme.success),
+                VersionState::Disabled => ("", theme.warning),
+                VersionState::Destroyed => ("", theme.error),
+                VersionState::Unknown => ("?", theme.muted),
             };
 
             let base_style = if is_selected {
-                Style::default()
-                    .bg(colors::SELECTION)
-                    .fg(colors::SELECTION_TEXT)
+                theme.selected()
             } else {
                 Style::default()
             };
 
             let version_str = format!("v{:<4}", v.version);
-            let state_str = v.state.to_string();
+            let state_str = if v.scheduled_destroy_time.is_some() {
+                "Scheduled".to_string()
+            } else {
+                v.state.to_string()
+            };
             let create_time = v.create_time.clone();
 
             let mut spans = vec![
@@ -171,7 +414,7 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
                 Span::styled(
                     format!("{state_str:<10}"),
                     base_style.fg(if is_selected {
-                        colors::SELECTION_TEXT
+                        theme.selection_text
                     } else {
                         state_color
                     }),
@@ -180,9 +423,9 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
                 Span::styled(
                     format!("created {create_time}"),
                     base_style.fg(if is_selected {
-                        colors::SELECTION_TEXT
+                        theme.selection_text
                     } else {
-                        colors::MUTED
+                        theme.muted
                     }),
                 ),
             ];
@@ -192,31 +435,65 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
                 spans.push(Span::styled(
                     "  destroyed ",
                     base_style.fg(if is_selected {
-                        colors::SELECTION_TEXT
+                        theme.selection_text
                     } else {
-                        colors::ERROR
+                        theme.error
                     }),
                 ));
                 spans.push(Span::styled(
                     destroy_time,
                     base_style.fg(if is_selected {
-                        colors::SELECTION_TEXT
+                        theme.selection_text
                     } else {
-                        colors::MUTED
+                        theme.muted
                     }),
                 ));
             }
 
-            // Add scheduled destroy time if pending destruction
-            if let Some(scheduled) = &v.scheduled_destroy_time {
+            // Add the time left if pending destruction
+            if let Some(scheduled) = v.scheduled_destroy_time {
                 spans.push(Span::styled("  ", base_style));
-                spans.push(Span::styled("", Style::default().fg(colors::WARNING)));
                 spans.push(Span::styled(
-                    format!(" destroys {scheduled}"),
+                    "",
+                    Style::default().fg(theme.expiration(urgency(scheduled, now))),
+                ));
+                spans.push(Span::styled(
+                    format!(
+                        " destroyed {} ({})",
+                        countdown(scheduled, now),
+                        format_time(scheduled)
+                    ),
                     base_style.fg(if is_selected {
-                        colors::SELECTION_TEXT
+                        theme.selection_text
                     } else {
-                        colors::WARNING
+                        theme.expiration(urgency(scheduled, now))
+                    }),
+                ));
+                if let Some(key) = app.keymap.key(Scope::SecretDetail, &Action::Enable) {
+                    spans.push(Span::styled(
+                        format!(" - {key} cancels"),
+                        base_style.fg(if is_selected {
+                            theme.selection_text
+                        } else {
+                            theme.muted
+                        }),
+                    ));
+                }
+            }
+
+            // Add the key versions a CMEK-protected payload is encrypted with
+            if !v.kms_key_versions.is_empty() {
+                let keys: Vec<String> = v
+                    .kms_key_versions
+                    .iter()
+                    .map(|k| short_kms_name(k))
+                    .collect();
+                spans.push(Span::styled(
+                    format!("  key {}", keys.join(",")),
+                    base_style.fg(if is_selected {
+                        theme.selection_text
+                    } else {
+                        theme.secondary
                     }),
                 ));
             }
@@ -224,7 +501,7 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
             // Add checksum indicator
             if v.has_checksum {
                 spans.push(Span::styled("  ", base_style));
-                spans.push(Span::styled("", Style::default().fg(colors::SECONDARY)));
+                spans.push(Span::styled("", Style::default().fg(theme.secondary)));
             }
 
             let content = Line::from(spans);
@@ -237,15 +514,16 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(colors::BORDER))
+                .border_style(Style::default().fg(theme.border))
                 .border_set(symbols::border::ROUNDED)
                 .title(Line::from(vec![
                     Span::styled(" ", Style::default()),
                     Span::styled(
                         format!("{}", app.versions.len()),
-                        Style::default().fg(colors::SECONDARY).bold(),
+                        Style::default().fg(theme.secondary).bold(),
                     ),
-                    Span::styled(" versions ", Style::default().fg(Color::White)),
+                    Span::styled(" versions ", Style::default().fg(theme.text)),
+                    filter_span(app.filter.versions.as_deref(), theme),
                 ]))
                 .padding(Padding::horizontal(1)),
         )
@@ -253,3 +531,19 @@ pub fn draw_versions_list(frame: &mut Frame, area: Rect, app: &App) {
 
     frame.render_stateful_widget(list, area, &mut app.versions_state.clone());
 }
+
+/// Returns the empty-state hint for clearing a filter with the filter key.
+fn clear_filter_hint(app: &App, scope: Scope, listed: &str) -> String {
+    match app.keymap.key(scope, &Action::Filter) {
+        Some(key) => format!("Press '{key}' and submit an empty filter to list all {listed}"),
+        None => format!("Clear the filter to list all {listed}"),
+    }
+}
+
+/// Returns the title span showing an active API filter expression.
+fn filter_span<'a>(filter: Option<&str>, theme: &Theme) -> Span<'a> {
+    Span::styled(
+        filter.map_or_else(String::new, |f| format!("[{f}] ")),
+        Style::default().fg(theme.accent),
+    )
+}
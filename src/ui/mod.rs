@@ -4,18 +4,22 @@
 //! Each view is rendered by a separate submodule for clarity.
 
 mod auth;
-mod colors;
 mod detail;
 mod dialogs;
 mod empty;
 mod header;
 mod help;
+mod history;
+mod iam;
 mod lists;
 mod status;
+mod tags;
+pub mod theme;
 mod utils;
 
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
 
@@ -25,11 +29,17 @@ use crate::constants::layout;
 // Re-export submodule draw functions for internal use
 use auth::draw_auth_required;
 use detail::draw_secret_detail;
-use dialogs::{draw_confirm_dialog, draw_input_dialog, draw_project_selector};
+use dialogs::{
+    draw_config_selector, draw_confirm_dialog, draw_conflict_dialog, draw_input_dialog,
+    draw_location_selector, draw_project_selector,
+};
 use header::draw_header;
 use help::draw_help_overlay;
+use history::draw_history;
 use lists::draw_secrets_list;
-use status::{draw_commands_bar, draw_status_bar};
+use status::{draw_busy_status, draw_commands_bar, draw_status_bar};
+
+pub use status::Busy;
 
 /// Main draw function - dispatches to specific view renderers.
 pub fn draw(frame: &mut Frame, app: &App) {
@@ -49,9 +59,12 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
     // Draw the main content based on current view
     match &app.current_view {
-        View::AuthRequired => draw_auth_required(frame, chunks[1]),
+        View::AuthRequired => draw_auth_required(frame, chunks[1], app),
         View::SecretsList => draw_secrets_list(frame, chunks[1], app),
-        View::SecretDetail => draw_secret_detail(frame, chunks[1], app),
+        View::SecretDetail | View::SecretIam | View::SecretTags => {
+            draw_secret_detail(frame, chunks[1], app);
+        }
+        View::History => draw_history(frame, chunks[1], app),
         View::Input(mode) => {
             // Draw the underlying view first
             if let Some(View::SecretsList) = &app.previous_view {
@@ -70,7 +83,15 @@ pub fn draw(frame: &mut Frame, app: &App) {
                 draw_secret_detail(frame, chunks[1], app);
             }
             // Then draw the confirmation dialog on top
-            draw_confirm_dialog(frame, action);
+            draw_confirm_dialog(frame, action, app);
+        }
+        View::Conflict(conflict) => {
+            if let Some(View::SecretsList) = &app.previous_view {
+                draw_secrets_list(frame, chunks[1], app);
+            } else {
+                draw_secret_detail(frame, chunks[1], app);
+            }
+            draw_conflict_dialog(frame, conflict, app);
         }
         View::ProjectSelector => {
             // Draw the secrets list in the background
@@ -78,6 +99,14 @@ pub fn draw(frame: &mut Frame, app: &App) {
             // Then draw the project selector dialog on top
             draw_project_selector(frame, app);
         }
+        View::ConfigSelector => {
+            draw_secrets_list(frame, chunks[1], app);
+            draw_config_selector(frame, app);
+        }
+        View::LocationSelector => {
+            draw_secrets_list(frame, chunks[1], app);
+            draw_location_selector(frame, app);
+        }
     }
 
     // Draw the commands bar (shows available actions)
@@ -88,6 +117,25 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
     // Draw help overlay if enabled
     if app.show_help {
-        draw_help_overlay(frame);
+        draw_help_overlay(frame, &app.keymap, &app.theme);
+    }
+}
+
+/// Redraws `snapshot`, the last frame drawn before a call started, with the
+/// status bar showing that the call is still running.
+///
+/// The running call borrows the app, so the views cannot be drawn from it
+/// until the call returns. After a resize only the status bar is drawn.
+pub fn draw_busy(frame: &mut Frame, snapshot: &Buffer, busy: &Busy) {
+    let area = frame.area();
+    if snapshot.area == area {
+        frame.buffer_mut().clone_from(snapshot);
     }
+    let height = layout::STATUS_BAR_HEIGHT.min(area.height);
+    let status_area = Rect {
+        y: area.bottom() - height,
+        height,
+        ..area
+    };
+    draw_busy_status(frame, status_area, busy);
 }
@@ -2,81 +2,128 @@
 
 use ratatui::{
     layout::Rect,
-    style::{Style, Stylize},
+    style::{Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-use crate::app::{App, View};
+use crate::app::App;
+use crate::keymap::Scope;
+use crate::ui::theme::Theme;
 
-use super::colors;
+/// Spinner frames animating the status bar while a call runs.
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// What the status bar shows while a call is running.
+pub struct Busy {
+    /// Color theme, copied because the running call borrows the app
+    pub theme: Theme,
+    /// Key that cancels the call, if it may be cancelled
+    pub cancel_key: Option<String>,
+    /// The latest retry of the call
+    pub progress: Option<String>,
+    /// Frames drawn so far, animating the spinner
+    pub ticks: usize,
+}
 
 /// Draws the commands bar showing available actions for current view.
+///
+/// The entries come from the keymap, so they always match the active keys.
+/// Commands the caller lacks the permission for are greyed out and name
+/// the missing permission.
 pub fn draw_commands_bar(frame: &mut Frame, area: Rect, app: &App) {
-    let commands = get_commands_for_view(&app.current_view);
+    let theme = &app.theme;
+    let commands = app
+        .keymap
+        .commands(Scope::from(&app.current_view), app.is_read_only());
 
     let mut spans: Vec<Span> = vec![Span::styled(" ", Style::default())];
 
-    for (i, (key, desc)) in commands.iter().enumerate() {
+    for (i, command) in commands.into_iter().enumerate() {
         if i > 0 {
-            spans.push(Span::styled(" │ ", Style::default().fg(colors::BORDER)));
+            spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        }
+        let missing: Vec<Option<&str>> = command
+            .actions
+            .iter()
+            .map(|action| app.missing_permission(action))
+            .collect();
+        if missing.iter().all(Option::is_some) {
+            let mut permissions: Vec<&str> = missing.into_iter().flatten().collect();
+            permissions.dedup();
+            let style = Style::default()
+                .fg(theme.dim)
+                .add_modifier(Modifier::CROSSED_OUT);
+            spans.push(Span::styled(command.keys, style));
+            spans.push(Span::styled(" ", Style::default()));
+            spans.push(Span::styled(command.hint, style));
+            spans.push(Span::styled(
+                format!(" (needs {})", permissions.join(", ")),
+                Style::default().fg(theme.muted),
+            ));
+        } else {
+            spans.push(Span::styled(
+                command.keys,
+                Style::default().fg(theme.key).bold(),
+            ));
+            spans.push(Span::styled(" ", Style::default()));
+            spans.push(Span::styled(command.hint, Style::default().fg(theme.muted)));
         }
-        spans.push(Span::styled(*key, Style::default().fg(colors::KEY).bold()));
-        spans.push(Span::styled(" ", Style::default()));
-        spans.push(Span::styled(*desc, Style::default().fg(colors::MUTED)));
     }
 
     let block = Block::default()
         .borders(Borders::TOP)
-        .border_style(Style::default().fg(colors::BORDER));
+        .border_style(Style::default().fg(theme.border));
 
     let commands_widget = Paragraph::new(Line::from(spans)).block(block);
 
     frame.render_widget(commands_widget, area);
 }
 
-/// Returns the list of commands available for a given view.
-fn get_commands_for_view(view: &View) -> Vec<(&'static str, &'static str)> {
-    match view {
-        View::AuthRequired => vec![("Enter", "authenticate"), ("q", "quit")],
-        View::SecretsList => vec![
-            ("j/k", "navigate"),
-            ("Enter", "view"),
-            ("n", "new secret"),
-            ("p", "switch project"),
-            ("r", "refresh"),
-            ("?", "help"),
-            ("q", "quit"),
-        ],
-        View::ProjectSelector => vec![("j/k", "navigate"), ("Enter", "select"), ("Esc", "cancel")],
-        View::SecretDetail => vec![
-            ("b", "back"),
-            ("j/k", "navigate"),
-            ("s", "show"),
-            ("c", "copy"),
-            ("a", "add"),
-            ("e/x", "enable/disable"),
-            ("p", "project"),
-        ],
-        View::Input(_) => vec![("Enter", "submit"), ("Esc", "cancel")],
-        View::Confirm(_) => vec![("Enter", "confirm"), ("Esc", "cancel")],
-    }
-}
-
 /// Draws the status bar at the bottom (for messages).
 pub fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let (text, style) = if let Some(status) = &app.status {
         let style = if status.is_error {
-            Style::default().fg(colors::ERROR)
+            Style::default().fg(theme.error)
         } else {
-            Style::default().fg(colors::SUCCESS)
+            Style::default().fg(theme.success)
         };
         (format!(" {} ", status.text), style)
     } else {
-        (" Ready".to_string(), Style::default().fg(colors::MUTED))
+        (" Ready".to_string(), Style::default().fg(theme.muted))
     };
 
     let status = Paragraph::new(text).style(style);
     frame.render_widget(status, area);
 }
+
+/// Draws the status bar while a call is running: a spinner, the latest
+/// retry if there was one, and the key that cancels the call.
+pub fn draw_busy_status(frame: &mut Frame, area: Rect, busy: &Busy) {
+    let theme = &busy.theme;
+    let spinner = SPINNER[busy.ticks % SPINNER.len()];
+    let mut spans = vec![Span::styled(
+        format!(" {spinner} "),
+        Style::default().fg(theme.warning),
+    )];
+    match &busy.progress {
+        Some(progress) => spans.push(Span::styled(
+            progress.clone(),
+            Style::default().fg(theme.warning),
+        )),
+        None => spans.push(Span::styled("Working...", Style::default().fg(theme.muted))),
+    }
+    if let Some(key) = &busy.cancel_key {
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        spans.push(Span::styled(
+            key.clone(),
+            Style::default().fg(theme.key).bold(),
+        ));
+        spans.push(Span::styled(" cancel", Style::default().fg(theme.muted)));
+    }
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
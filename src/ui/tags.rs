@@ -0,0 +1,113 @@
+//! Tags tab rendering: the tag values bound to a secret.
+
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Padding},
+    Frame,
+};
+
+use crate::app::App;
+use crate::event::Action;
+use crate::keymap::Scope;
+
+use super::empty::draw_empty_state;
+
+/// Draws the tag bindings of the current secret.
+pub fn draw_tag_bindings(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(bindings) = &app.tag_bindings else {
+        draw_empty_state(
+            frame,
+            area,
+            "Tags not loaded",
+            &app.keymap
+                .key(Scope::SecretTags, &Action::Refresh)
+                .map_or_else(
+                    || "Refresh to load them".to_string(),
+                    |key| format!("Press '{key}' to load them"),
+                ),
+            "Listing tags needs resourcemanager.tagValueBindings permissions",
+            theme,
+        );
+        return;
+    };
+    if bindings.is_empty() {
+        draw_empty_state(
+            frame,
+            area,
+            "No tags bound to this secret",
+            &match app.keymap.key(Scope::SecretTags, &Action::BindTag) {
+                Some(key) if !app.is_read_only() => format!("Press '{key}' to bind a tag value"),
+                _ => "Tags may still be inherited from the project".to_string(),
+            },
+            "Tags inherited from the project or organization are not listed",
+            theme,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = bindings
+        .iter()
+        .enumerate()
+        .map(|(idx, binding)| {
+            let is_selected = app.tags_state.selected() == Some(idx);
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+            let muted = style.fg(if is_selected {
+                theme.selection_text
+            } else {
+                theme.muted
+            });
+
+            // Namespaced values read as parent/key/value
+            let mut spans = vec![Span::styled("   ", style)];
+            match binding.namespaced_name.rsplitn(3, '/').collect::<Vec<_>>()[..] {
+                [value, key, parent] => {
+                    spans.push(Span::styled(format!("{parent}/"), muted));
+                    spans.push(Span::styled(
+                        key.to_string(),
+                        style.fg(if is_selected {
+                            theme.selection_text
+                        } else {
+                            theme.accent
+                        }),
+                    ));
+                    spans.push(Span::styled("=", muted));
+                    spans.push(Span::styled(value.to_string(), style.bold()));
+                }
+                _ => spans.push(Span::styled(binding.label().to_string(), style.bold())),
+            }
+            if !binding.namespaced_name.is_empty() {
+                spans.push(Span::styled(format!("  {}", binding.tag_value), muted));
+            }
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .border_set(symbols::border::ROUNDED)
+                .title(Line::from(vec![
+                    Span::styled(" ", Style::default()),
+                    Span::styled(
+                        format!("{}", bindings.len()),
+                        Style::default().fg(theme.secondary).bold(),
+                    ),
+                    Span::styled(" tags ", Style::default().fg(theme.text)),
+                ]))
+                .padding(Padding::horizontal(1)),
+        )
+        .highlight_style(Style::default())
+        .highlight_symbol("");
+
+    frame.render_stateful_widget(list, area, &mut app.tags_state.clone());
+}
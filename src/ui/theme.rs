@@ -0,0 +1,222 @@
+//! Color themes for the UI.
+//!
+//! Renderers take every color from a `Theme` rather than hardcoding them.
+//! Built-in themes cover dark and light terminals, high contrast and plain
+//! 16-color terminals; `NO_COLOR` selects a monochrome theme.
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::expiration::Urgency;
+
+/// A set of UI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Primary accent color (used for titles, highlights)
+    pub primary: Color,
+    /// Secondary accent color (used for active elements)
+    pub secondary: Color,
+    /// Background for selected items
+    pub selection: Color,
+    /// Text on selection
+    pub selection_text: Color,
+    /// Muted text color
+    pub muted: Color,
+    /// Error/danger color
+    pub error: Color,
+    /// Warning color
+    pub warning: Color,
+    /// Success color
+    pub success: Color,
+    /// Border color
+    pub border: Color,
+    /// Key highlight color (for keyboard shortcuts)
+    pub key: Color,
+    /// Accent color for icons and decorations
+    pub accent: Color,
+    /// Regular text color
+    pub text: Color,
+    /// Faint decorations (header separators)
+    pub dim: Color,
+    /// Low-emphasis text (header labels)
+    pub subtle: Color,
+    /// Mark the selection with reverse video instead of colors
+    pub reverse_selection: bool,
+}
+
+impl Theme {
+    /// Names of the built-in themes.
+    pub const BUILTIN: [&'static str; 4] = ["dark", "light", "high-contrast", "16-color"];
+
+    /// Looks up a built-in theme by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "16-color" => Some(Self::ansi16()),
+            _ => None,
+        }
+    }
+
+    /// The default theme, for dark terminal backgrounds.
+    pub const fn dark() -> Self {
+        Self {
+            primary: Color::Rgb(56, 189, 248),   // Bright cyan
+            secondary: Color::Rgb(52, 211, 153), // Bright emerald
+            selection: Color::Rgb(99, 102, 241), // Indigo
+            selection_text: Color::White,
+            muted: Color::Rgb(148, 163, 184),  // Brighter gray
+            error: Color::Rgb(251, 113, 133),  // Bright rose
+            warning: Color::Rgb(251, 191, 36), // Bright amber
+            success: Color::Rgb(74, 222, 128), // Bright green
+            border: Color::Rgb(129, 140, 248), // Light indigo
+            key: Color::Rgb(244, 114, 182),    // Bright pink
+            accent: Color::Rgb(192, 132, 252), // Bright purple
+            text: Color::White,
+            dim: Color::Rgb(55, 65, 81),
+            subtle: Color::Rgb(75, 85, 99),
+            reverse_selection: false,
+        }
+    }
+
+    /// Darker shades that stay readable on light backgrounds.
+    pub const fn light() -> Self {
+        Self {
+            primary: Color::Rgb(2, 132, 199),     // Sky
+            secondary: Color::Rgb(5, 150, 105),   // Emerald
+            selection: Color::Rgb(199, 210, 254), // Pale indigo
+            selection_text: Color::Black,
+            muted: Color::Rgb(71, 85, 105),   // Slate
+            error: Color::Rgb(190, 18, 60),   // Rose
+            warning: Color::Rgb(180, 83, 9),  // Amber
+            success: Color::Rgb(21, 128, 61), // Green
+            border: Color::Rgb(79, 70, 229),  // Indigo
+            key: Color::Rgb(190, 24, 93),     // Pink
+            accent: Color::Rgb(126, 34, 206), // Purple
+            text: Color::Black,
+            dim: Color::Rgb(203, 213, 225),
+            subtle: Color::Rgb(100, 116, 139),
+            reverse_selection: false,
+        }
+    }
+
+    /// Saturated colors and no grays, for low vision or bright rooms.
+    pub const fn high_contrast() -> Self {
+        Self {
+            primary: Color::LightCyan,
+            secondary: Color::LightGreen,
+            selection: Color::Yellow,
+            selection_text: Color::Black,
+            muted: Color::White,
+            error: Color::LightRed,
+            warning: Color::LightYellow,
+            success: Color::LightGreen,
+            border: Color::White,
+            key: Color::LightYellow,
+            accent: Color::LightMagenta,
+            text: Color::White,
+            dim: Color::White,
+            subtle: Color::White,
+            reverse_selection: false,
+        }
+    }
+
+    /// Only the basic ANSI palette, for terminals without 256/true color.
+    pub const fn ansi16() -> Self {
+        Self {
+            primary: Color::Cyan,
+            secondary: Color::Green,
+            selection: Color::Blue,
+            selection_text: Color::White,
+            muted: Color::Gray,
+            error: Color::Red,
+            warning: Color::Yellow,
+            success: Color::Green,
+            border: Color::Blue,
+            key: Color::Magenta,
+            accent: Color::Magenta,
+            text: Color::Reset,
+            dim: Color::DarkGray,
+            subtle: Color::DarkGray,
+            reverse_selection: false,
+        }
+    }
+
+    /// No colors at all; used when `NO_COLOR` is set.
+    pub const fn monochrome() -> Self {
+        Self {
+            primary: Color::Reset,
+            secondary: Color::Reset,
+            selection: Color::Reset,
+            selection_text: Color::Reset,
+            muted: Color::Reset,
+            error: Color::Reset,
+            warning: Color::Reset,
+            success: Color::Reset,
+            border: Color::Reset,
+            key: Color::Reset,
+            accent: Color::Reset,
+            text: Color::Reset,
+            dim: Color::Reset,
+            subtle: Color::Reset,
+            reverse_selection: true,
+        }
+    }
+
+    /// Returns true if the user asked for no colors (<https://no-color.org>).
+    pub fn no_color_requested() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+    }
+
+    /// Sets one color by its config name.
+    pub fn set_color(&mut self, name: &str, color: Color) -> Result<(), String> {
+        let slot = match name {
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "selection" => &mut self.selection,
+            "selection_text" => &mut self.selection_text,
+            "muted" => &mut self.muted,
+            "error" => &mut self.error,
+            "warning" => &mut self.warning,
+            "success" => &mut self.success,
+            "border" => &mut self.border,
+            "key" => &mut self.key,
+            "accent" => &mut self.accent,
+            "text" => &mut self.text,
+            "dim" => &mut self.dim,
+            "subtle" => &mut self.subtle,
+            _ => return Err(format!("unknown theme color '{name}'")),
+        };
+        *slot = color;
+        Ok(())
+    }
+
+    /// Style for the selected row of a list.
+    pub fn selected(&self) -> Style {
+        if self.reverse_selection {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.selection).fg(self.selection_text)
+        }
+    }
+
+    /// Color of an expiration countdown.
+    pub fn expiration(&self, urgency: Urgency) -> Color {
+        match urgency {
+            Urgency::Distant => self.secondary,
+            Urgency::Soon => self.warning,
+            Urgency::Imminent => self.error,
+        }
+    }
+
+    /// Colors the header logo may be drawn in.
+    pub fn logo_colors(&self) -> [Color; 4] {
+        [self.primary, self.key, self.accent, self.secondary]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
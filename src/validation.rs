@@ -44,6 +44,51 @@ pub fn validate_secret_name(name: &str) -> ValidationResult {
     Ok(())
 }
 
+/// Validates an IAM member such as `user:ana@example.com` or `allUsers`.
+pub fn validate_iam_member(member: &str) -> ValidationResult {
+    const PREFIXES: [&str; 6] = [
+        "user:",
+        "serviceAccount:",
+        "group:",
+        "domain:",
+        "principal:",
+        "principalSet:",
+    ];
+
+    if crate::iam::is_public_member(member) {
+        return Ok(());
+    }
+    match PREFIXES.iter().find(|p| member.starts_with(*p)) {
+        Some(prefix) if member.len() > prefix.len() => Ok(()),
+        Some(_) => Err(format!("Member '{member}' is missing an identity")),
+        None => Err(format!(
+            "Member '{member}' must start with user:, serviceAccount:, group:, domain:, \
+             principal: or principalSet:"
+        )),
+    }
+}
+
+/// Validates a tag value such as `123456789/env/prod` (organization ID or
+/// project ID, key and value) or a value ID such as `tagValues/281484`.
+pub fn validate_tag_value(value: &str) -> ValidationResult {
+    if let Some(id) = value.strip_prefix("tagValues/") {
+        if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(());
+        }
+        return Err(format!(
+            "Tag value ID '{value}' must be tagValues/ and a number"
+        ));
+    }
+    let parts: Vec<&str> = value.split('/').collect();
+    if parts.len() == 3 && parts.iter().all(|p| !p.is_empty()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Tag value '{value}' must be parent/key/value (e.g. 123456789/env/prod) or tagValues/ID"
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +135,23 @@ mod tests {
         assert!(validate_secret_name("日本語").is_err());
         assert!(validate_secret_name("emoji🎉").is_err());
     }
+
+    #[test]
+    fn test_iam_members() {
+        assert!(validate_iam_member("user:ana@example.com").is_ok());
+        assert!(validate_iam_member("serviceAccount:app@p.iam.gserviceaccount.com").is_ok());
+        assert!(validate_iam_member("allUsers").is_ok());
+        assert!(validate_iam_member("ana@example.com").is_err());
+        assert!(validate_iam_member("user:").is_err());
+    }
+
+    #[test]
+    fn test_tag_values() {
+        assert!(validate_tag_value("123456789/env/prod").is_ok());
+        assert!(validate_tag_value("my-project/team/payments").is_ok());
+        assert!(validate_tag_value("tagValues/281484").is_ok());
+        assert!(validate_tag_value("env/prod").is_err());
+        assert!(validate_tag_value("123456789//prod").is_err());
+        assert!(validate_tag_value("tagValues/prod").is_err());
+    }
 }